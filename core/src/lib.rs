@@ -12,6 +12,21 @@ pub enum ReadStatus {
 }
 
 impl ReadStatus {
+    /// Calls `f` if read status is [`ReadStatus::NotDone`], otherwise returns
+    /// [`ReadStatus::Done`].
+    ///
+    /// This allows chaining reads that should stop as soon as one is done,
+    /// without `f` being called after reading has finished.
+    pub fn and_then<F>(self, f: F) -> io::Result<Self>
+    where
+        F: FnOnce() -> io::Result<Self>,
+    {
+        match self {
+            Self::Done => Ok(Self::Done),
+            Self::NotDone => f(),
+        }
+    }
+
     /// Returns the read status of a reader.
     ///
     /// # Returns
@@ -42,3 +57,55 @@ impl ReadStatus {
         matches!(self, Self::NotDone)
     }
 }
+
+impl From<ReadStatus> for bool {
+    /// Returns `true` if read status is [`ReadStatus::NotDone`], that is, if
+    /// data was read.
+    fn from(status: ReadStatus) -> Self {
+        status.is_not_done()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_and_then_short_circuits_on_done() {
+        let mut called = false;
+        let status = ReadStatus::Done.and_then(|| {
+            called = true;
+            Ok(ReadStatus::NotDone)
+        });
+
+        assert_eq!(status.unwrap(), ReadStatus::Done);
+        assert!(!called);
+    }
+
+    #[test]
+    fn test_and_then_calls_on_not_done() {
+        assert_eq!(
+            ReadStatus::NotDone
+                .and_then(|| Ok(ReadStatus::Done))
+                .unwrap(),
+            ReadStatus::Done
+        );
+        assert_eq!(
+            ReadStatus::NotDone
+                .and_then(|| Ok(ReadStatus::NotDone))
+                .unwrap(),
+            ReadStatus::NotDone
+        );
+
+        let error = ReadStatus::NotDone
+            .and_then(|| Err(io::Error::from(io::ErrorKind::UnexpectedEof)))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_into_bool() {
+        assert!(bool::from(ReadStatus::NotDone));
+        assert!(!bool::from(ReadStatus::Done));
+    }
+}
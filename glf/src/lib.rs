@@ -19,7 +19,7 @@
 
 pub use angsd_io_core::ReadStatus;
 
-pub(self) type Endian = byteorder::LittleEndian;
+type Endian = byteorder::LittleEndian;
 
 mod reader;
 pub use reader::{BgzfReader, Reader};
@@ -1,7 +1,7 @@
 use std::{
     fs,
     io::{self, Read},
-    mem,
+    iter, mem,
     path::Path,
 };
 
@@ -9,7 +9,7 @@ use byteorder::ReadBytesExt;
 
 use crate::ReadStatus;
 
-use super::{Endian, Record};
+use super::{record::SIZE, Endian, Genotype, Record};
 
 /// A BGZF GLF reader.
 ///
@@ -26,6 +26,57 @@ impl<R> Reader<R>
 where
     R: io::BufRead,
 {
+    /// Returns an iterator over the likelihoods of a single genotype for all remaining records.
+    ///
+    /// The likelihoods of all other genotypes are skipped without being decoded. The iterator
+    /// stops after the first error.
+    pub fn column(&mut self, genotype: Genotype) -> impl Iterator<Item = io::Result<f64>> + '_ {
+        let before = genotype as usize;
+        let after = SIZE - before - 1;
+
+        let mut failed = false;
+
+        iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+
+            let result = ReadStatus::check(&mut self.inner).and_then(|status| {
+                if status.is_done() {
+                    return Ok(None);
+                }
+
+                skip_values_unchecked(&mut self.inner, before)?;
+                let value = self.inner.read_f64::<Endian>()?;
+                skip_values_unchecked(&mut self.inner, after)?;
+
+                Ok(Some(value))
+            });
+
+            failed = result.is_err();
+            result.transpose()
+        })
+    }
+
+    /// Counts the remaining records.
+    ///
+    /// The remaining records are skipped without being decoded, so that the reader is exhausted
+    /// afterwards. Returns an error if the remaining data does not consist of a whole number of
+    /// records.
+    pub fn count_records(&mut self) -> io::Result<usize> {
+        let bytes = io::copy(&mut self.inner, &mut io::sink())?;
+        let record_bytes = mem::size_of::<Record>() as u64;
+
+        if bytes % record_bytes == 0 {
+            Ok((bytes / record_bytes) as usize)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "trailing data does not make up a whole record",
+            ))
+        }
+    }
+
     /// Returns a mutable reference to the inner reader.
     pub fn get_mut(&mut self) -> &mut R {
         &mut self.inner
@@ -59,6 +110,79 @@ where
         read_record_unchecked(&mut self.inner, record).map(|()| ReadStatus::NotDone)
     }
 
+    /// Reads a single record with `n` values.
+    ///
+    /// This allows reading GLF files with a number of genotype likelihoods per site other than the
+    /// ten of the diploid, diallelic case, in which case [`Self::read_record`] should be used.
+    /// The buffer is resized to hold exactly `n` values.
+    ///
+    /// Returns an error if `n` is zero, since no progress could be made reading such records.
+    pub fn read_record_n(&mut self, n: usize, buf: &mut Vec<f64>) -> io::Result<ReadStatus> {
+        if n == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot read GLF records with zero values",
+            ));
+        }
+
+        if ReadStatus::check(&mut self.inner)?.is_done() {
+            return Ok(ReadStatus::Done);
+        }
+
+        buf.resize(n, 0.0);
+        self.inner
+            .read_f64_into::<Endian>(buf)
+            .map(|()| ReadStatus::NotDone)
+    }
+
+    /// Reads up to `n_records` records into a flat buffer.
+    ///
+    /// The values of each record are appended to `out` in order, so that each record occupies
+    /// ten consecutive values. This avoids constructing [`Record`]s, which may be useful for bulk
+    /// loading. Returns the number of records read, which is less than `n_records` only if the
+    /// reader is exhausted.
+    pub fn read_into_flat(&mut self, out: &mut Vec<f64>, n_records: usize) -> io::Result<usize> {
+        out.reserve(n_records * SIZE);
+
+        for n in 0..n_records {
+            if ReadStatus::check(&mut self.inner)?.is_done() {
+                return Ok(n);
+            }
+
+            let start = out.len();
+            out.resize(start + SIZE, 0.0);
+            if let Err(e) = self.inner.read_f64_into::<Endian>(&mut out[start..]) {
+                out.truncate(start);
+                return Err(e);
+            }
+        }
+
+        Ok(n_records)
+    }
+
+    /// Returns an iterator over the remaining records.
+    ///
+    /// The iterator stops after the first error.
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Record>> + '_ {
+        let mut done = false;
+
+        iter::from_fn(move || {
+            if done {
+                return None;
+            }
+
+            let mut record = Record::new();
+            let result = self.read_record(&mut record);
+
+            done = !matches!(result, Ok(ReadStatus::NotDone));
+            match result {
+                Ok(ReadStatus::NotDone) => Some(Ok(record)),
+                Ok(ReadStatus::Done) => None,
+                Err(e) => Some(Err(e)),
+            }
+        })
+    }
+
     /// Reads multiple records.
     pub fn read_records(&mut self, records: &mut [Record]) -> io::Result<ReadStatus> {
         if ReadStatus::check(&mut self.inner)?.is_done() {
@@ -101,6 +225,30 @@ where
 
         Ok(ReadStatus::NotDone)
     }
+
+    /// Writes all remaining records as delimited text.
+    ///
+    /// Each record is written on a separate line, with values separated by `sep` and formatted
+    /// with `precision` decimal places. Using `":"` as separator gives the same format as the
+    /// [`Display`](std::fmt::Display) implementation of [`Record`], which may be parsed back.
+    pub fn write_text<W>(&mut self, writer: &mut W, sep: &str, precision: usize) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let mut record = Record::new();
+
+        while self.read_record(&mut record)?.is_not_done() {
+            let (first, rest) = record.as_slice().split_first().expect("non-empty record");
+
+            write!(writer, "{first:.precision$}")?;
+            for value in rest.iter() {
+                write!(writer, "{sep}{value:.precision$}")?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Reader<io::BufReader<fs::File>> {
@@ -168,3 +316,209 @@ where
 
     Ok(())
 }
+
+fn skip_values_unchecked<R>(reader: &mut R, values: usize) -> io::Result<()>
+where
+    R: io::BufRead,
+{
+    let skip_bytes = (values * mem::size_of::<f64>()) as u64;
+
+    if io::copy(&mut reader.take(skip_bytes), &mut io::sink())? == skip_bytes {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "failed to skip record values",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Writer;
+
+    fn reader_from_records(records: &[Record]) -> io::Result<Reader<io::Cursor<Vec<u8>>>> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_records(records)?;
+
+        Ok(Reader::new(io::Cursor::new(writer.into_inner())))
+    }
+
+    #[test]
+    fn test_column() -> io::Result<()> {
+        let records = (0..4)
+            .map(|i| Record::from([f64::from(i); 10].map(|x| x * 10.)))
+            .map(|mut record| {
+                record[Genotype::CG] += 1.;
+                record[Genotype::TT] += 2.;
+                record
+            })
+            .collect::<Vec<_>>();
+
+        for genotype in [Genotype::AA, Genotype::CG, Genotype::TT] {
+            let mut reader = reader_from_records(&records)?;
+            let column = reader.column(genotype).collect::<io::Result<Vec<_>>>()?;
+
+            let expected = records.iter().map(|r| r[genotype]).collect::<Vec<_>>();
+            assert_eq!(column, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_into_flat() -> io::Result<()> {
+        let records = (0..5)
+            .map(|i| Record::from([f64::from(i); 10].map(|x| x + 0.5)))
+            .collect::<Vec<_>>();
+
+        let mut expected = vec![Record::new(); records.len()];
+        reader_from_records(&records)?.read_records(&mut expected)?;
+        let expected = expected
+            .iter()
+            .flat_map(|record| record.as_slice())
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut reader = reader_from_records(&records)?;
+        let mut flat = Vec::new();
+
+        assert_eq!(reader.read_into_flat(&mut flat, 3)?, 3);
+        assert_eq!(flat, expected[..3 * SIZE]);
+
+        assert_eq!(reader.read_into_flat(&mut flat, 3)?, 2);
+        assert_eq!(flat, expected);
+
+        assert_eq!(reader.read_into_flat(&mut flat, 3)?, 0);
+        assert_eq!(flat, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_and_count_records() -> io::Result<()> {
+        let records = (0..3)
+            .map(|i| Record::from([f64::from(i); 10]))
+            .collect::<Vec<_>>();
+
+        let read = reader_from_records(&records)?
+            .records()
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(read, records);
+
+        let mut reader = reader_from_records(&records)?;
+        reader.skip_record()?;
+        assert_eq!(reader.count_records()?, read.len() - 1);
+        assert_eq!(reader.count_records()?, 0);
+        assert!(reader.records().next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_truncated() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_record(&Record::from([1.; 10]))?;
+        let mut data = writer.into_inner();
+        data.extend([0; 4 * 8]);
+
+        let mut reader = Reader::new(io::Cursor::new(data.clone()));
+        let mut records = reader.records();
+        assert_eq!(records.next().transpose()?, Some(Record::from([1.; 10])));
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+
+        assert!(Reader::new(io::Cursor::new(data)).count_records().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_text() -> io::Result<()> {
+        let records = [
+            Record::from([0., -1., -2., -3., -4., -5., -6., -7., -8., -9.]),
+            Record::from([-0.5; 10]),
+        ];
+
+        for (sep, precision, expected) in [
+            ("\t", 0, "0\t-1\t-2\t-3\t-4\t-5\t-6\t-7\t-8\t-9\n"),
+            (",", 1, "0.0,-1.0,-2.0,-3.0,-4.0,-5.0,-6.0,-7.0,-8.0,-9.0\n"),
+        ] {
+            let mut text = Vec::new();
+            reader_from_records(&records[..1])?.write_text(&mut text, sep, precision)?;
+            assert_eq!(String::from_utf8(text).unwrap(), expected);
+        }
+
+        let mut text = Vec::new();
+        reader_from_records(&records)?.write_text(&mut text, ":", 2)?;
+        let parsed = String::from_utf8(text)
+            .unwrap()
+            .lines()
+            .map(|line| line.parse::<Record>())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(parsed, records);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_n() -> io::Result<()> {
+        let values = [0., -1., -2., -3., -4., -5.];
+        let data = values
+            .iter()
+            .flat_map(|v: &f64| v.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let mut reader = Reader::new(io::Cursor::new(data));
+        let mut buf = Vec::new();
+
+        assert!(reader.read_record_n(3, &mut buf)?.is_not_done());
+        assert_eq!(buf, &values[..3]);
+        assert!(reader.read_record_n(3, &mut buf)?.is_not_done());
+        assert_eq!(buf, &values[3..]);
+        assert!(reader.read_record_n(3, &mut buf)?.is_done());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_n_zero() {
+        let mut reader = Reader::new(io::Cursor::new(1f64.to_le_bytes().to_vec()));
+        let mut buf = Vec::new();
+
+        let error = reader.read_record_n(0, &mut buf).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_read_record_n_truncated() {
+        let data = [1f64, 2.]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let mut reader = Reader::new(io::Cursor::new(data));
+        let mut buf = Vec::new();
+
+        assert!(reader.read_record_n(3, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_column_truncated() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_record(&Record::from([1.; 10]))?;
+        let mut data = writer.into_inner();
+        data.extend([0; 4 * 8]);
+
+        let mut reader = Reader::new(io::Cursor::new(data));
+        let mut column = reader.column(Genotype::AC);
+
+        assert_eq!(column.next().transpose()?, Some(1.));
+        assert!(column.next().unwrap().is_err());
+        assert!(column.next().is_none());
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ mod genotype;
 pub use genotype::Genotype;
 
 const SEP: &str = ":";
-const SIZE: usize = 10;
+pub(crate) const SIZE: usize = 10;
 
 /// A GLF record.
 ///
@@ -18,6 +18,23 @@ const SIZE: usize = 10;
 pub struct Record([f64; SIZE]);
 
 impl Record {
+    /// Returns the most likely genotype.
+    ///
+    /// If several genotypes share the largest likelihood, the first of these in the order of
+    /// [`Genotype::all`] is returned.
+    pub fn argmax(&self) -> Genotype {
+        Genotype::all()
+            .into_iter()
+            .reduce(|max, genotype| {
+                if self[genotype] > self[max] {
+                    genotype
+                } else {
+                    max
+                }
+            })
+            .expect("non-empty genotypes")
+    }
+
     /// Returns a slice containing the entire record.
     ///
     /// The order of likelihoods within the slice is encoded by [`Genotype`].
@@ -41,6 +58,16 @@ impl Record {
         Self::default()
     }
 
+    /// Normalizes the record by subtracting the largest likelihood from all likelihoods.
+    ///
+    /// Assuming log-scaled likelihoods, this scales the likelihoods to the most likely genotype,
+    /// which then has likelihood zero.
+    pub fn normalize(&mut self) {
+        let max = self[self.argmax()];
+
+        self.0.iter_mut().for_each(|value| *value -= max);
+    }
+
     /// Returns an array containining the entire record, consuming `self`.
     pub fn to_array(self) -> [f64; SIZE] {
         self.0
@@ -165,6 +192,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_argmax() {
+        let mut record = Record::from([-5.; SIZE]);
+        assert_eq!(record.argmax(), Genotype::AA);
+
+        record[Genotype::CT] = -1.;
+        assert_eq!(record.argmax(), Genotype::CT);
+
+        record[Genotype::TT] = -1.;
+        record[Genotype::AG] = -1.;
+        assert_eq!(record.argmax(), Genotype::AG);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut record = Record::from([-3., -2., -4., -2., -5., -6., -7., -8., -9., -10.]);
+        record.normalize();
+
+        assert_eq!(record[record.argmax()], 0.);
+        assert_eq!(
+            record,
+            Record::from([-1., 0., -2., 0., -3., -4., -5., -6., -7., -8.])
+        );
+    }
+
+    #[test]
+    fn test_all_genotypes_in_record_order() {
+        for (i, genotype) in Genotype::all().into_iter().enumerate() {
+            assert_eq!(genotype as usize, i);
+        }
+    }
+
     #[test]
     fn test_parse() {
         assert_eq!(
@@ -22,3 +22,24 @@ pub enum Genotype {
     /// TT genotype.
     TT = 9,
 }
+
+impl Genotype {
+    /// Returns all genotypes.
+    ///
+    /// The genotypes are ordered in the same way as the likelihoods within a
+    /// [`Record`](crate::Record).
+    pub fn all() -> [Self; 10] {
+        [
+            Self::AA,
+            Self::AC,
+            Self::AG,
+            Self::AT,
+            Self::CC,
+            Self::CG,
+            Self::CT,
+            Self::GG,
+            Self::GT,
+            Self::TT,
+        ]
+    }
+}
@@ -0,0 +1,123 @@
+use std::{fmt, io};
+
+use crate::ReaderV3;
+
+/// Describes a set of readers.
+///
+/// This summarises the readers that will be used for estimating a joint SFS, and may be used
+/// for diagnostics during setup. See [`ReaderSetDescription`] for details.
+pub fn describe_readers<R>(readers: &[ReaderV3<R>]) -> ReaderSetDescription
+where
+    R: io::BufRead,
+{
+    let readers = readers
+        .iter()
+        .map(|reader| {
+            let index = reader.index();
+
+            ReaderDescription {
+                alleles: index.alleles(),
+                contigs: index.records().len(),
+                sites: index.total_sites(),
+            }
+        })
+        .collect();
+
+    ReaderSetDescription { readers }
+}
+
+/// A description of a single reader.
+///
+/// Created as part of a [`ReaderSetDescription`] using [`describe_readers`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReaderDescription {
+    alleles: usize,
+    contigs: usize,
+    sites: usize,
+}
+
+impl ReaderDescription {
+    /// Returns the number of alleles of the reader.
+    pub fn alleles(&self) -> usize {
+        self.alleles
+    }
+
+    /// Returns the number of contigs in the index of the reader.
+    pub fn contigs(&self) -> usize {
+        self.contigs
+    }
+
+    /// Returns the total number of sites in the index of the reader.
+    pub fn sites(&self) -> usize {
+        self.sites
+    }
+}
+
+/// A description of a set of readers.
+///
+/// Created using [`describe_readers`]. The description holds a [`ReaderDescription`] for each
+/// reader, as well as the shape of the joint SFS of the readers. The [`fmt::Display`]
+/// implementation gives a human-readable summary.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReaderSetDescription {
+    readers: Vec<ReaderDescription>,
+}
+
+impl ReaderSetDescription {
+    /// Returns the number of dimensions of the joint SFS.
+    ///
+    /// This is the number of readers.
+    pub fn dimensions(&self) -> usize {
+        self.readers.len()
+    }
+
+    /// Returns the descriptions of each reader.
+    pub fn readers(&self) -> &[ReaderDescription] {
+        &self.readers
+    }
+
+    /// Returns the shape of the joint SFS.
+    ///
+    /// The joint SFS has a dimension for each reader, and each dimension has a size one greater
+    /// than the number of alleles of the corresponding reader.
+    pub fn shape(&self) -> Vec<usize> {
+        self.readers
+            .iter()
+            .map(|reader| reader.alleles + 1)
+            .collect()
+    }
+
+    /// Returns the total number of values in the joint SFS.
+    pub fn sfs_len(&self) -> usize {
+        self.shape().iter().product()
+    }
+}
+
+impl fmt::Display for ReaderSetDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, reader) in self.readers.iter().enumerate() {
+            writeln!(
+                f,
+                "reader {}: {} alleles, {} contigs, {} sites",
+                i + 1,
+                reader.alleles,
+                reader.contigs,
+                reader.sites
+            )?;
+        }
+
+        let shape = self
+            .shape()
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        write!(
+            f,
+            "joint SFS: {} dimensions, shape {shape}, {} values",
+            self.dimensions(),
+            self.sfs_len()
+        )
+    }
+}
@@ -0,0 +1,147 @@
+//! SAF errors.
+
+use std::{error, fmt, io};
+
+use crate::version::MAGIC_LEN;
+
+/// A SAF error.
+///
+/// Fallible operations in this crate return [`io::Error`]s, since reading and writing SAF files
+/// is fundamentally I/O. Where an error is specific to the SAF format, the returned
+/// [`io::Error`] wraps a SAF error, which can be recovered using [`Error::from`] to inspect what
+/// went wrong in a structured way. Other errors are wrapped in [`Error::Io`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The magic number read does not match the expected SAF version.
+    Magic {
+        /// The magic number read.
+        found: [u8; MAGIC_LEN],
+        /// The magic number of the expected version.
+        expected: [u8; MAGIC_LEN],
+        /// The expected SAF version.
+        version: u8,
+        /// The name of the expected SAF version in ANGSD.
+        angsd_name: &'static str,
+    },
+    /// A SAF file ended before the data defined by the index was read.
+    UnexpectedEof(&'static str),
+    /// One or more SAF files ended at a site before the end of its contig defined by the index.
+    Truncated {
+        /// The description of the files that ended.
+        files: &'static str,
+        /// The name of the contig being read.
+        contig: String,
+        /// The 0-based index of the site being read on the contig.
+        site: usize,
+    },
+    /// The end of the index was reached before the end of the named SAF files.
+    IndexExhausted(&'static str),
+    /// An index record name is not valid UTF-8.
+    InvalidName,
+    /// Other invalid SAF data.
+    InvalidData(String),
+    /// An I/O error not specific to the SAF format.
+    Io(io::Error),
+}
+
+impl Error {
+    /// Returns the kind of the [`io::Error`] corresponding to the error.
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            Self::UnexpectedEof(_) | Self::Truncated { .. } => io::ErrorKind::UnexpectedEof,
+            Self::Io(e) => e.kind(),
+            _ => io::ErrorKind::InvalidData,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Magic {
+                found,
+                expected,
+                version,
+                angsd_name,
+            } => write!(
+                f,
+                "invalid or unsupported SAF magic number \
+                (found '{found:02x?}', expected '{expected:02x?}' for safv{version}, \
+                known as '{angsd_name}' in ANGSD)",
+            ),
+            Self::UnexpectedEof(msg) => f.write_str(msg),
+            Self::Truncated {
+                files,
+                contig,
+                site,
+            } => write!(
+                f,
+                "reached EoF in {files} at site {site} on contig '{contig}' \
+                before reaching end of index"
+            ),
+            Self::IndexExhausted(files) => {
+                write!(f, "reached end of index before reaching EoF in {files}")
+            }
+            Self::InvalidName => f.write_str("index record name not valid UTF8"),
+            Self::InvalidData(msg) => f.write_str(msg),
+            Self::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(e) => e,
+            error => io::Error::new(error.kind(), error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        if error.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            let inner = error.into_inner().expect("checked inner error");
+            *inner.downcast::<Error>().expect("checked inner error type")
+        } else {
+            Self::Io(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_roundtrip() {
+        let error = io::Error::from(Error::UnexpectedEof("reached EoF"));
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(error.to_string(), "reached EoF");
+
+        assert!(matches!(
+            Error::from(error),
+            Error::UnexpectedEof("reached EoF")
+        ));
+    }
+
+    #[test]
+    fn test_io_error_not_saf() {
+        let error = Error::from(io::Error::other("other"));
+        assert!(matches!(&error, Error::Io(e) if e.kind() == io::ErrorKind::Other));
+
+        let error = io::Error::from(error);
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+        assert_eq!(error.to_string(), "other");
+    }
+}
@@ -9,6 +9,12 @@ pub const POSITIONS_FILE_EXT: &str = "saf.pos.gz";
 /// Conventional item file extension.
 pub const ITEM_FILE_EXT: &str = "saf.gz";
 
+/// Conventional depth file extension.
+///
+/// The depth file is an optional member carrying the depth of each site, and is not included
+/// when reconstructing member paths. See [`crate::reader::WithDepth`].
+pub const DEPTH_FILE_EXT: &str = "saf.depth.gz";
+
 const EXTS: [&str; 3] = [INDEX_EXT, POSITIONS_FILE_EXT, ITEM_FILE_EXT];
 
 /// Returns the shared prefix of SAF file member paths given any one of them.
@@ -1,23 +1,54 @@
 //! A SAF index.
 
-use std::{fmt, fs, io, path::Path};
+use std::{fmt, fs, io, path::Path, sync::OnceLock};
+
+use indexmap::IndexMap;
 
 use super::{reader::ReaderExt, version::Version};
 
+mod builder;
+pub use builder::IndexBuilder;
+
+mod raw;
+pub use raw::{RawIndex, RawRecord};
+
+mod reconstruct;
+pub use reconstruct::reconstruct_index;
+
 mod record;
 pub use record::Record;
 
+mod summary;
+pub use summary::IndexSummary;
+
 mod traits;
-pub(crate) use traits::{IndexReaderExt, IndexWriterExt};
+use traits::{
+    invalid_name_err, truncated_err, CONTIG_ALLELES_MARKER, POSITION_BASE_MARKER,
+    POSITION_WIDTH_MARKER, REFERENCE_LENGTHS_MARKER, SITE_CHECKSUM_MARKER,
+};
+pub(crate) use traits::{CountingReader, IndexReaderExt, IndexWriterExt};
 
 /// A SAF file index.
 ///
 /// Different SAF file versions differ only in what their records contain. For more details. see
 /// [`Record`].
+///
+/// With the `serde` feature, the index can be serialized for inspection, see [`Record`] for
+/// details on the serialized records.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "", deserialize = "V: Version"))
+)]
 pub struct Index<V> {
     alleles: usize,
     records: Vec<Record<V>>,
+    site_checksum: Option<usize>,
+    position_width: PositionWidth,
+    position_base: PositionBase,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    contig_ids: ContigIds,
 }
 
 impl<V> Index<V>
@@ -38,6 +69,98 @@ where
         &mut self.alleles
     }
 
+    /// Appends the records of another index, consuming it.
+    ///
+    /// This is a metadata-only merge: record offsets are copied as they are, and so the offsets of
+    /// the appended records refer to the files of `other`, and cannot be used for seeking in the
+    /// files of `self`. See [`crate::merge`] to merge the files themselves. If both indices have a
+    /// site checksum, the merged checksum is their sum, and otherwise the merged index has none.
+    ///
+    /// Returns an error if the indices differ in their number of alleles, position width, or
+    /// position base, or if a contig name occurs in both. In this case, `self` is unchanged.
+    pub fn append(&mut self, other: Index<V>) -> io::Result<()> {
+        let mismatch = if self.alleles != other.alleles {
+            Some(format!("alleles ({} and {})", self.alleles, other.alleles))
+        } else if self.position_width != other.position_width {
+            Some(String::from("position width"))
+        } else if self.position_base != other.position_base {
+            Some(String::from("position base"))
+        } else {
+            None
+        };
+
+        if let Some(mismatch) = mismatch {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot append indices with different {mismatch}"),
+            ));
+        }
+
+        if let Some(name) = other
+            .contig_names()
+            .find(|name| self.contig_id(name).is_some())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot append indices with shared contig name '{name}'"),
+            ));
+        }
+
+        self.site_checksum = self
+            .site_checksum
+            .zip(other.site_checksum)
+            .map(|(a, b)| a + b);
+        self.records_mut().extend(other.records);
+
+        Ok(())
+    }
+
+    /// Returns the number of alleles for the contig with the provided ID.
+    ///
+    /// This is the per-contig override set on the index record, if any, and otherwise the number
+    /// of alleles shared by all contigs. See [`Record::alleles`] for details.
+    pub fn contig_alleles(&self, contig_id: usize) -> usize {
+        self.records
+            .get(contig_id)
+            .and_then(|record| record.alleles())
+            .unwrap_or(self.alleles)
+    }
+
+    /// Returns an iterator over the contig names in the index, in index order.
+    pub fn contig_names(&self) -> impl Iterator<Item = &str> {
+        self.records.iter().map(|record| record.name())
+    }
+
+    /// Returns the ID of the contig with the provided name, if any.
+    ///
+    /// The ID refers to the position of the record in the index. If several records share the
+    /// same name, the ID of the first is returned.
+    ///
+    /// A mapping from names to IDs is built on the first call, after which lookups take constant
+    /// time. The mapping is discarded if the records are mutably accessed using
+    /// [`Self::records_mut`].
+    pub fn contig_id(&self, name: &str) -> Option<usize> {
+        self.contig_ids
+            .0
+            .get_or_init(|| {
+                let mut map = IndexMap::with_capacity(self.records.len());
+                for (i, record) in self.records.iter().enumerate() {
+                    map.entry(record.name().to_owned()).or_insert(i);
+                }
+                map
+            })
+            .get(name)
+            .copied()
+    }
+
+    /// Returns the record of the contig with the provided name, if any.
+    ///
+    /// See [`Self::contig_id`] for details on the lookup.
+    pub fn get(&self, name: &str) -> Option<&Record<V>> {
+        self.contig_id(name)
+            .map(|contig_id| &self.records[contig_id])
+    }
+
     /// Returns the index records, consuming `self`.
     pub fn into_records(self) -> Vec<Record<V>> {
         self.records
@@ -45,28 +168,86 @@ where
 
     /// Creates a new index.
     pub fn new(alleles: usize, records: Vec<Record<V>>) -> Self {
-        Self { alleles, records }
+        Self {
+            alleles,
+            records,
+            site_checksum: None,
+            position_width: PositionWidth::U32,
+            position_base: PositionBase::Zero,
+            contig_ids: ContigIds::default(),
+        }
     }
 
     /// Reads a new index from a reader.
     ///
-    /// The stream is assumed to be positioned at the start.
+    /// The stream is assumed to be positioned at the start. If the index is truncated in the middle
+    /// of a record, the returned error reports the number of records read and the byte offset at
+    /// which the truncation occurred.
+    ///
+    /// Record reference lengths are read if the index contains them, see
+    /// [`Record::reference_length`]. Likewise for record number of alleles overrides, see
+    /// [`Record::alleles`], and for the position width and base, see [`Self::position_width`] and
+    /// [`Self::position_base`].
     pub fn read<R>(reader: &mut R) -> io::Result<Self>
     where
         R: io::BufRead,
     {
-        V::read_magic(reader)?;
+        let mut reader = CountingReader::new(reader);
+
+        V::read_magic(&mut reader)?;
 
         let alleles = reader.read_alleles()?;
 
         let mut records = Vec::new();
+        let mut site_checksum = None;
+        let mut position_width = PositionWidth::U32;
+        let mut position_base = PositionBase::Zero;
+        let mut has_reference_lengths = false;
+        let mut contig_alleles = None;
         while reader.is_data_left()? {
-            let record = Record::read(reader)?;
+            let entry = read_entry(&mut reader)
+                .map_err(|e| truncated_err(e, records.len(), reader.offset()))?;
+
+            match entry {
+                Entry::Record(name_len) => {
+                    let record = reader
+                        .read_contig_name_bytes_with_len(name_len)
+                        .and_then(|name| String::from_utf8(name).map_err(|_| invalid_name_err()))
+                        .and_then(|name| V::read_index_record_with_name(&mut reader, name))
+                        .and_then(|mut record| {
+                            *record.alleles_mut() = contig_alleles.take();
 
-            records.push(record)
+                            if has_reference_lengths {
+                                *record.reference_length_mut() = reader.read_reference_length()?;
+                            }
+
+                            Ok(record)
+                        })
+                        .map_err(|e| truncated_err(e, records.len(), reader.offset()))?;
+
+                    records.push(record)
+                }
+                Entry::ReferenceLengths => {
+                    has_reference_lengths = check_reference_lengths_entry(records.len())?;
+                }
+                Entry::ContigAlleles(alleles) => contig_alleles = Some(alleles),
+                Entry::PositionWidth(width) => position_width = width,
+                Entry::PositionBase(base) => position_base = base,
+                Entry::SiteChecksum(sites) => {
+                    site_checksum = Some(sites);
+                    break;
+                }
+            }
         }
 
-        Ok(Index::new(alleles, records))
+        Ok(Self {
+            alleles,
+            records,
+            site_checksum,
+            position_width,
+            position_base,
+            contig_ids: ContigIds::default(),
+        })
     }
 
     /// Creates a new index by reading from a path.
@@ -79,6 +260,48 @@ where
             .and_then(|mut reader| Self::read(&mut reader))
     }
 
+    /// Returns the base of positions in the position file.
+    ///
+    /// SAF files written by ANGSD store 0-based positions, which is the default. Positions may
+    /// instead be stored 1-based, which is recorded in the index. In either case, positions are
+    /// always 0-based when read or written using a [`Reader`] or [`Writer`], which convert to and
+    /// from the stored base as needed. Note that this is an extension to the SAF index format, and
+    /// so files with 1-based positions cannot be correctly read by other tools. See also
+    /// [`Writer::set_position_base`].
+    ///
+    /// [`Reader`]: crate::Reader
+    /// [`Writer`]: crate::Writer
+    /// [`Writer::set_position_base`]: crate::Writer::set_position_base
+    pub fn position_base(&self) -> PositionBase {
+        self.position_base
+    }
+
+    /// Returns a mutable reference to the base of positions in the position file.
+    ///
+    /// See [`Self::position_base`] for details.
+    pub fn position_base_mut(&mut self) -> &mut PositionBase {
+        &mut self.position_base
+    }
+
+    /// Returns the width of positions in the position file.
+    ///
+    /// SAF files store positions as 32-bit integers, which is the default. Contigs longer than
+    /// this allows may instead be stored with 64-bit positions, which is recorded in the index.
+    /// Note that this is an extension to the SAF index format, and so files with 64-bit positions
+    /// cannot be read by other tools. See also [`Writer::set_position_width`].
+    ///
+    /// [`Writer::set_position_width`]: crate::Writer::set_position_width
+    pub fn position_width(&self) -> PositionWidth {
+        self.position_width
+    }
+
+    /// Returns a mutable reference to the width of positions in the position file.
+    ///
+    /// See [`Self::position_width`] for details.
+    pub fn position_width_mut(&mut self) -> &mut PositionWidth {
+        &mut self.position_width
+    }
+
     /// Returns the index records.
     pub fn records(&self) -> &[Record<V>] {
         self.records.as_ref()
@@ -86,15 +309,44 @@ where
 
     /// Returns a mutable reference to the index records.
     pub fn records_mut(&mut self) -> &mut Vec<Record<V>> {
+        self.contig_ids = ContigIds::default();
         &mut self.records
     }
 
+    /// Returns the site checksum, if any.
+    ///
+    /// The site checksum is the total number of sites written, as recorded at the end of the index
+    /// when writing with [`Writer::set_site_checksum`]. When reading, it is checked against the
+    /// number of sites read on reaching the end of the index.
+    ///
+    /// [`Writer::set_site_checksum`]: crate::Writer::set_site_checksum
+    pub fn site_checksum(&self) -> Option<usize> {
+        self.site_checksum
+    }
+
+    /// Returns a mutable reference to the site checksum.
+    ///
+    /// See [`Self::site_checksum`] for details.
+    pub fn site_checksum_mut(&mut self) -> &mut Option<usize> {
+        &mut self.site_checksum
+    }
+
+    /// Returns summary statistics of the index.
+    ///
+    /// See [`IndexSummary`] for the statistics computed.
+    pub fn summary(&self) -> IndexSummary {
+        IndexSummary::from(self)
+    }
+
     /// Returns the total number of sites.
     pub fn total_sites(&self) -> usize {
         self.records.iter().map(|rec| rec.sites()).sum()
     }
 
     /// Writes the index to a writer.
+    ///
+    /// If any record has a reference length, reference lengths are written for all records. See
+    /// [`Record::reference_length`].
     pub fn write<W>(&self, writer: &mut W) -> io::Result<()>
     where
         W: io::Write,
@@ -103,8 +355,32 @@ where
 
         writer.write_alleles(self.alleles())?;
 
+        let has_reference_lengths = self
+            .records
+            .iter()
+            .any(|record| record.reference_length().is_some());
+        if has_reference_lengths {
+            writer.write_reference_lengths_marker()?;
+        }
+
         for record in self.records() {
             record.write(writer)?;
+
+            if has_reference_lengths {
+                writer.write_reference_length(record.reference_length())?;
+            }
+        }
+
+        if self.position_width != PositionWidth::U32 {
+            writer.write_position_width(self.position_width)?;
+        }
+
+        if self.position_base != PositionBase::Zero {
+            writer.write_position_base(self.position_base)?;
+        }
+
+        if let Some(sites) = self.site_checksum {
+            writer.write_site_checksum(sites)?;
         }
 
         Ok(())
@@ -123,6 +399,213 @@ where
     }
 }
 
+/// An entry in an index following the header.
+pub(crate) enum Entry {
+    /// A record, where the contig name of the given length follows.
+    Record(usize),
+    /// The marker that all following records are followed by a reference length.
+    ReferenceLengths,
+    /// The number of alleles override for the immediately following record.
+    ContigAlleles(usize),
+    /// The position width.
+    PositionWidth(PositionWidth),
+    /// The position base.
+    PositionBase(PositionBase),
+    /// The site checksum.
+    SiteChecksum(usize),
+}
+
+/// Reads the start of the next entry in an index.
+///
+/// The stream is assumed to be positioned immediately in front of a record or site checksum.
+pub(crate) fn read_entry<R>(reader: &mut R) -> io::Result<Entry>
+where
+    R: io::BufRead,
+{
+    let name_len = reader.read_contig_name_len()?;
+
+    if name_len == SITE_CHECKSUM_MARKER {
+        reader.read_sites().map(Entry::SiteChecksum)
+    } else if name_len == REFERENCE_LENGTHS_MARKER {
+        Ok(Entry::ReferenceLengths)
+    } else if name_len == CONTIG_ALLELES_MARKER {
+        reader.read_contig_alleles().map(Entry::ContigAlleles)
+    } else if name_len == POSITION_WIDTH_MARKER {
+        reader.read_position_width().map(Entry::PositionWidth)
+    } else if name_len == POSITION_BASE_MARKER {
+        reader.read_position_base().map(Entry::PositionBase)
+    } else {
+        Ok(Entry::Record(name_len))
+    }
+}
+
+/// Checks that the reference lengths marker is not preceded by any of the provided records.
+///
+/// Returns `true` if so, and an error otherwise.
+pub(crate) fn check_reference_lengths_entry(records: usize) -> io::Result<bool> {
+    if records == 0 {
+        Ok(true)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "reference lengths marker found after index records",
+        ))
+    }
+}
+
+/// A lazily built mapping from contig names to IDs.
+///
+/// This is a cache, and so is ignored when comparing indices.
+#[derive(Clone, Debug, Default)]
+struct ContigIds(OnceLock<IndexMap<String, usize>>);
+
+impl PartialEq for ContigIds {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ContigIds {}
+
+/// The width of positions in a SAF position file.
+///
+/// See [`Index::position_width`] for details.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionWidth {
+    /// Positions stored as 32-bit integers, as in all SAF files written by ANGSD.
+    #[default]
+    U32,
+    /// Positions stored as 64-bit integers.
+    U64,
+}
+
+impl PositionWidth {
+    /// Returns the number of bytes used to store a single position.
+    pub fn bytes(&self) -> usize {
+        match self {
+            Self::U32 => 4,
+            Self::U64 => 8,
+        }
+    }
+
+    /// Returns the position width using the provided number of bytes per position, if any.
+    pub fn from_bytes(bytes: usize) -> Option<Self> {
+        match bytes {
+            4 => Some(Self::U32),
+            8 => Some(Self::U64),
+            _ => None,
+        }
+    }
+}
+
+/// The base of positions in a SAF position file.
+///
+/// See [`Index::position_base`] for details.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionBase {
+    /// Positions stored 0-based, as in all SAF files written by ANGSD.
+    #[default]
+    Zero,
+    /// Positions stored 1-based.
+    One,
+}
+
+impl PositionBase {
+    /// Returns the position base with the provided offset from 0-based positions, if any.
+    pub fn from_offset(offset: u64) -> Option<Self> {
+        match offset {
+            0 => Some(Self::Zero),
+            1 => Some(Self::One),
+            _ => None,
+        }
+    }
+
+    /// Returns the offset of the base from 0-based positions.
+    pub fn offset(&self) -> u64 {
+        match self {
+            Self::Zero => 0,
+            Self::One => 1,
+        }
+    }
+
+    /// Returns the stored position corresponding to the provided 0-based position.
+    ///
+    /// Returns an error if the stored position overflows.
+    pub(crate) fn to_stored(self, position: u64) -> io::Result<u64> {
+        position.checked_add(self.offset()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("position {position} overflows when stored {self}"),
+            )
+        })
+    }
+
+    /// Returns the 0-based position corresponding to the provided stored position.
+    ///
+    /// Returns an error if the stored position is invalid for the base.
+    pub(crate) fn to_zero_based(self, position: u64) -> io::Result<u64> {
+        position.checked_sub(self.offset()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid position {position} in SAF position file stored {self}"),
+            )
+        })
+    }
+}
+
+impl fmt::Display for PositionBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero => f.write_str("0-based"),
+            Self::One => f.write_str("1-based"),
+        }
+    }
+}
+
+/// Consumes the index into its records.
+///
+/// # Examples
+///
+/// ```
+/// use angsd_saf::{index::Record, version::V3, Index};
+///
+/// let index = Index::<V3>::new(
+///     4,
+///     vec![
+///         Record::new(String::from("chr1"), 10, 8, 8),
+///         Record::new(String::from("chr2"), 5, 48, 328),
+///     ],
+/// );
+///
+/// let names = (&index).into_iter().map(|record| record.name()).collect::<Vec<_>>();
+/// assert_eq!(names, ["chr1", "chr2"]);
+///
+/// let mut sites = 0;
+/// for record in index {
+///     sites += record.sites();
+/// }
+/// assert_eq!(sites, 15);
+/// ```
+impl<V> IntoIterator for Index<V> {
+    type Item = Record<V>;
+    type IntoIter = std::vec::IntoIter<Record<V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a Index<V> {
+    type Item = &'a Record<V>;
+    type IntoIter = std::slice::Iter<'a, Record<V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
 impl<V> fmt::Display for Index<V>
 where
     V: Version,
@@ -139,3 +622,287 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::version::{V3, V4};
+
+    #[test]
+    fn test_read_truncated_index() -> io::Result<()> {
+        let index = Index::<V3>::new(
+            2,
+            vec![
+                Record::new("chr1".to_string(), 1, 8, 8),
+                Record::new("chr2".to_string(), 2, 12, 20),
+            ],
+        );
+
+        let mut writer = Vec::new();
+        index.write(&mut writer)?;
+        writer.truncate(writer.len() - 10);
+
+        let error = Index::<V3>::read(&mut writer.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let msg = error.to_string();
+        assert!(msg.contains(&format!("byte offset {}", writer.len())));
+        assert!(msg.contains("after reading 1 complete records"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary() {
+        let index = Index::<V3>::new(
+            2,
+            vec![
+                Record::new("chr1".to_string(), 3, 8, 8),
+                Record::new("chr2".to_string(), 1, 20, 44),
+                Record::new("chr3".to_string(), 5, 24, 56),
+            ],
+        );
+
+        let summary = index.summary();
+        assert_eq!(summary.contigs(), 3);
+        assert_eq!(summary.total_sites(), 9);
+        assert_eq!(summary.min_sites(), 1);
+        assert_eq!(summary.max_sites(), 5);
+        assert_eq!(summary.total_sum_band(), None);
+
+        let index = Index::<V4>::new(
+            3,
+            vec![
+                Record::new_with_sum_band("chr1".to_string(), 2, 6, 8, 8),
+                Record::new_with_sum_band("chr2".to_string(), 1, 1, 16, 48),
+            ],
+        );
+
+        let summary = index.summary();
+        assert_eq!(summary.contigs(), 2);
+        assert_eq!(summary.total_sites(), 3);
+        assert_eq!(summary.min_sites(), 1);
+        assert_eq!(summary.max_sites(), 2);
+        assert_eq!(summary.total_sum_band(), Some(7));
+
+        let summary = Index::<V4>::new(3, Vec::new()).summary();
+        assert_eq!(summary.contigs(), 0);
+        assert_eq!(summary.min_sites(), 0);
+        assert_eq!(summary.total_sum_band(), Some(0));
+
+        let summary = Index::<V3>::new(2, Vec::new()).summary();
+        assert_eq!(summary.total_sum_band(), None);
+    }
+
+    #[test]
+    fn test_contig_id() {
+        let records = vec![
+            Record::new("chr1".to_string(), 1, 8, 8),
+            Record::new("chr2".to_string(), 2, 12, 20),
+            Record::new("chr1".to_string(), 1, 28, 28),
+        ];
+        let mut index = Index::<V3>::new(2, records.clone());
+
+        assert_eq!(index.contig_id("chr1"), Some(0));
+        assert_eq!(index.contig_id("chr2"), Some(1));
+        assert_eq!(index.contig_id("chr3"), None);
+
+        assert_eq!(index, Index::new(2, records));
+
+        index.records_mut()[1] = Record::new("chr3".to_string(), 2, 12, 20);
+        assert_eq!(index.contig_id("chr2"), None);
+        assert_eq!(index.contig_id("chr3"), Some(1));
+    }
+
+    #[test]
+    fn test_get_and_contig_names() {
+        let index = Index::<V3>::new(
+            2,
+            vec![
+                Record::new("chr2".to_string(), 1, 8, 8),
+                Record::new("chr1".to_string(), 2, 12, 20),
+            ],
+        );
+
+        assert_eq!(index.contig_names().collect::<Vec<_>>(), ["chr2", "chr1"]);
+
+        assert_eq!(index.get("chr1"), Some(&index.records()[1]));
+        assert_eq!(index.get("chr3"), None);
+    }
+
+    #[test]
+    fn test_append() -> io::Result<()> {
+        let mut index = Index::<V3>::new(2, vec![Record::new("chr1".to_string(), 1, 8, 8)]);
+        let other = Index::<V3>::new(
+            2,
+            vec![
+                Record::new("chr2".to_string(), 2, 8, 8),
+                Record::new("chr3".to_string(), 3, 12, 20),
+            ],
+        );
+
+        index.append(other)?;
+
+        assert_eq!(
+            index.contig_names().collect::<Vec<_>>(),
+            ["chr1", "chr2", "chr3"]
+        );
+        assert_eq!(index.contig_id("chr3"), Some(2));
+        assert_eq!(index.total_sites(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_allele_mismatch() {
+        let mut index = Index::<V3>::new(2, vec![Record::new("chr1".to_string(), 1, 8, 8)]);
+        let other = Index::<V3>::new(4, vec![Record::new("chr2".to_string(), 1, 8, 8)]);
+
+        let unchanged = index.clone();
+        let error = index.append(other).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("alleles"));
+        assert_eq!(index, unchanged);
+    }
+
+    #[test]
+    fn test_append_duplicate_name() {
+        let mut index = Index::<V3>::new(2, vec![Record::new("chr1".to_string(), 1, 8, 8)]);
+        let other = Index::<V3>::new(
+            2,
+            vec![
+                Record::new("chr2".to_string(), 1, 8, 8),
+                Record::new("chr1".to_string(), 1, 12, 12),
+            ],
+        );
+
+        let unchanged = index.clone();
+        let error = index.append(other).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("'chr1'"));
+        assert_eq!(index, unchanged);
+    }
+
+    #[test]
+    fn test_read_write_reference_lengths() -> io::Result<()> {
+        let mut records = vec![
+            Record::new("chr1".to_string(), 3, 8, 8),
+            Record::new("chr2".to_string(), 2, 20, 44),
+        ];
+
+        // Without reference lengths, the index is written in the legacy format
+        let legacy = Index::<V3>::new(2, records.clone());
+        let mut legacy_writer = Vec::new();
+        legacy.write(&mut legacy_writer)?;
+        assert_eq!(legacy_writer.len(), 8 + 8 + 2 * (8 + 4 + 3 * 8));
+
+        let read = Index::<V3>::read(&mut legacy_writer.as_slice())?;
+        assert_eq!(read, legacy);
+        assert!(read
+            .records()
+            .iter()
+            .all(|r| r.reference_length().is_none()));
+
+        *records[0].reference_length_mut() = Some(1000);
+        let index = Index::<V3>::new(2, records);
+        let mut writer = Vec::new();
+        index.write(&mut writer)?;
+
+        let read = Index::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(read, index);
+        assert_eq!(read.records()[0].reference_length(), Some(1000));
+        assert_eq!(read.records()[0].site_density(), Some(0.003));
+        assert_eq!(read.records()[1].reference_length(), None);
+
+        let raw_index = RawIndex::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(raw_index.records()[0].reference_length(), Some(1000));
+        assert_eq!(raw_index.into_index()?, index);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_write_site_checksum() -> io::Result<()> {
+        let mut index = Index::<V3>::new(2, vec![Record::new("chr1".to_string(), 3, 8, 8)]);
+        *index.site_checksum_mut() = Some(3);
+
+        let mut writer = Vec::new();
+        index.write(&mut writer)?;
+
+        assert_eq!(Index::<V3>::read(&mut writer.as_slice())?, index);
+
+        let raw_index = RawIndex::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(raw_index.site_checksum(), Some(3));
+        assert_eq!(raw_index.into_index()?, index);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_write_position_base() -> io::Result<()> {
+        let mut index = Index::<V3>::new(2, vec![Record::new("chr1".to_string(), 3, 8, 8)]);
+        *index.position_base_mut() = PositionBase::One;
+        *index.position_width_mut() = PositionWidth::U64;
+        *index.site_checksum_mut() = Some(3);
+
+        let mut data = Vec::new();
+        index.write(&mut data)?;
+
+        assert_eq!(Index::<V3>::read(&mut &data[..])?, index);
+
+        let raw_index = RawIndex::<V3>::read(&mut &data[..])?;
+        assert_eq!(raw_index.position_base(), PositionBase::One);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_write_position_width() -> io::Result<()> {
+        let mut index = Index::<V3>::new(2, vec![Record::new("chr1".to_string(), 3, 8, 8)]);
+        *index.position_width_mut() = PositionWidth::U64;
+        *index.site_checksum_mut() = Some(3);
+
+        let mut writer = Vec::new();
+        index.write(&mut writer)?;
+
+        assert_eq!(Index::<V3>::read(&mut writer.as_slice())?, index);
+
+        let raw_index = RawIndex::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(raw_index.position_width(), PositionWidth::U64);
+        assert_eq!(raw_index.into_index()?, index);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_write_contig_alleles() -> io::Result<()> {
+        let mut records = vec![
+            Record::new("chr1".to_string(), 3, 8, 8),
+            Record::new("chrX".to_string(), 2, 20, 44),
+        ];
+        *records[1].alleles_mut() = Some(1);
+        *records[1].reference_length_mut() = Some(1000);
+        let index = Index::<V3>::new(2, records);
+
+        let mut writer = Vec::new();
+        index.write(&mut writer)?;
+
+        let read = Index::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(read, index);
+        assert_eq!(read.contig_alleles(0), 2);
+        assert_eq!(read.contig_alleles(1), 1);
+
+        let raw_index = RawIndex::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(raw_index.into_index()?, index);
+
+        let mut record_writer = Vec::new();
+        index.records()[1].write(&mut record_writer)?;
+        assert_eq!(
+            Record::<V3>::read(&mut record_writer.as_slice())?.alleles(),
+            Some(1)
+        );
+
+        Ok(())
+    }
+}
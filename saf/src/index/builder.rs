@@ -0,0 +1,177 @@
+use std::{io, marker::PhantomData};
+
+use byteorder::{ReadBytesExt, LE};
+
+use crate::{
+    reader::ReaderExt,
+    version::{Version, V3, V4},
+    POSITION_BYTES,
+};
+
+use super::{Index, Record};
+
+/// A builder of an index from the position and item files and known contig boundaries.
+///
+/// This may be used to recover from a lost or corrupt index where the number of sites on each
+/// contig is known, for instance from the original input data. Unlike [`reconstruct_index`],
+/// contig boundaries are not inferred from positions, and so contig names are preserved and
+/// adjacent contigs may have arbitrary positions. The position and item files are walked to find
+/// the virtual offsets of the start of each contig, but positions and likelihoods are skipped
+/// without being decoded. Positions are assumed to be stored as 32-bit integers.
+///
+/// The built index can be written to a file using [`Index::write_to_path`].
+///
+/// [`reconstruct_index`]: super::reconstruct_index
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexBuilder<V> {
+    alleles: usize,
+    contigs: Vec<(String, usize)>,
+    v: PhantomData<V>,
+}
+
+impl<V> IndexBuilder<V>
+where
+    V: Version,
+{
+    /// Adds a contig with the provided name and number of sites.
+    ///
+    /// Contigs must be added in the order they occur in the position and item files.
+    pub fn add_contig(&mut self, name: String, sites: usize) {
+        self.contigs.push((name, sites));
+    }
+
+    /// Creates a new builder for an index with the provided number of alleles and no contigs.
+    pub fn new(alleles: usize) -> Self {
+        Self {
+            alleles,
+            contigs: Vec::new(),
+            v: PhantomData,
+        }
+    }
+
+    /// Walks the position and item files, calling `skip_items` to skip the items of each contig.
+    ///
+    /// The function `create_record` is passed the contig name, number of sites, position offset,
+    /// item offset, and the value returned by `skip_items` for the contig, and creates the record.
+    fn build_with<R, S, T, F>(
+        &self,
+        position_reader: &mut bgzf::Reader<R>,
+        item_reader: &mut bgzf::Reader<R>,
+        mut skip_items: S,
+        create_record: F,
+    ) -> io::Result<Index<V>>
+    where
+        R: io::Read,
+        S: FnMut(&mut bgzf::Reader<R>, usize) -> io::Result<T>,
+        F: Fn(String, usize, u64, u64, T) -> Record<V>,
+    {
+        V::read_magic(position_reader)?;
+        V::read_magic(item_reader)?;
+
+        let mut records = Vec::with_capacity(self.contigs.len());
+
+        for (name, sites) in self.contigs.iter() {
+            let position_offset = u64::from(position_reader.virtual_position());
+            let item_offset = u64::from(item_reader.virtual_position());
+
+            skip_bytes(position_reader, sites * POSITION_BYTES)?;
+            let extra = skip_items(item_reader, *sites)?;
+
+            records.push(create_record(
+                name.clone(),
+                *sites,
+                position_offset,
+                item_offset,
+                extra,
+            ));
+        }
+
+        if position_reader.is_data_left()? || item_reader.is_data_left()? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "data left in SAF position or item file after all contig sites",
+            ));
+        }
+
+        Ok(Index::new(self.alleles, records))
+    }
+}
+
+impl IndexBuilder<V3> {
+    /// Builds the index by walking the position and item files.
+    ///
+    /// The streams are assumed to be positioned at the start, and the magic numbers will be read.
+    /// Returns an error if either file ends before the number of sites given by the contigs, or if
+    /// either file has data left after.
+    pub fn build<R>(
+        &self,
+        position_reader: &mut bgzf::Reader<R>,
+        item_reader: &mut bgzf::Reader<R>,
+    ) -> io::Result<Index<V3>>
+    where
+        R: io::Read,
+    {
+        let alleles = self.alleles;
+
+        self.build_with(
+            position_reader,
+            item_reader,
+            |reader, sites| reader.skip_likelihoods(sites * (alleles + 1)),
+            |name, sites, position_offset, item_offset, ()| {
+                Record::new(name, sites, position_offset, item_offset)
+            },
+        )
+    }
+}
+
+impl IndexBuilder<V4> {
+    /// Builds the index by walking the position and item files.
+    ///
+    /// The band header of each item is read to find the sum of band lengths for each contig,
+    /// but likelihoods are skipped. Otherwise, see [`IndexBuilder::<V3>::build`].
+    pub fn build<R>(
+        &self,
+        position_reader: &mut bgzf::Reader<R>,
+        item_reader: &mut bgzf::Reader<R>,
+    ) -> io::Result<Index<V4>>
+    where
+        R: io::Read,
+    {
+        self.build_with(
+            position_reader,
+            item_reader,
+            |reader, sites| {
+                let mut sum_band = 0;
+
+                for _ in 0..sites {
+                    let _start = reader.read_u32::<LE>()?;
+                    let len = reader.read_u32::<LE>()? as usize;
+                    reader.skip_likelihoods(len)?;
+
+                    sum_band += len;
+                }
+
+                Ok(sum_band)
+            },
+            |name, sites, position_offset, item_offset, sum_band| {
+                Record::new_with_sum_band(name, sites, sum_band, position_offset, item_offset)
+            },
+        )
+    }
+}
+
+fn skip_bytes<R>(reader: &mut R, n: usize) -> io::Result<()>
+where
+    R: io::BufRead,
+{
+    let skip_bytes = n as u64;
+
+    if io::copy(&mut io::Read::take(reader, skip_bytes), &mut io::sink())? == skip_bytes {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "failed to skip positions",
+        ))
+    }
+}
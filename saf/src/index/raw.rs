@@ -0,0 +1,320 @@
+use std::{fs, io, path::Path, str};
+
+use crate::{
+    reader::ReaderExt,
+    version::{Version, V4},
+};
+
+use super::{
+    check_reference_lengths_entry, read_entry,
+    traits::{invalid_name_err, truncated_err, CountingReader},
+    Entry, Index, IndexReaderExt, PositionBase, PositionWidth, Record,
+};
+
+/// A SAF file index with unvalidated contig names.
+///
+/// Validating each contig name as UTF-8 and allocating a string for it can be wasteful when
+/// reading very large indexes where the names are not needed. The raw index instead keeps the raw
+/// bytes of each name, which are validated only on access. See [`RawRecord::name`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawIndex<V> {
+    alleles: usize,
+    records: Vec<RawRecord<V>>,
+    site_checksum: Option<usize>,
+    position_width: PositionWidth,
+    position_base: PositionBase,
+}
+
+impl<V> RawIndex<V>
+where
+    V: Version,
+{
+    /// Returns the number of alleles.
+    ///
+    /// This is equal to `2N` for `N` diploid individuals.
+    pub fn alleles(&self) -> usize {
+        self.alleles
+    }
+
+    /// Converts the raw index into an index, validating all contig names.
+    ///
+    /// Returns an error if any contig name is not valid UTF-8.
+    pub fn into_index(self) -> io::Result<Index<V>> {
+        let records = self
+            .records
+            .into_iter()
+            .map(RawRecord::into_record)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut index = Index::new(self.alleles, records);
+        *index.site_checksum_mut() = self.site_checksum;
+        *index.position_width_mut() = self.position_width;
+        *index.position_base_mut() = self.position_base;
+
+        Ok(index)
+    }
+
+    /// Returns the raw index records, consuming `self`.
+    pub fn into_records(self) -> Vec<RawRecord<V>> {
+        self.records
+    }
+
+    /// Reads a new raw index from a reader.
+    ///
+    /// The stream is assumed to be positioned at the start. If the index is truncated in the middle
+    /// of a record, the returned error reports the number of records read and the byte offset at
+    /// which the truncation occurred.
+    pub fn read<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: io::BufRead,
+    {
+        let mut reader = CountingReader::new(reader);
+
+        V::read_magic(&mut reader)?;
+
+        let alleles = reader.read_alleles()?;
+
+        let mut records = Vec::new();
+        let mut site_checksum = None;
+        let mut position_width = PositionWidth::U32;
+        let mut position_base = PositionBase::Zero;
+        let mut has_reference_lengths = false;
+        let mut contig_alleles = None;
+        while reader.is_data_left()? {
+            let entry = read_entry(&mut reader)
+                .map_err(|e| truncated_err(e, records.len(), reader.offset()))?;
+
+            match entry {
+                Entry::Record(name_len) => {
+                    let record = RawRecord::read_with_name_len(&mut reader, name_len)
+                        .and_then(|mut record| {
+                            *record.record.alleles_mut() = contig_alleles.take();
+
+                            if has_reference_lengths {
+                                *record.record.reference_length_mut() =
+                                    reader.read_reference_length()?;
+                            }
+
+                            Ok(record)
+                        })
+                        .map_err(|e| truncated_err(e, records.len(), reader.offset()))?;
+
+                    records.push(record)
+                }
+                Entry::ReferenceLengths => {
+                    has_reference_lengths = check_reference_lengths_entry(records.len())?;
+                }
+                Entry::ContigAlleles(alleles) => contig_alleles = Some(alleles),
+                Entry::PositionWidth(width) => position_width = width,
+                Entry::PositionBase(base) => position_base = base,
+                Entry::SiteChecksum(sites) => {
+                    site_checksum = Some(sites);
+                    break;
+                }
+            }
+        }
+
+        Ok(Self {
+            alleles,
+            records,
+            site_checksum,
+            position_width,
+            position_base,
+        })
+    }
+
+    /// Creates a new raw index by reading from a path.
+    pub fn read_from_path<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        fs::File::open(path)
+            .map(io::BufReader::new)
+            .and_then(|mut reader| Self::read(&mut reader))
+    }
+
+    /// Returns the base of positions in the position file.
+    ///
+    /// See [`Index::position_base`] for details.
+    pub fn position_base(&self) -> PositionBase {
+        self.position_base
+    }
+
+    /// Returns the width of positions in the position file.
+    ///
+    /// See [`Index::position_width`] for details.
+    pub fn position_width(&self) -> PositionWidth {
+        self.position_width
+    }
+
+    /// Returns the raw index records.
+    pub fn records(&self) -> &[RawRecord<V>] {
+        self.records.as_ref()
+    }
+
+    /// Returns the site checksum, if any.
+    ///
+    /// See [`Index::site_checksum`] for details.
+    pub fn site_checksum(&self) -> Option<usize> {
+        self.site_checksum
+    }
+
+    /// Returns the total number of sites.
+    pub fn total_sites(&self) -> usize {
+        self.records.iter().map(|rec| rec.sites()).sum()
+    }
+}
+
+/// A SAF index record with an unvalidated contig name.
+///
+/// See [`RawIndex`] for details.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawRecord<V> {
+    name: Vec<u8>,
+    // Record with an empty name, where name is kept separately above
+    record: Record<V>,
+}
+
+impl<V> RawRecord<V>
+where
+    V: Version,
+{
+    /// Converts the raw record into a record, validating the contig name.
+    ///
+    /// Returns an error if the contig name is not valid UTF-8.
+    pub fn into_record(self) -> io::Result<Record<V>> {
+        let mut record = self.record;
+        *record.name_mut() = String::from_utf8(self.name).map_err(|_| invalid_name_err())?;
+
+        Ok(record)
+    }
+
+    /// Returns the item offset.
+    ///
+    /// This is the byte offset into the item file at which the reference sequence data begins.
+    pub fn item_offset(&self) -> u64 {
+        self.record.item_offset()
+    }
+
+    /// Returns the reference sequence name, validating it as UTF-8.
+    ///
+    /// Returns an error if the name is not valid UTF-8.
+    pub fn name(&self) -> io::Result<&str> {
+        str::from_utf8(&self.name).map_err(|_| invalid_name_err())
+    }
+
+    /// Returns the raw bytes of the reference sequence name.
+    pub fn name_bytes(&self) -> &[u8] {
+        &self.name
+    }
+
+    /// Returns the position offset.
+    ///
+    /// This is the byte offset into the position file at which the reference sequence data begins.
+    pub fn position_offset(&self) -> u64 {
+        self.record.position_offset()
+    }
+
+    /// Reads a raw record from a reader.
+    ///
+    /// The stream is assumed to be positioned immediately in front of a record.
+    pub fn read<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: io::BufRead,
+    {
+        let name_len = reader.read_contig_name_len()?;
+
+        Self::read_with_name_len(reader, name_len)
+    }
+
+    /// Reads a raw record from a reader, using an already read contig name length.
+    fn read_with_name_len<R>(reader: &mut R, name_len: usize) -> io::Result<Self>
+    where
+        R: io::BufRead,
+    {
+        let name = reader.read_contig_name_bytes_with_len(name_len)?;
+        let record = V::read_index_record_with_name(reader, String::new())?;
+
+        Ok(Self { name, record })
+    }
+
+    /// Returns the total length of the reference sequence, if known.
+    ///
+    /// See [`Record::reference_length`] for details.
+    pub fn reference_length(&self) -> Option<u64> {
+        self.record.reference_length()
+    }
+
+    /// Returns the record sites.
+    ///
+    /// This is the number of sites on the reference sequence contained in the position and item
+    /// files.
+    pub fn sites(&self) -> usize {
+        self.record.sites()
+    }
+}
+
+impl RawRecord<V4> {
+    /// Returns the record sum of bands.
+    pub fn sum_band(&self) -> usize {
+        self.record.sum_band()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{index::IndexWriterExt, version::V3};
+
+    fn write_raw_record(writer: &mut Vec<u8>, name: &[u8], sites: usize) -> io::Result<()> {
+        writer.extend(name.len().to_le_bytes());
+        writer.extend(name);
+        writer.write_sites(sites)?;
+        writer.write_position_offset(8)?;
+        writer.write_item_offset(8)
+    }
+
+    #[test]
+    fn test_read_raw_index_validates_on_demand() -> io::Result<()> {
+        let mut writer = Vec::new();
+        V3::write_magic(&mut writer)?;
+        writer.write_alleles(4)?;
+        write_raw_record(&mut writer, b"chr1", 3)?;
+        write_raw_record(&mut writer, &[b'c', 0xff, 0xfe], 5)?;
+
+        let index = RawIndex::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(index.alleles(), 4);
+        assert_eq!(index.total_sites(), 8);
+
+        let records = index.records();
+        assert_eq!(records[0].name_bytes(), b"chr1");
+        assert_eq!(records[0].name()?, "chr1");
+        assert_eq!(records[1].name_bytes(), &[b'c', 0xff, 0xfe]);
+        assert_eq!(records[1].sites(), 5);
+        assert!(records[1].name().is_err());
+
+        assert!(index.into_index().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_index_into_index() -> io::Result<()> {
+        let index = Index::<V3>::new(
+            2,
+            vec![
+                Record::new("chr1".to_string(), 1, 8, 8),
+                Record::new("chr2".to_string(), 2, 12, 20),
+            ],
+        );
+
+        let mut writer = Vec::new();
+        index.write(&mut writer)?;
+
+        let raw_index = RawIndex::<V3>::read(&mut writer.as_slice())?;
+        assert_eq!(raw_index.into_index()?, index);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use crate::{
+    reader::ReaderExt,
+    version::{Version, V3},
+    ReadStatus,
+};
+
+use super::{Index, Record};
+
+/// Reconstructs a [`V3`] index from the position and item files.
+///
+/// This may be used to recover from a lost or corrupt index. The position and item readers are
+/// walked in lockstep, and contig boundaries are inferred from the positions: since positions
+/// are sorted within each contig, a position that is not greater than the previous position is
+/// assumed to start a new contig. Hence, this relies on positions resetting on each new contig.
+/// In particular, if a contig starts at a position greater than the last position on the
+/// preceding contig, the two contigs will be merged in the reconstructed index.
+///
+/// Since contig names are not recoverable from the position and item files, names are created
+/// by `contig_namer` from the zero-based index of each contig.
+///
+/// The streams are assumed to be positioned at the start, and the magic numbers will be read.
+pub fn reconstruct_index<R, F>(
+    position_reader: &mut bgzf::Reader<R>,
+    item_reader: &mut bgzf::Reader<R>,
+    alleles: usize,
+    contig_namer: F,
+) -> io::Result<Index<V3>>
+where
+    R: io::Read,
+    F: Fn(usize) -> String,
+{
+    V3::read_magic(position_reader)?;
+    V3::read_magic(item_reader)?;
+
+    let mut records: Vec<Record<V3>> = Vec::new();
+    let mut previous_position = None;
+
+    loop {
+        let position_offset = u64::from(position_reader.virtual_position());
+        let item_offset = u64::from(item_reader.virtual_position());
+
+        let position = match position_reader.read_position()? {
+            Some(position) => position,
+            None => break,
+        };
+
+        if ReadStatus::check(item_reader)?.is_done() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "reached EoF in SAF item file before reaching EoF in SAF position file",
+            ));
+        }
+        item_reader.skip_likelihoods(alleles + 1)?;
+
+        match (records.last_mut(), previous_position) {
+            (Some(record), Some(previous)) if position > previous => *record.sites_mut() += 1,
+            _ => {
+                let name = contig_namer(records.len());
+                records.push(Record::new(name, 1, position_offset, item_offset));
+            }
+        }
+
+        previous_position = Some(position);
+    }
+
+    if ReadStatus::check(item_reader)?.is_not_done() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "reached EoF in SAF position file before reaching EoF in SAF item file",
+        ));
+    }
+
+    Ok(Index::new(alleles, records))
+}
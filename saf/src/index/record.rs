@@ -2,20 +2,38 @@ use std::{fmt, io, marker::PhantomData};
 
 use crate::version::{Version, V3, V4};
 
+use super::{
+    traits::{invalid_name_err, CONTIG_ALLELES_MARKER},
+    IndexReaderExt, IndexWriterExt,
+};
+
 /// A SAF index record.
 ///
 /// Each index record corresponds to a contig contained in the associated SAF files.
 ///
 /// The [`V3`] and [`V4`] records differ in whether the record contains the sum of band information
-/// for the record in question.
+/// for the record in question. When serialized with the `serde` feature, the sum of band is
+/// omitted for [`V3`] records, and deserializing a [`V4`] record without it is an error.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "SerdeRecord",
+        bound(serialize = "", deserialize = "V: Version")
+    )
+)]
 pub struct Record<V> {
     name: String,
     sites: usize,
     // We maintain the invariant is that `sum_band` is always `None` for V3 and `Some` for V4
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     sum_band: Option<usize>,
     position_offset: u64,
     item_offset: u64,
+    alleles: Option<usize>,
+    reference_length: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     v: PhantomData<V>,
 }
 
@@ -23,6 +41,26 @@ impl<V> Record<V>
 where
     V: Version,
 {
+    /// Returns the number of alleles override for the reference sequence, if any.
+    ///
+    /// By default, all reference sequences share the number of alleles defined in the index. In
+    /// rare cases, such as sex chromosomes with different ploidy, a reference sequence may have a
+    /// different number of alleles, which can be set here. The override is an extension to the
+    /// SAF index format: it is written preceding the record only if set, and is absent for legacy
+    /// files. See also [`Writer::set_contig_alleles`].
+    ///
+    /// [`Writer::set_contig_alleles`]: crate::Writer::set_contig_alleles
+    pub fn alleles(&self) -> Option<usize> {
+        self.alleles
+    }
+
+    /// Returns a mutable reference to the number of alleles override for the reference sequence.
+    ///
+    /// See [`Self::alleles`] for details.
+    pub fn alleles_mut(&mut self) -> &mut Option<usize> {
+        &mut self.alleles
+    }
+
     /// Returns the reference sequence name, consuming `self`.
     pub fn into_name(self) -> String {
         self.name
@@ -68,12 +106,44 @@ where
 
     /// Reads a record from a reader.
     ///
-    /// The stream is assumed to be positioned immediately in front of a record.
+    /// The stream is assumed to be positioned immediately in front of a record, or in front of
+    /// the number of alleles override preceding it, see [`Self::alleles`].
     pub fn read<R>(reader: &mut R) -> io::Result<Self>
     where
         R: io::BufRead,
     {
-        V::read_index_record(reader)
+        let mut name_len = reader.read_contig_name_len()?;
+
+        let mut alleles = None;
+        if name_len == CONTIG_ALLELES_MARKER {
+            alleles = Some(reader.read_contig_alleles()?);
+            name_len = reader.read_contig_name_len()?;
+        }
+
+        let name = reader.read_contig_name_bytes_with_len(name_len)?;
+        let name = String::from_utf8(name).map_err(|_| invalid_name_err())?;
+
+        let mut record = V::read_index_record_with_name(reader, name)?;
+        record.alleles = alleles;
+
+        Ok(record)
+    }
+
+    /// Returns the total length of the reference sequence, if known.
+    ///
+    /// This is the length of the reference sequence in the reference genome, as opposed to the
+    /// number of sites with data. Reference lengths are an extension to the SAF index format: they
+    /// are read and written with the index only if present, and are absent for legacy files.
+    /// Note that reference lengths are not read or written by [`Self::read`] and [`Self::write`].
+    pub fn reference_length(&self) -> Option<u64> {
+        self.reference_length
+    }
+
+    /// Returns a mutable reference to the total length of the reference sequence.
+    ///
+    /// See [`Self::reference_length`] for details.
+    pub fn reference_length_mut(&mut self) -> &mut Option<u64> {
+        &mut self.reference_length
     }
 
     /// Returns the record sites.
@@ -92,11 +162,31 @@ where
         &mut self.sites
     }
 
+    /// Returns the site density of the reference sequence, if its reference length is known.
+    ///
+    /// This is the number of sites divided by the reference length.
+    pub fn site_density(&self) -> Option<f64> {
+        self.reference_length
+            .map(|reference_length| self.sites as f64 / reference_length as f64)
+    }
+
+    /// Returns the record sum of bands, if the version has one.
+    pub(crate) fn sum_band_if_any(&self) -> Option<usize> {
+        self.sum_band
+    }
+
     /// Writes a record to a writer.
+    ///
+    /// If set, the number of alleles override is written preceding the record, see
+    /// [`Self::alleles`].
     pub fn write<W>(&self, writer: &mut W) -> io::Result<()>
     where
         W: io::Write,
     {
+        if let Some(alleles) = self.alleles {
+            writer.write_contig_alleles(alleles)?;
+        }
+
         V::write_index_record(writer, self)
     }
 }
@@ -110,6 +200,8 @@ impl Record<V3> {
             sum_band: None,
             position_offset,
             item_offset,
+            alleles: None,
+            reference_length: None,
             v: PhantomData,
         }
     }
@@ -130,6 +222,8 @@ impl Record<V4> {
             sum_band: Some(sum_band),
             position_offset,
             item_offset,
+            alleles: None,
+            reference_length: None,
             v: PhantomData,
         }
     }
@@ -161,3 +255,55 @@ impl fmt::Display for Record<V4> {
         )
     }
 }
+
+/// The serialized form of an index record, used to check the sum of band when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerdeRecord {
+    name: String,
+    sites: usize,
+    sum_band: Option<usize>,
+    position_offset: u64,
+    item_offset: u64,
+    alleles: Option<usize>,
+    reference_length: Option<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl<V> TryFrom<SerdeRecord> for Record<V>
+where
+    V: Version,
+{
+    type Error = String;
+
+    fn try_from(record: SerdeRecord) -> Result<Self, Self::Error> {
+        match (V::VERSION, record.sum_band) {
+            (3, Some(_)) => {
+                return Err(format!(
+                    "unexpected sum of band for SAF V3 index record '{}'",
+                    record.name
+                ))
+            }
+            (3, None) => (),
+            (_, None) => {
+                return Err(format!(
+                    "missing sum of band for SAF V{} index record '{}'",
+                    V::VERSION,
+                    record.name
+                ))
+            }
+            (_, Some(_)) => (),
+        }
+
+        Ok(Self {
+            name: record.name,
+            sites: record.sites,
+            sum_band: record.sum_band,
+            position_offset: record.position_offset,
+            item_offset: record.item_offset,
+            alleles: record.alleles,
+            reference_length: record.reference_length,
+            v: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use crate::version::{Version, V4};
+
+use super::Index;
+
+/// Summary statistics of a SAF index.
+///
+/// Created using [`Index::summary`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IndexSummary {
+    contigs: usize,
+    total_sites: usize,
+    min_sites: usize,
+    max_sites: usize,
+    total_sum_band: Option<usize>,
+}
+
+impl IndexSummary {
+    /// Returns the number of contigs in the index.
+    pub fn contigs(&self) -> usize {
+        self.contigs
+    }
+
+    /// Returns the largest number of sites on any contig.
+    ///
+    /// This is zero if the index has no contigs.
+    pub fn max_sites(&self) -> usize {
+        self.max_sites
+    }
+
+    /// Returns the smallest number of sites on any contig.
+    ///
+    /// This is zero if the index has no contigs.
+    pub fn min_sites(&self) -> usize {
+        self.min_sites
+    }
+
+    /// Returns the total sum of bands across all contigs.
+    ///
+    /// This is `None` for [`V3`](crate::version::V3) indices, which do not store the sum of bands.
+    pub fn total_sum_band(&self) -> Option<usize> {
+        self.total_sum_band
+    }
+
+    /// Returns the total number of sites in the index.
+    pub fn total_sites(&self) -> usize {
+        self.total_sites
+    }
+}
+
+impl<V> From<&Index<V>> for IndexSummary
+where
+    V: Version,
+{
+    fn from(index: &Index<V>) -> Self {
+        let records = index.records();
+        let sites = || records.iter().map(|record| record.sites());
+
+        Self {
+            contigs: records.len(),
+            total_sites: sites().sum(),
+            min_sites: sites().min().unwrap_or(0),
+            max_sites: sites().max().unwrap_or(0),
+            total_sum_band: (V::VERSION == V4::VERSION).then(|| {
+                records
+                    .iter()
+                    .filter_map(|record| record.sum_band_if_any())
+                    .sum()
+            }),
+        }
+    }
+}
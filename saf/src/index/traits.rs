@@ -1,6 +1,41 @@
 use std::{io, mem};
 
-use crate::reader::ReaderExt;
+use crate::{reader::ReaderExt, Error};
+
+use super::{PositionBase, PositionWidth};
+
+/// The marker preceding the site checksum at the end of an index.
+///
+/// The marker takes the place of the contig name length of a record, and so must be a value that
+/// cannot occur as such.
+pub(crate) const SITE_CHECKSUM_MARKER: usize = usize::MAX;
+
+/// The marker preceding index records that are each followed by a reference length.
+///
+/// Like [`SITE_CHECKSUM_MARKER`], the marker takes the place of the contig name length of a
+/// record. Indexes without the marker are read as not having reference lengths.
+pub(crate) const REFERENCE_LENGTHS_MARKER: usize = usize::MAX - 1;
+
+/// The marker preceding the width of positions in the position file.
+///
+/// Like [`SITE_CHECKSUM_MARKER`], the marker takes the place of the contig name length of a
+/// record. Indexes without the marker are read as having 32-bit positions.
+pub(crate) const POSITION_WIDTH_MARKER: usize = usize::MAX - 2;
+
+/// The marker preceding the base of positions in the position file.
+///
+/// Like [`SITE_CHECKSUM_MARKER`], the marker takes the place of the contig name length of a
+/// record. Indexes without the marker are read as having 0-based positions.
+pub(crate) const POSITION_BASE_MARKER: usize = usize::MAX - 3;
+
+/// The marker preceding the number of alleles for the immediately following record.
+///
+/// Like [`SITE_CHECKSUM_MARKER`], the marker takes the place of the contig name length of a
+/// record. Records without the marker are read as having no number of alleles override.
+pub(crate) const CONTIG_ALLELES_MARKER: usize = usize::MAX - 4;
+
+/// The value used to represent a missing reference length.
+const MISSING_REFERENCE_LENGTH: u64 = u64::MAX;
 
 /// An extension trait for reading indexes
 pub trait IndexReaderExt: ReaderExt {
@@ -10,19 +45,55 @@ pub trait IndexReaderExt: ReaderExt {
     /// The stream is assumed to be positioned immediately before the alleles value.
     fn read_alleles(&mut self) -> io::Result<usize>;
 
+    /// Reads the number of alleles override of a record following [`CONTIG_ALLELES_MARKER`].
+    fn read_contig_alleles(&mut self) -> io::Result<usize>;
+
     /// Reads the contig name of a next record.
     ///
     /// The stream is assumed to be positioned immediately before a the usize giving the number
     /// of characters in a record contig name.
     fn read_contig_name(&mut self) -> io::Result<String>;
 
+    /// Reads the contig name of a next record without validating it as UTF-8.
+    ///
+    /// The stream is assumed to be positioned immediately before a the usize giving the number
+    /// of characters in a record contig name.
+    fn read_contig_name_bytes(&mut self) -> io::Result<Vec<u8>> {
+        let name_len = self.read_contig_name_len()?;
+
+        self.read_contig_name_bytes_with_len(name_len)
+    }
+
+    /// Reads the contig name of a next record with a known length without validating it as UTF-8.
+    ///
+    /// The stream is assumed to be positioned immediately after the usize giving the number of
+    /// characters in a record contig name.
+    fn read_contig_name_bytes_with_len(&mut self, name_len: usize) -> io::Result<Vec<u8>>;
+
+    /// Reads the number of characters in the contig name of a next record.
+    ///
+    /// If this is equal to [`SITE_CHECKSUM_MARKER`], the site checksum follows instead of a
+    /// record.
+    fn read_contig_name_len(&mut self) -> io::Result<usize>;
+
     /// Reads the item offset of a record.
     fn read_item_offset(&mut self) -> io::Result<u64>;
 
     /// Reads the position offset of a record.
     fn read_position_offset(&mut self) -> io::Result<u64>;
 
+    /// Reads the position base following [`POSITION_BASE_MARKER`].
+    fn read_position_base(&mut self) -> io::Result<PositionBase>;
+
+    /// Reads the position width following [`POSITION_WIDTH_MARKER`].
+    fn read_position_width(&mut self) -> io::Result<PositionWidth>;
+
+    /// Reads the reference length of a record, if present.
+    fn read_reference_length(&mut self) -> io::Result<Option<u64>>;
+
     /// Reads the number of sites for a record.
+    ///
+    /// This is also used to read the site checksum following [`SITE_CHECKSUM_MARKER`].
     fn read_sites(&mut self) -> io::Result<usize>;
 
     /// Reads the sum of bins for a record.
@@ -37,19 +108,25 @@ where
         read_usize(self)
     }
 
+    fn read_contig_alleles(&mut self) -> io::Result<usize> {
+        read_usize(self)
+    }
+
     fn read_contig_name(&mut self) -> io::Result<String> {
-        let mut usize_buf = [0; mem::size_of::<usize>()];
-        self.read_exact(&mut usize_buf)?;
-        let name_len = usize::from_le_bytes(usize_buf);
+        let name_buf = self.read_contig_name_bytes()?;
 
+        String::from_utf8(name_buf).map_err(|_| invalid_name_err())
+    }
+
+    fn read_contig_name_bytes_with_len(&mut self, name_len: usize) -> io::Result<Vec<u8>> {
         let mut name_buf = vec![0; name_len];
         self.read_exact(&mut name_buf)?;
-        String::from_utf8(name_buf).map_err(|_| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                "index record name not valid UTF8",
-            )
-        })
+
+        Ok(name_buf)
+    }
+
+    fn read_contig_name_len(&mut self) -> io::Result<usize> {
+        read_usize(self)
     }
 
     fn read_item_offset(&mut self) -> io::Result<u64> {
@@ -60,6 +137,32 @@ where
         read_u64(self)
     }
 
+    fn read_position_base(&mut self) -> io::Result<PositionBase> {
+        let offset = read_u64(self)?;
+
+        PositionBase::from_offset(offset).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid position base in index: {offset}"),
+            )
+        })
+    }
+
+    fn read_position_width(&mut self) -> io::Result<PositionWidth> {
+        let bytes = read_usize(self)?;
+
+        PositionWidth::from_bytes(bytes).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid position width in index: {bytes} bytes"),
+            )
+        })
+    }
+
+    fn read_reference_length(&mut self) -> io::Result<Option<u64>> {
+        read_u64(self).map(|v| (v != MISSING_REFERENCE_LENGTH).then_some(v))
+    }
+
     fn read_sites(&mut self) -> io::Result<usize> {
         read_usize(self)
     }
@@ -76,6 +179,9 @@ pub trait IndexWriterExt {
     /// This is a usize and follows immediately after the magic numbers in all supported formats.
     fn write_alleles(&mut self, alleles: usize) -> io::Result<()>;
 
+    /// Writes the number of alleles override of a record, preceded by [`CONTIG_ALLELES_MARKER`].
+    fn write_contig_alleles(&mut self, alleles: usize) -> io::Result<()>;
+
     /// Writes the contig name of a next record.
     fn write_contig_name(&mut self, contig_name: &str) -> io::Result<()>;
 
@@ -85,6 +191,21 @@ pub trait IndexWriterExt {
     /// Writes the position offset of a record.
     fn write_position_offset(&mut self, position_offset: u64) -> io::Result<()>;
 
+    /// Writes the position base, preceded by [`POSITION_BASE_MARKER`].
+    fn write_position_base(&mut self, position_base: PositionBase) -> io::Result<()>;
+
+    /// Writes the position width, preceded by [`POSITION_WIDTH_MARKER`].
+    fn write_position_width(&mut self, position_width: PositionWidth) -> io::Result<()>;
+
+    /// Writes the reference length of a record.
+    fn write_reference_length(&mut self, reference_length: Option<u64>) -> io::Result<()>;
+
+    /// Writes the marker that all following records are followed by a reference length.
+    fn write_reference_lengths_marker(&mut self) -> io::Result<()>;
+
+    /// Writes the site checksum, preceded by [`SITE_CHECKSUM_MARKER`].
+    fn write_site_checksum(&mut self, sites: usize) -> io::Result<()>;
+
     /// Writes the number of sites for a record.
     fn write_sites(&mut self, sites: usize) -> io::Result<()>;
 
@@ -100,6 +221,11 @@ where
         write_usize(self, alleles)
     }
 
+    fn write_contig_alleles(&mut self, alleles: usize) -> io::Result<()> {
+        write_usize(self, CONTIG_ALLELES_MARKER)?;
+        write_usize(self, alleles)
+    }
+
     fn write_contig_name(&mut self, contig_name: &str) -> io::Result<()> {
         let raw_name = contig_name.as_bytes();
         write_usize(self, raw_name.len())?;
@@ -114,6 +240,29 @@ where
         write_u64(self, position_offset)
     }
 
+    fn write_position_base(&mut self, position_base: PositionBase) -> io::Result<()> {
+        write_usize(self, POSITION_BASE_MARKER)?;
+        write_u64(self, position_base.offset())
+    }
+
+    fn write_position_width(&mut self, position_width: PositionWidth) -> io::Result<()> {
+        write_usize(self, POSITION_WIDTH_MARKER)?;
+        write_usize(self, position_width.bytes())
+    }
+
+    fn write_reference_length(&mut self, reference_length: Option<u64>) -> io::Result<()> {
+        write_u64(self, reference_length.unwrap_or(MISSING_REFERENCE_LENGTH))
+    }
+
+    fn write_reference_lengths_marker(&mut self) -> io::Result<()> {
+        write_usize(self, REFERENCE_LENGTHS_MARKER)
+    }
+
+    fn write_site_checksum(&mut self, sites: usize) -> io::Result<()> {
+        write_usize(self, SITE_CHECKSUM_MARKER)?;
+        write_usize(self, sites)
+    }
+
     fn write_sites(&mut self, sites: usize) -> io::Result<()> {
         write_usize(self, sites)
     }
@@ -123,6 +272,72 @@ where
     }
 }
 
+/// A reader that keeps track of the number of bytes read from the inner reader.
+///
+/// Used for reporting the byte offset of errors when reading an index.
+pub(crate) struct CountingReader<R> {
+    inner: R,
+    offset: u64,
+}
+
+impl<R> CountingReader<R> {
+    /// Creates a new counting reader.
+    pub fn new(inner: R) -> Self {
+        Self { inner, offset: 0 }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl<R> io::Read for CountingReader<R>
+where
+    R: io::Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R> io::BufRead for CountingReader<R>
+where
+    R: io::BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.offset += amt as u64;
+        self.inner.consume(amt)
+    }
+}
+
+/// Adds context to an error from reading an index record, if the index was truncated.
+///
+/// Other errors are returned unchanged.
+pub(crate) fn truncated_err(error: io::Error, records: usize, offset: u64) -> io::Error {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!(
+                "truncated SAF index: reached EoF at byte offset {offset} \
+                after reading {records} complete records"
+            ),
+        )
+    } else {
+        error
+    }
+}
+
+pub(crate) fn invalid_name_err() -> io::Error {
+    Error::InvalidName.into()
+}
+
 fn read_usize<R>(reader: &mut R) -> io::Result<usize>
 where
     R: io::BufRead,
@@ -25,18 +25,44 @@
 
 pub use angsd_io_core::ReadStatus;
 
+/// The number of bytes used to store a single position in the SAF position file.
+pub const POSITION_BYTES: usize = std::mem::size_of::<u32>();
+
+mod describe;
+pub use describe::{describe_readers, ReaderDescription, ReaderSetDescription};
+
+mod error;
+pub use error::Error;
+
 pub mod ext;
 
 pub mod index;
-pub use index::Index;
+pub use index::{reconstruct_index, Index};
+
+mod merge;
+pub use merge::merge;
+
+mod probe;
+pub use probe::{probe, SafProbe};
 
 pub mod reader;
 pub use reader::{Intersect, Reader, ReaderV3, ReaderV4};
 
+pub mod region;
+pub use region::Region;
+
 pub mod record;
 pub use record::Record;
 
+pub mod single_file;
+
+mod transform;
+pub use transform::transform;
+
 pub mod version;
 
+mod write_sorted;
+pub use write_sorted::write_sorted;
+
 pub mod writer;
 pub use writer::{Writer, WriterV3, WriterV4};
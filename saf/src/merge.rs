@@ -0,0 +1,57 @@
+use std::{io, path::Path};
+
+use crate::{
+    ext::member_paths_from_prefix, reader::Builder, version::Version, Index, Record, Writer,
+};
+
+/// Merges SAF files into a writer.
+///
+/// Each input is given by the shared prefix of its member paths, as when creating a reader, see
+/// [`Builder::build_from_prefix`]. All records of each input are streamed into the writer in
+/// input order, so that the writer builds a new index with offsets into the merged files. This
+/// allows recombining SAF files split by contig, such as per-chromosome runs, into a single file.
+///
+/// The inputs are checked for compatibility before any records are written, in the same way as
+/// when appending their indices, see [`Index::append`]. In particular, an error is returned if
+/// the inputs differ in their number of alleles, or if a contig name occurs in more than one
+/// input. The number of alleles of the writer must be written before merging, and should match
+/// that of the inputs. Note that the writer is not finished, so that further records may be
+/// written: use [`Writer::finish`] when done.
+pub fn merge<W, V, P>(writer: &mut Writer<W, V>, inputs: &[P]) -> io::Result<()>
+where
+    W: io::Write,
+    V: Version,
+    P: AsRef<Path>,
+{
+    let mut merged: Option<Index<V>> = None;
+    for input in inputs.iter() {
+        let [index_path, _, _] = member_paths_from_prefix(&input.as_ref().to_string_lossy());
+        let index = Index::read_from_path(index_path)?;
+
+        match merged.as_mut() {
+            Some(merged) => merged.append(index)?,
+            None => merged = Some(index),
+        }
+    }
+
+    for input in inputs.iter() {
+        let mut reader = Builder::<V>::default().build_from_prefix(input)?;
+        let mut record = reader.create_record_buf();
+
+        while reader.read_record(&mut record)?.is_not_done() {
+            let contig_id = *record.contig_id();
+            let position = record.position();
+
+            let named = Record::new(
+                reader.index().records()[contig_id].name(),
+                position,
+                record.into_item(),
+            );
+            writer.write_record(&named)?;
+
+            record = Record::new(contig_id, position, named.into_item());
+        }
+    }
+
+    Ok(())
+}
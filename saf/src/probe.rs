@@ -0,0 +1,81 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    ext::member_paths_from_prefix,
+    version::{Version, MAGIC_LEN, V3, V4},
+    Index,
+};
+
+/// Probes a SAF file by reading its index.
+///
+/// The SAF version is detected from the magic number of the index, and the index is read to
+/// summarise the file. Only the index file is read. The index path is reconstructed from the
+/// shared prefix as when creating a reader, see [`Builder::build_from_prefix`].
+///
+/// Returns an error if the index cannot be read, or if the version is not supported.
+///
+/// [`Builder::build_from_prefix`]: crate::reader::Builder::build_from_prefix
+pub fn probe<P>(prefix: P) -> io::Result<SafProbe>
+where
+    P: AsRef<Path>,
+{
+    let [index_path, _, _] = member_paths_from_prefix(&prefix.as_ref().to_string_lossy());
+
+    let bytes = fs::read(index_path)?;
+
+    match bytes.get(..MAGIC_LEN) {
+        Some(magic) if magic == V3::MAGIC_NUMBER => probe_index::<V3>(&bytes),
+        Some(magic) if magic == V4::MAGIC_NUMBER => probe_index::<V4>(&bytes),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid or unsupported SAF magic number in index",
+        )),
+    }
+}
+
+fn probe_index<V>(mut bytes: &[u8]) -> io::Result<SafProbe>
+where
+    V: Version,
+{
+    let index = Index::<V>::read(&mut bytes)?;
+
+    Ok(SafProbe {
+        version: V::VERSION,
+        alleles: index.alleles(),
+        contigs: index.records().len(),
+        total_sites: index.total_sites(),
+    })
+}
+
+/// A summary of a SAF file.
+///
+/// Created using [`probe`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SafProbe {
+    version: u8,
+    alleles: usize,
+    contigs: usize,
+    total_sites: usize,
+}
+
+impl SafProbe {
+    /// Returns the number of alleles.
+    pub fn alleles(&self) -> usize {
+        self.alleles
+    }
+
+    /// Returns the number of contigs in the index.
+    pub fn contigs(&self) -> usize {
+        self.contigs
+    }
+
+    /// Returns the total number of sites in the index.
+    pub fn total_sites(&self) -> usize {
+        self.total_sites
+    }
+
+    /// Returns the SAF version, see [`Version::VERSION`].
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
@@ -1,24 +1,74 @@
 //! Reading of the SAF format.
 
-use std::io;
+use std::{
+    cmp::Ordering,
+    io, iter,
+    num::NonZeroUsize,
+    ops::{Deref, DerefMut},
+};
 
-use crate::ReadStatus;
+use crate::{Error, ReadStatus};
 
 use super::{
-    index::Index,
-    record::{Id, Record},
-    version::{Version, V3, V4},
+    index::{CountingReader, Index, PositionBase, PositionWidth},
+    record::{Band, Contig, Id, Likelihoods, Record, Record64, Site},
+    region::Region,
+    version::{Version, MAGIC_LEN, V3, V4},
 };
 
 mod builder;
 pub use builder::Builder;
+#[cfg(feature = "mmap")]
+pub use builder::MmapReader;
+
+mod checkpoint;
+pub use checkpoint::ReaderCheckpoint;
+
+mod clamp_likelihoods;
+pub use clamp_likelihoods::ClampLikelihoods;
+
+mod columnar;
+pub use columnar::Columnar;
+
+mod depth;
+pub use depth::{depth_reader_from_prefix, WithDepth};
+
+mod events;
+pub use events::{Events, SafEvent};
+
+mod filter_finite;
+pub use filter_finite::FilterFinite;
+
+mod filter_informative;
+pub use filter_informative::FilterInformative;
+
+mod fold_likelihoods;
+pub use fold_likelihoods::FoldingReader;
 
 mod intersect;
-pub use intersect::Intersect;
+pub use intersect::{Intersect, NoSharedContigs};
+
+mod into_records;
+pub use into_records::IntoRecords;
+
+mod parallel;
+pub use parallel::ParallelReader;
+
+mod position;
+pub use position::{IndexedPositionReader, PositionReader};
+
+mod stats;
+pub use stats::CompressionStats;
+
+mod take_contigs;
+pub use take_contigs::TakeContigs;
 
 mod traits;
 pub(crate) use traits::ReaderExt;
 
+/// A function resolving the item reader for a contig ID.
+type ItemResolver<R> = Box<dyn FnMut(usize) -> io::Result<bgzf::Reader<R>>>;
+
 /// A SAF reader for the [`V3`] format.
 pub type ReaderV3<R> = Reader<R, V3>;
 
@@ -31,8 +81,12 @@ pub type ReaderV4<R> = Reader<R, V4>;
 /// Version-specific aliases [`ReaderV3`] and [`ReaderV4`] are provided for convenience.
 pub struct Reader<R, V> {
     location: Location<V>,
-    position_reader: bgzf::Reader<R>,
-    item_reader: bgzf::Reader<R>,
+    position_reader: InnerReader<R>,
+    item_reader: InnerReader<R>,
+    item_resolver: Option<ItemResolver<R>>,
+    current_contig_id: Option<usize>,
+    worker_count: Option<NonZeroUsize>,
+    magic: [u8; MAGIC_LEN],
 }
 
 impl<R, V> Reader<R, V>
@@ -40,6 +94,18 @@ where
     R: io::BufRead,
     V: Version,
 {
+    /// Returns a checkpoint of the current state of the reader.
+    ///
+    /// See [`ReaderCheckpoint`] and [`Self::restore`].
+    pub fn checkpoint(&self) -> ReaderCheckpoint {
+        ReaderCheckpoint::new(
+            u64::from(self.position_reader.virtual_position()),
+            u64::from(self.item_reader.virtual_position()),
+            self.location.contig_id,
+            self.location.sites_left_on_contig,
+        )
+    }
+
     /// Returns a new record suitable for use in reading.
     pub fn create_record_buf(&self) -> Record<Id, V::Item> {
         V::create_record_buf(self.index())
@@ -57,11 +123,120 @@ where
     ) -> Option<Self> {
         Location::setup(index).map(|location| Self {
             location,
-            position_reader,
-            item_reader,
+            position_reader: InnerReader::new(position_reader),
+            item_reader: InnerReader::new(item_reader),
+            item_resolver: None,
+            current_contig_id: None,
+            worker_count: None,
+            magic: [0; MAGIC_LEN],
         })
     }
 
+    /// Creates a new reader from an index and BGZF-compressed position and item sources.
+    ///
+    /// The sources may be any readers, such as files, in-memory buffers, or readers over remote
+    /// byte ranges; they will be wrapped in [`bgzf::Reader`]s. Seeking requires the sources to
+    /// also implement [`io::Seek`]. Unlike [`Self::from_bgzf`], the magic numbers will be read,
+    /// and so [`Self::read_magic`] should *not* be called manually.
+    ///
+    /// Returns an error if the index contains no records, or if the magic numbers do not match
+    /// the expected SAF version.
+    pub fn from_bgzf_readers(
+        index: Index<V>,
+        position_reader: R,
+        item_reader: R,
+    ) -> io::Result<Self> {
+        let mut new = Self::from_bgzf(
+            index,
+            bgzf::Reader::new(position_reader),
+            bgzf::Reader::new(item_reader),
+        )
+        .ok_or_else(|| data_err("empty index in reader construction"))?;
+        new.read_magic()?;
+
+        Ok(new)
+    }
+
+    /// Creates a new reader from its raw parts, where items are stored separately for each contig.
+    ///
+    /// Rather than a single item reader, this takes a function that resolves the item reader for
+    /// the contig with the provided ID. This function will be called to create a new item reader
+    /// on construction, whenever the reader moves to a new contig, and on seeking. The returned
+    /// item reader must be positioned at the first item of the contig: in particular, if the item
+    /// stream starts with a magic number, this must be read by the resolver. Hence,
+    /// [`Self::read_magic`] should not be used with the resulting reader, and the magic number of
+    /// the position reader should be read manually, if required.
+    ///
+    /// Returns an error if the index contains no records, or if resolving the item reader for
+    /// the first contig fails.
+    pub fn from_bgzf_with_item_resolver<F>(
+        index: Index<V>,
+        position_reader: bgzf::Reader<R>,
+        mut item_resolver: F,
+    ) -> io::Result<Self>
+    where
+        F: FnMut(usize) -> io::Result<bgzf::Reader<R>> + 'static,
+    {
+        let location =
+            Location::setup(index).ok_or_else(|| data_err("empty index in reader construction"))?;
+        let item_reader = item_resolver(location.contig_id)?;
+
+        Ok(Self {
+            location,
+            position_reader: InnerReader::new(position_reader),
+            item_reader: InnerReader::new(item_reader),
+            item_resolver: Some(Box::new(item_resolver)),
+            current_contig_id: None,
+            worker_count: None,
+            magic: [0; MAGIC_LEN],
+        })
+    }
+
+    /// Creates a reader that clamps likelihoods to a floor, consuming `self`.
+    ///
+    /// See [`ClampLikelihoods`] for details.
+    pub fn clamp_likelihoods(self, floor: f32) -> ClampLikelihoods<R, V> {
+        ClampLikelihoods::new(self, floor)
+    }
+
+    /// Creates a reader that folds likelihoods, consuming `self`.
+    ///
+    /// See [`FoldingReader`] for details.
+    pub fn fold_likelihoods(self) -> FoldingReader<R, V> {
+        let alleles = self.index().alleles();
+        FoldingReader::new(self, alleles)
+    }
+
+    /// Returns the name of the contig the reader is currently positioned on.
+    ///
+    /// This is the contig of the most recently read record, or the contig most recently sought to.
+    /// Returns `None` if nothing has been read yet, or if reading has finished.
+    pub fn current_contig_name(&self) -> Option<&str> {
+        self.current_contig_id
+            .map(|contig_id| self.index().records()[contig_id].name())
+    }
+
+    /// Returns an iterator over the events in the file.
+    ///
+    /// Reading continues from the current location of the reader. See [`Events`] for details.
+    pub fn events(&mut self) -> Events<'_, R, V> {
+        Events::new(self)
+    }
+
+    /// Creates a reader that skips sites with any non-finite likelihood, consuming `self`.
+    ///
+    /// See [`FilterFinite`] for details.
+    pub fn filter_finite(self) -> FilterFinite<R, V> {
+        FilterFinite::new(self)
+    }
+
+    /// Creates a reader that skips uninformative sites, consuming `self`.
+    ///
+    /// See [`FilterInformative`] for details.
+    pub fn filter_informative(self, min_spread: f32) -> FilterInformative<R, V> {
+        FilterInformative::new(self, min_spread)
+    }
+
     /// Returns the index.
     pub fn index(&self) -> &Index<V> {
         &self.location.index
@@ -72,9 +247,69 @@ where
         &mut self.location.index
     }
 
+    /// Returns an iterator over the remaining records as owned records, consuming `self`.
+    ///
+    /// Reading continues from the current location of the reader. See [`IntoRecords`] for
+    /// details, and [`Self::records`] for a borrowing iterator.
+    pub fn into_records(self) -> IntoRecords<R, V> {
+        IntoRecords::new(self)
+    }
+
+    /// Converts the reader into a reader decompressing positions and items on separate threads.
+    ///
+    /// Reading continues from the current location of the reader. See [`ParallelReader`] for
+    /// details.
+    ///
+    /// Returns an error if the reader was created with an item resolver, or if the index has
+    /// 64-bit or 1-based positions, none of which are supported.
+    pub fn into_parallel(self) -> io::Result<ParallelReader<V>>
+    where
+        R: Send + 'static,
+        V::Item: Clone + Send + 'static,
+    {
+        if self.item_resolver.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "parallel reading not supported with item resolver",
+            ));
+        }
+
+        if self.index().position_width() != PositionWidth::U32 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "parallel reading not supported with 64-bit positions",
+            ));
+        }
+
+        if self.index().position_base() != PositionBase::Zero {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "parallel reading not supported with 1-based positions",
+            ));
+        }
+
+        Ok(ParallelReader::new(
+            self.location,
+            self.position_reader.into_inner(),
+            self.item_reader.into_inner(),
+        ))
+    }
+
     /// Returns the inner index, position reader, and item reader, consuming `self`.
     pub fn into_parts(self) -> (Index<V>, bgzf::Reader<R>, bgzf::Reader<R>) {
-        (self.location.index, self.position_reader, self.item_reader)
+        (
+            self.location.index,
+            self.position_reader.into_inner(),
+            self.item_reader.into_inner(),
+        )
+    }
+
+    /// Returns the number of BGZF worker threads used by the inner readers, if known.
+    ///
+    /// This is known when the reader was created using a [`Builder`], or the worker count was set
+    /// using [`Self::set_worker_count`].
+    pub fn worker_count(&self) -> Option<NonZeroUsize> {
+        self.worker_count
     }
 
     /// Returns the inner item reader.
@@ -101,70 +336,683 @@ where
     ///
     /// Note that this will bring the item and position readers out of sync. Use
     /// [`Self::read_record`] instead unless you wish to manually re-sync the underlying readers.
+    ///
+    /// The item buffer is resized to fit the number of alleles of the current contig, if required.
     pub fn read_item(&mut self, buf: &mut V::Item) -> io::Result<ReadStatus> {
-        V::read_item(&mut self.item_reader, buf)
+        V::resize_item(buf, self.index().contig_alleles(self.location.contig_id));
+        V::read_item(&mut *self.item_reader, buf)
+    }
+
+    /// Returns the magic number read by the reader.
+    ///
+    /// This is populated by [`Self::read_magic`], and is all zeros before. If the magic number of
+    /// the position file is valid, this is the magic number of the item file. Otherwise, it is the
+    /// invalid magic number of the position file. This allows inspecting unexpected or corrupt
+    /// magic numbers without re-reading the files.
+    pub fn magic(&self) -> &[u8; MAGIC_LEN] {
+        &self.magic
+    }
+
+    /// Reads all remaining sites on the current contig.
+    ///
+    /// If the reader is positioned at the start of a contig, this reads the full contig. Returns
+    /// `None` if no more records are left. Note that contigs without sites are skipped, as when
+    /// reading records.
+    pub fn read_contig(&mut self) -> io::Result<Option<Contig<V>>> {
+        let mut record = self.create_record_buf();
+        if self.read_record(&mut record)?.is_done() {
+            return Ok(None);
+        }
+
+        let contig_id = *record.contig_id();
+        let name = self.index().records()[contig_id].name().to_owned();
+
+        let mut sites = Vec::with_capacity(self.location.sites_left_on_contig + 1);
+        sites.push(Site::from(record));
+
+        while !self.location.contig_is_finished() {
+            let mut record = self.create_record_buf();
+            self.read_record(&mut record)?;
+            sites.push(Site::from(record));
+        }
+
+        Ok(Some(Contig::new(name, sites)))
     }
 
     /// Reads and checks the magic numbers.
     ///
-    /// Assumes the streams are positioned at the beginning of the files.
+    /// Assumes the streams are positioned at the beginning of the files. The magic number read is
+    /// stored, see [`Self::magic`].
     pub fn read_magic(&mut self) -> io::Result<()> {
-        V::read_magic(&mut self.position_reader).and_then(|_| V::read_magic(&mut self.item_reader))
+        io::Read::read_exact(&mut *self.position_reader, &mut self.magic)?;
+        V::check_magic(&self.magic)?;
+
+        io::Read::read_exact(&mut *self.item_reader, &mut self.magic)?;
+        V::check_magic(&self.magic)
+    }
+
+    /// Returns an iterator over the contig IDs and positions of the remaining records.
+    ///
+    /// Only the position reader is read from, and the index is used to assign contig IDs to the
+    /// positions, so that items are never decompressed. This may be much faster than reading
+    /// records when only positions are required. The iterator stops after the end of the index,
+    /// or after returning an error.
+    ///
+    /// Note that the item reader is left untouched, and so this will bring the item and position
+    /// readers out of sync: reading records after advancing this iterator will pair positions with
+    /// the wrong items. To continue reading records, seek to a contig first, see [`Self::seek`].
+    pub fn positions(&mut self) -> impl Iterator<Item = io::Result<(Id, u32)>> + '_ {
+        let mut is_done = false;
+
+        iter::from_fn(move || {
+            if is_done {
+                return None;
+            }
+
+            let contig_id = match self.location.next_site() {
+                Some(contig_id) => contig_id,
+                None => {
+                    is_done = true;
+                    return None;
+                }
+            };
+
+            let result = match self.read_position() {
+                Ok(Some(position)) => {
+                    self.current_contig_id = Some(contig_id);
+                    Ok((contig_id, position))
+                }
+                Ok(None) => Err(self.truncation_err("SAF position file")),
+                Err(e) => Err(self.with_truncation_context(e, "SAF position file")),
+            };
+
+            is_done = result.is_err();
+            Some(result)
+        })
     }
 
     /// Reads a single position from the position reader.
     ///
     /// Note that this will bring the item and position readers out of sync. Use
     /// [`Self::read_record`] instead unless you wish to manually re-sync the underlying readers.
+    ///
+    /// If the index has 64-bit positions, returns an error if the position does not fit in 32
+    /// bits. See [`Self::read_position64`] for reading such positions.
     pub fn read_position(&mut self) -> io::Result<Option<u32>> {
-        self.position_reader.read_position()
+        self.read_position64()?.map(narrow_position).transpose()
+    }
+
+    /// Reads a single position from the position reader as a 64-bit position.
+    ///
+    /// The width and base of the position read is given by the index, see
+    /// [`Index::position_width`] and [`Index::position_base`]. The returned position is 0-based.
+    ///
+    /// Note that this will bring the item and position readers out of sync. Use
+    /// [`Self::read_record64`] instead unless you wish to manually re-sync the underlying readers.
+    pub fn read_position64(&mut self) -> io::Result<Option<u64>> {
+        let width = self.index().position_width();
+        let base = self.index().position_base();
+
+        self.position_reader
+            .read_position_with_width(width)?
+            .map(|position| base.to_zero_based(position))
+            .transpose()
     }
 
     /// Reads a single record.
     ///
     /// Note that the record buffer needs to be correctly set up. Use [`Self::create_record_buf`]
-    /// for a correctly initialised record buffer to use for reading.
+    /// for a correctly initialised record buffer to use for reading. If the index sets a number
+    /// of alleles for the contig of the record, the buffer is resized accordingly.
+    ///
+    /// If the index has a site checksum, it is verified on reaching the end of the index.
     pub fn read_record(&mut self, record: &mut Record<Id, V::Item>) -> io::Result<ReadStatus> {
-        if !self.location.contig_is_finished() || self.location.next_contig().is_some() {
+        self.read_record_with(record, |reader, item, alleles| {
+            V::resize_item(item, alleles);
+            V::read_item(reader, item)
+        })
+    }
+
+    /// Reads a single record with a 64-bit position.
+    ///
+    /// This is equivalent to [`Self::read_record`], except that positions that do not fit in 32
+    /// bits can be read from files with 64-bit positions. See [`Index::position_width`].
+    pub fn read_record64(&mut self, record: &mut Record64<Id, V::Item>) -> io::Result<ReadStatus> {
+        let site = self.read_site_with(record.item_mut(), |reader, item, alleles| {
+            V::resize_item(item, alleles);
+            V::read_item(reader, item)
+        })?;
+
+        Ok(match site {
+            Some((contig_id, position)) => {
+                *record.contig_id_mut() = contig_id;
+                *record.position_mut() = position;
+                ReadStatus::NotDone
+            }
+            None => ReadStatus::Done,
+        })
+    }
+
+    /// Reads multiple records.
+    ///
+    /// Records are read into the provided buffers in order until all buffers are filled, or the
+    /// end of the index is reached. Returns the number of buffers filled, which is less than the
+    /// number of buffers only if the end of the index was reached, and zero if no records remain.
+    /// Buffers past those filled are left untouched. See [`Self::read_record`] for details.
+    pub fn read_records(&mut self, bufs: &mut [Record<Id, V::Item>]) -> io::Result<usize> {
+        for (n, buf) in bufs.iter_mut().enumerate() {
+            if self.read_record(buf)?.is_done() {
+                return Ok(n);
+            }
+        }
+
+        Ok(bufs.len())
+    }
+
+    /// Returns an iterator over the remaining records.
+    ///
+    /// Each record is read into a new buffer created using [`Self::create_record_buf`], and yielded
+    /// as an owned record. This is a convenience over manually calling [`Self::read_record`] in a
+    /// loop. Reading continues from the current location of the reader, and the iterator stops
+    /// after the end of the index, or after returning an error.
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Record<Id, V::Item>>> + '_ {
+        let mut is_done = false;
+
+        iter::from_fn(move || {
+            if is_done {
+                return None;
+            }
+
+            let mut record = self.create_record_buf();
+            match self.read_record(&mut record) {
+                Ok(ReadStatus::NotDone) => Some(Ok(record)),
+                Ok(ReadStatus::Done) => {
+                    is_done = true;
+                    None
+                }
+                Err(e) => {
+                    is_done = true;
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// Returns an iterator over records at the provided coordinates.
+    ///
+    /// The coordinates are given as pairs of contig names and positions, and are merge-joined
+    /// against the records of the reader: for each coordinate, the iterator yields `Some(record)`
+    /// if the reader has a record at the coordinate, and `None` otherwise. Reading continues from
+    /// the current location of the reader, and records at other coordinates are skipped.
+    ///
+    /// The coordinates must be sorted in the same way as the records of the reader. That is,
+    /// contigs must occur in the same order as in the index, and positions must be sorted
+    /// numerically within each contig. This ensures that the join is linear in the size of both
+    /// inputs. Coordinates on contigs not in the index yield `None`.
+    ///
+    /// The iterator stops after returning an error.
+    pub fn select_positions<'a>(
+        &'a mut self,
+        sorted: &'a [(String, u32)],
+    ) -> impl Iterator<Item = io::Result<Option<Record<Id, V::Item>>>> + 'a {
+        let mut targets = sorted.iter();
+        let mut pending: Option<Record<Id, V::Item>> = None;
+        let mut is_done = false;
+        let mut has_failed = false;
+
+        iter::from_fn(move || {
+            if has_failed {
+                return None;
+            }
+
+            let (name, position) = targets.next()?;
+
+            let contig_id = match self.index().contig_id(name) {
+                Some(contig_id) => contig_id,
+                None => return Some(Ok(None)),
+            };
+            let target = (contig_id, *position);
+
+            loop {
+                if let Some(record) = pending.as_ref() {
+                    match (*record.contig_id(), record.position()).cmp(&target) {
+                        Ordering::Less => (),
+                        Ordering::Equal => return Some(Ok(pending.take())),
+                        Ordering::Greater => return Some(Ok(None)),
+                    }
+                }
+
+                if is_done {
+                    return Some(Ok(None));
+                }
+
+                // Reuse the buffer of a skipped record, if any
+                let mut buf = pending.take().unwrap_or_else(|| self.create_record_buf());
+                match self.read_record(&mut buf) {
+                    Ok(ReadStatus::NotDone) => pending = Some(buf),
+                    Ok(ReadStatus::Done) => is_done = true,
+                    Err(e) => {
+                        has_failed = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        })
+    }
+
+    /// Creates a reader that stops after a number of contigs, consuming `self`.
+    ///
+    /// See [`TakeContigs`] for details.
+    pub fn take_contigs(self, n: usize) -> TakeContigs<R, V> {
+        TakeContigs::new(self, n)
+    }
+
+    /// Returns a deterministic, uncompressed serialization of the remaining records.
+    ///
+    /// The serialization consists of the magic number and the number of alleles as a `u64`,
+    /// followed by the contig ID and position as `u64`s and the item for each site. Items are
+    /// serialized as in the item file, with likelihoods as little-endian `f32`s. All integers are
+    /// little-endian. Since this does not depend on compression, it is suitable for comparing
+    /// files in tests, e.g. against golden files.
+    ///
+    /// Reading continues from the current location of the reader, and the magic number should
+    /// already have been read using [`Self::read_magic`].
+    pub fn to_canonical_bytes(&mut self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.magic);
+        bytes.extend_from_slice(&(self.index().alleles() as u64).to_le_bytes());
+
+        let mut record = Record64::from(self.create_record_buf());
+        while self.read_record64(&mut record)?.is_not_done() {
+            bytes.extend_from_slice(&(*record.contig_id() as u64).to_le_bytes());
+            bytes.extend_from_slice(&record.position().to_le_bytes());
+            V::write_item(&mut bytes, record.item())?;
+        }
+
+        Ok(bytes)
+    }
+
+    /// Creates a reader that reads the depth of each site along with records, consuming `self`.
+    ///
+    /// If `depth_reader` is `None`, the depth of each site is read as missing. See [`WithDepth`]
+    /// for details.
+    pub fn with_depth<D>(self, depth_reader: Option<D>) -> WithDepth<R, V, D> {
+        WithDepth::new(self, depth_reader)
+    }
+
+    /// Replaces the item reader by the one for the provided contig ID, if an item resolver is set.
+    fn resolve_item_reader(&mut self, contig_id: usize) -> io::Result<()> {
+        if let Some(item_resolver) = self.item_resolver.as_mut() {
+            *self.item_reader = item_resolver(contig_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a single record, using the provided function to read the item from the item reader.
+    ///
+    /// This allows reading items in other ways than [`Version::read_item`], while maintaining the
+    /// location bookkeeping and EoF checks of [`Self::read_record`]. The function is passed the
+    /// number of alleles of the contig being read.
+    fn read_record_with<T, F>(
+        &mut self,
+        record: &mut Record<Id, T>,
+        read_item: F,
+    ) -> io::Result<ReadStatus>
+    where
+        F: FnOnce(&mut bgzf::Reader<R>, &mut T, usize) -> io::Result<ReadStatus>,
+    {
+        match self.read_site_with(record.item_mut(), read_item)? {
+            Some((contig_id, position)) => {
+                *record.contig_id_mut() = contig_id;
+                *record.position_mut() = narrow_position(position)?;
+
+                Ok(ReadStatus::NotDone)
+            }
+            None => Ok(ReadStatus::Done),
+        }
+    }
+
+    /// Reads a single site, using the provided function to read the item from the item reader.
+    ///
+    /// Returns the contig ID and position of the site, or `None` if the end of the index has been
+    /// reached. See [`Self::read_record_with`].
+    fn read_site_with<T, F>(&mut self, item: &mut T, read_item: F) -> io::Result<Option<(Id, u64)>>
+    where
+        F: FnOnce(&mut bgzf::Reader<R>, &mut T, usize) -> io::Result<ReadStatus>,
+    {
+        let contig_is_finished = self.location.contig_is_finished();
+
+        if let Some(contig_id) = self.location.next_site() {
+            if contig_is_finished {
+                self.resolve_item_reader(contig_id)?;
+            }
+
             // Index still contains data, read and check that readers are not at EoF
-            match (self.read_position()?, self.read_item(record.item_mut())?) {
-                (Some(pos), ReadStatus::NotDone) => {
-                    *record.contig_id_mut() = self.location.contig_id;
-                    *record.position_mut() = pos;
+            let position = self
+                .read_position64()
+                .map_err(|e| self.with_truncation_context(e, "SAF position file"))?;
+            let alleles = self.location.index.contig_alleles(contig_id);
+            let item_status = read_item(&mut *self.item_reader, item, alleles)
+                .map_err(|e| self.with_truncation_context(e, "SAF item file"))?;
 
-                    self.location.next_site_on_contig();
+            match (position, item_status) {
+                (Some(pos), ReadStatus::NotDone) => {
+                    self.current_contig_id = Some(contig_id);
 
-                    Ok(ReadStatus::NotDone)
+                    Ok(Some((contig_id, pos)))
                 }
-                (Some(_), ReadStatus::Done) => Err(eof_err(
-                    "reached EoF in SAF position file before reaching EoF in SAF item file",
-                )),
-                (None, ReadStatus::NotDone) => Err(eof_err(
-                    "reached EoF in SAF item file before reaching EoF in SAF position file",
-                )),
-                (None, ReadStatus::Done) => Err(eof_err(
-                    "reached EoF in both SAF files before reaching end of index",
-                )),
+                (Some(_), ReadStatus::Done) => Err(self.truncation_err("SAF item file")),
+                (None, ReadStatus::NotDone) => Err(self.truncation_err("SAF position file")),
+                (None, ReadStatus::Done) => Err(self.truncation_err("both SAF files")),
             }
         } else {
             // Reached end of index, check that readers are at EoF
-            let position_reader_is_done = ReadStatus::check(&mut self.position_reader)?.is_done();
-            let item_reader_is_done = ReadStatus::check(&mut self.item_reader)?.is_done();
+            let position_reader_is_done = ReadStatus::check(&mut *self.position_reader)?.is_done();
+            let item_reader_is_done = ReadStatus::check(&mut *self.item_reader)?.is_done();
 
             match (position_reader_is_done, item_reader_is_done) {
-                (true, true) => Ok(ReadStatus::Done),
-                (true, false) => Err(data_err(
-                    "reached end of index before reaching EoF in SAF position file",
-                )),
-                (false, true) => Err(data_err(
-                    "reached end of index before reaching EoF in SAF item file",
-                )),
-                (false, false) => Err(data_err(
-                    "reached end of index before reaching EoF in both SAF files",
-                )),
+                (true, true) => {
+                    self.current_contig_id = None;
+
+                    // Having reached the end of the index, the number of sites read is the total
+                    // defined by the index
+                    let index = self.index();
+                    match index.site_checksum() {
+                        Some(sites) if sites != index.total_sites() => Err(data_err(&format!(
+                            "number of sites read ({}) does not match site checksum ({sites})",
+                            index.total_sites()
+                        ))),
+                        _ => Ok(None),
+                    }
+                }
+                (true, false) => Err(index_exhausted_err("SAF item file")),
+                (false, true) => Err(index_exhausted_err("SAF position file")),
+                (false, false) => Err(index_exhausted_err("both SAF files")),
             }
         }
     }
+
+    /// Returns an error for a SAF file ending at the site just advanced to by the location.
+    ///
+    /// The error includes the name of the current contig and the 0-based index of the site on the
+    /// contig, see [`Error::Truncated`].
+    #[cold]
+    fn truncation_err(&self, files: &'static str) -> io::Error {
+        let record = &self.location.index.records()[self.location.contig_id];
+
+        Error::Truncated {
+            files,
+            contig: record.name().to_owned(),
+            site: record.sites() - self.location.sites_left_on_contig - 1,
+        }
+        .into()
+    }
+
+    /// Replaces an error from reading a truncated SAF file by an error with context, see
+    /// [`Self::truncation_err`].
+    ///
+    /// Other errors are returned unchanged.
+    #[cold]
+    fn with_truncation_context(&self, error: io::Error, files: &'static str) -> io::Error {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            self.truncation_err(files)
+        } else {
+            error
+        }
+    }
+}
+
+impl<R> ReaderV3<R>
+where
+    R: io::BufRead,
+{
+    /// Reads up to `n_sites` sites into a columnar layout.
+    ///
+    /// Fewer than `n_sites` sites will be read only if the end of the index is reached. See
+    /// [`Columnar`] for details.
+    ///
+    /// Returns an error if a contig overrides the number of alleles defined in the index, since all
+    /// sites in the columnar layout must have the same number of likelihoods.
+    pub fn read_columnar(&mut self, n_sites: usize) -> io::Result<Columnar> {
+        let index_alleles = self.index().alleles();
+
+        let mut columnar = Columnar::with_capacity(index_alleles, n_sites);
+        let mut record = Record::new(0, 0, ());
+
+        for _ in 0..n_sites {
+            let status = self.read_record_with(&mut record, |reader, _, alleles| {
+                if alleles != index_alleles {
+                    return Err(data_err(
+                        "cannot read contigs with differing number of alleles into columnar layout",
+                    ));
+                } else if ReadStatus::check(reader)?.is_done() {
+                    return Ok(ReadStatus::Done);
+                }
+
+                reader
+                    .read_likelihoods(columnar.push_likelihoods())
+                    .map(|_| ReadStatus::NotDone)
+            })?;
+
+            if status.is_done() {
+                break;
+            }
+
+            columnar.push(*record.contig_id(), record.position());
+        }
+
+        Ok(columnar)
+    }
+
+    /// Reads a single record, keeping only the likelihoods for sample frequencies in `lo..=hi`.
+    ///
+    /// Likelihoods outside the range are skipped in the item file without being decoded. The
+    /// record buffer must be set up to hold exactly `hi - lo + 1` likelihoods, for instance by
+    /// using [`Record::from_alleles`] with `hi - lo` alleles.
+    ///
+    /// Returns an error if `lo > hi`, if `hi` is greater than the number of alleles of the contig
+    /// being read, or if the record buffer has the wrong number of likelihoods.
+    pub fn read_record_freq_range(
+        &mut self,
+        buf: &mut Record<Id, Likelihoods>,
+        lo: usize,
+        hi: usize,
+    ) -> io::Result<ReadStatus> {
+        if lo > hi {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid sample frequency range {lo}..={hi}"),
+            ));
+        } else if buf.item().len() != hi - lo + 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "record buffer with {} likelihoods cannot hold sample frequency range {lo}..={hi}",
+                    buf.item().len()
+                ),
+            ));
+        }
+
+        self.read_record_with(buf, |reader, item, alleles| {
+            if hi > alleles {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid sample frequency range {lo}..={hi} for {alleles} alleles"),
+                ));
+            } else if ReadStatus::check(reader)?.is_done() {
+                return Ok(ReadStatus::Done);
+            }
+
+            reader.skip_likelihoods(lo)?;
+            reader.read_likelihoods(item)?;
+            reader.skip_likelihoods(alleles - hi)?;
+
+            Ok(ReadStatus::NotDone)
+        })
+    }
+
+    /// Reads all remaining records, accumulating their folded likelihoods into a folded SFS.
+    ///
+    /// The likelihoods of each site are assumed to be in log-space. They are normalised to sum to
+    /// one in linear space, and then folded by combining sample frequency `i` with `alleles - i`
+    /// before being added to `sfs`. For an even number of alleles, the middle sample frequency is
+    /// its own complement and is counted only once. Hence, `sfs` must have length
+    /// `alleles / 2 + 1`, or an error is returned. An error is likewise returned if a contig
+    /// overrides the number of alleles defined in the index.
+    pub fn fold_folded_sfs(&mut self, sfs: &mut [f64]) -> io::Result<()> {
+        let alleles = self.index().alleles();
+
+        if sfs.len() != alleles / 2 + 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "folded SFS of length {} does not match {alleles} alleles",
+                    sfs.len()
+                ),
+            ));
+        }
+
+        let mut record = self.create_record_buf();
+        let mut normalised = vec![0.0; alleles + 1];
+
+        while self.read_record(&mut record)?.is_not_done() {
+            if record.item().len() != alleles + 1 {
+                return Err(data_err(
+                    "cannot fold SFS for contigs with differing number of alleles",
+                ));
+            }
+
+            let max = record
+                .item()
+                .iter()
+                .copied()
+                .fold(f32::NEG_INFINITY, f32::max);
+            for (x, &v) in normalised.iter_mut().zip(record.item().iter()) {
+                *x = f64::from(v - max).exp();
+            }
+            let sum: f64 = normalised.iter().sum();
+
+            for (i, x) in sfs.iter_mut().enumerate() {
+                let j = alleles - i;
+
+                *x += if i == j {
+                    normalised[i] / sum
+                } else {
+                    (normalised[i] + normalised[j]) / sum
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> ReaderV4<R>
+where
+    R: io::BufRead,
+{
+    /// Reads all remaining records, returning the mean fraction of the full range of sample
+    /// frequencies covered by their bands.
+    ///
+    /// For each site, the fraction is the band length divided by `alleles + 1`, where `alleles`
+    /// is the number of alleles of the contig of the site. A low mean fraction means that banding
+    /// saves much space compared to storing the full likelihoods as in [`V3`]. Returns `NaN` if no
+    /// records remain.
+    pub fn coverage_fraction(&mut self) -> io::Result<f64> {
+        let mut record = self.create_record_buf();
+        let mut sum = 0.0;
+        let mut sites = 0;
+
+        while self.read_record(&mut record)?.is_not_done() {
+            let alleles = self.index().contig_alleles(*record.contig_id());
+            sum += record.item().len() as f64 / (alleles + 1) as f64;
+            sites += 1;
+        }
+
+        Ok(sum / sites as f64)
+    }
+
+    /// Reads a single record, expanding the band into the full set of likelihoods.
+    ///
+    /// Likelihoods not represented in the band are set to `fill`, as in [`Band::into_full`], where
+    /// the number of alleles is that of the contig being read, see [`Index::contig_alleles`]. If
+    /// the record buffer already has the full number of likelihoods, it is filled in place.
+    ///
+    /// Returns an error if the band read extends past the number of alleles.
+    pub fn read_record_full(
+        &mut self,
+        buf: &mut Record<Id, Likelihoods>,
+        fill: f32,
+    ) -> io::Result<ReadStatus> {
+        self.read_record_with(buf, |reader, likelihoods, alleles| {
+            let mut band = Band::new(0, Vec::new());
+            let status = V4::read_item(reader, &mut band)?;
+
+            if status.is_done() {
+                return Ok(status);
+            }
+
+            let (start, end) = (band.start(), band.start() + band.len());
+            if end > alleles + 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("band with start {start} and end {end} exceeds {alleles} alleles"),
+                ));
+            }
+
+            if likelihoods.len() == alleles + 1 {
+                likelihoods.fill(fill);
+                likelihoods[start..end].copy_from_slice(band.likelihoods());
+            } else {
+                *likelihoods = band.into_full(alleles, fill);
+            }
+
+            Ok(status)
+        })
+    }
+
+    /// Reads a single record, keeping only the part of the band for sample frequencies in
+    /// `lo..=hi`.
+    ///
+    /// The band read into the record buffer will be the intersection of the band stored in the
+    /// item file and the requested range. If these do not overlap, the band will be empty and start
+    /// at `lo`.
+    ///
+    /// Returns an error if `lo > hi`.
+    pub fn read_record_freq_range(
+        &mut self,
+        buf: &mut Record<Id, Band>,
+        lo: usize,
+        hi: usize,
+    ) -> io::Result<ReadStatus> {
+        if lo > hi {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid sample frequency range {lo}..={hi}"),
+            ));
+        }
+
+        self.read_record_with(buf, |reader, band, _| {
+            let status = V4::read_item(reader, band)?;
+
+            let band_start = band.start();
+            let start = band_start.max(lo);
+            let end = (band_start + band.len()).min(hi + 1);
+
+            if start < end {
+                let likelihoods = band.likelihoods_mut();
+                likelihoods.truncate(end - band_start);
+                likelihoods.drain(..start - band_start);
+                *band.start_mut() = start;
+            } else {
+                band.likelihoods_mut().clear();
+                *band.start_mut() = lo;
+            }
+
+            Ok(status)
+        })
+    }
 }
 
 impl<R, V> Reader<R, V>
@@ -172,6 +1020,52 @@ where
     R: io::BufRead + io::Seek,
     V: Version,
 {
+    /// Returns the number of sites in the file with positions satisfying a predicate.
+    ///
+    /// All sites in the file are scanned, regardless of the current location of the reader. Only
+    /// positions are decoded: the position of the inner position reader is restored afterwards,
+    /// and the item reader is not touched, so that reading may continue as before.
+    ///
+    /// Returns an error if the position file ends before the number of sites given in the index
+    /// have been read.
+    pub fn count_matching(&mut self, mut pred: impl FnMut(u32) -> bool) -> io::Result<usize> {
+        let mut count = 0;
+        self.scan_positions(|_, position| {
+            if pred(position) {
+                count += 1;
+            }
+        })?;
+
+        Ok(count)
+    }
+
+    /// Returns statistics on the compression achieved for the position and item files.
+    ///
+    /// The compressed sizes are the lengths of the inner position and item readers, while the
+    /// uncompressed sizes are computed from the index. The positions of the inner readers are
+    /// restored afterwards. If the reader was created with an item resolver, the compressed item
+    /// size is the length of the current item reader only.
+    pub fn compression_stats(&mut self) -> io::Result<CompressionStats> {
+        let index = self.index();
+
+        let uncompressed_position_bytes =
+            V::MAGIC_NUMBER.len() + index.total_sites() * index.position_width().bytes();
+        let uncompressed_item_bytes = V::MAGIC_NUMBER.len()
+            + index
+                .records()
+                .iter()
+                .enumerate()
+                .map(|(i, record)| V::contig_item_bytes(record, index.contig_alleles(i)))
+                .sum::<usize>();
+
+        Ok(CompressionStats::new(
+            compressed_len(&mut *self.position_reader)?,
+            uncompressed_position_bytes as u64,
+            compressed_len(&mut *self.item_reader)?,
+            uncompressed_item_bytes as u64,
+        ))
+    }
+
     /// Creates an intersection of two readers.
     ///
     /// The resulting intersecting readers will read only records that lie on the same contigs
@@ -181,17 +1075,189 @@ where
         Intersect::from_reader(self).intersect(other)
     }
 
+    /// Verifies that the index, position, and item files are mutually consistent.
+    ///
+    /// All contigs are read in order from the start of the files, regardless of the current
+    /// location of the reader. For each contig, this checks that the number of positions and
+    /// items match the number of sites in the index, and that positions are strictly increasing.
+    /// The number of bytes taken up by the items of each contig is also checked against the index,
+    /// which for [`V4`] amounts to checking the sum of band lengths. Finally, the files are checked
+    /// to end after the last contig. The location of the reader is restored afterwards, including
+    /// when all records have already been read.
+    ///
+    /// Returns a descriptive error on the first violation found. Such an error takes precedence
+    /// over any error restoring the location of the reader.
+    pub fn verify(&mut self) -> io::Result<()> {
+        let checkpoint = self.checkpoint();
+        let result = self.verify_contigs();
+        let restored = self.restore(checkpoint);
+
+        result.and(restored)
+    }
+
+    /// Verifies all contigs, see [`Self::verify`].
+    fn verify_contigs(&mut self) -> io::Result<()> {
+        let mut item = self.create_record_buf().into_item();
+
+        self.try_seek(0)?;
+
+        for contig_id in 0..self.index().records().len() {
+            if contig_id > 0 {
+                self.resolve_item_reader(contig_id)?;
+            }
+
+            let index = &self.location.index;
+            let record = &index.records()[contig_id];
+            let name = record.name();
+            let sites = record.sites();
+            let alleles = index.contig_alleles(contig_id);
+
+            let mut item_reader = CountingReader::new(&mut *self.item_reader);
+            let mut last_position = None;
+            for site in 0..sites {
+                let position = self
+                    .position_reader
+                    .read_position_with_width(index.position_width())?
+                    .map(|position| index.position_base().to_zero_based(position))
+                    .transpose()?
+                    .ok_or_else(|| {
+                        data_err(&format!(
+                            "SAF position file ended after {site} of {sites} sites \
+                            on contig '{name}'"
+                        ))
+                    })?;
+
+                if let Some(last_position) = last_position.filter(|&last| last >= position) {
+                    return Err(data_err(&format!(
+                        "position {position} does not follow position {last_position} \
+                        on contig '{name}'"
+                    )));
+                }
+                last_position = Some(position);
+
+                V::resize_item(&mut item, alleles);
+                if V::read_item(&mut item_reader, &mut item)?.is_done() {
+                    return Err(data_err(&format!(
+                        "SAF item file ended after {site} of {sites} sites on contig '{name}'"
+                    )));
+                }
+            }
+
+            let item_bytes = item_reader.offset();
+            let expected_item_bytes = V::contig_item_bytes(record, alleles) as u64;
+            if item_bytes != expected_item_bytes {
+                return Err(data_err(&format!(
+                    "items on contig '{name}' take up {item_bytes} bytes in SAF item file, \
+                    but index implies {expected_item_bytes} bytes"
+                )));
+            }
+        }
+
+        let position_reader_is_done = ReadStatus::check(&mut *self.position_reader)?.is_done();
+        let item_reader_is_done = ReadStatus::check(&mut *self.item_reader)?.is_done();
+
+        match (position_reader_is_done, item_reader_is_done) {
+            (true, true) => Ok(()),
+            (true, false) => Err(index_exhausted_err("SAF item file")),
+            (false, true) => Err(index_exhausted_err("SAF position file")),
+            (false, false) => Err(index_exhausted_err("both SAF files")),
+        }
+    }
+
+    /// Sets the number of BGZF worker threads used by the inner readers.
+    ///
+    /// The BGZF readers do not support changing the worker count mid-stream. Instead, the inner
+    /// readers are rebuilt with the new worker count, and sought back to their current positions,
+    /// so that reading continues where it left off. Any data buffered in the old readers will be
+    /// read again.
+    ///
+    /// Returns an error if seeking fails. The inner readers are kept in that case, but their
+    /// positions are unspecified, and so the reader should be sought, see [`Self::seek`], before
+    /// reading further. The worker count will then be unknown, see [`Self::worker_count`].
+    pub fn set_worker_count(&mut self, worker_count: NonZeroUsize) -> io::Result<()> {
+        let position_result = self.position_reader.rebuild(worker_count);
+        let item_result = self.item_reader.rebuild(worker_count);
+
+        let result = position_result.and(item_result);
+        self.worker_count = result.is_ok().then_some(worker_count);
+
+        result
+    }
+
+    /// Restores the reader to a checkpoint.
+    ///
+    /// The checkpoint may have been created by a different reader, as long as it was created for
+    /// the same files. Reading will resume exactly where the reader was when the checkpoint was
+    /// created. See [`ReaderCheckpoint`] and [`Self::checkpoint`]. This includes checkpoints
+    /// created after all records have been read, in which case reading remains finished.
+    ///
+    /// Returns an error if the checkpoint location is not valid for the index of the reader.
+    pub fn restore(&mut self, checkpoint: ReaderCheckpoint) -> io::Result<()> {
+        let contig_id = checkpoint.contig_id();
+        let sites_left_on_contig = checkpoint.sites_left_on_contig();
+
+        let records = self.index().records();
+        let is_valid = match records.get(contig_id) {
+            Some(record) => sites_left_on_contig <= record.sites(),
+            // Checkpoint taken after reading all records
+            None => contig_id == records.len() && sites_left_on_contig == 0,
+        };
+        if !is_valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "checkpoint location not valid for reader index",
+            ));
+        }
+        let last_contig_id = records.len() - 1;
+
+        let position_vpos = bgzf::VirtualPosition::from(checkpoint.position_offset());
+        self.position_reader.seek(position_vpos)?;
+
+        self.resolve_item_reader(contig_id.min(last_contig_id))?;
+        let item_vpos = bgzf::VirtualPosition::from(checkpoint.item_offset());
+        self.item_reader.seek(item_vpos)?;
+
+        self.location.contig_id = contig_id;
+        self.location.sites_left_on_contig = sites_left_on_contig;
+        self.current_contig_id = None;
+
+        Ok(())
+    }
+
     /// Seeks to start of contig.
     ///
     /// The `contig_id` refers to the position of records in the index.
     ///
     /// # Panics
     ///
-    /// Panics if `contig_id` is larger than the number of records defined in the index.
+    /// Panics if `contig_id` is larger than the number of records defined in the index. See
+    /// [`Self::try_seek`] for a non-panicking alternative.
     pub fn seek(&mut self, contig_id: usize) -> io::Result<()> {
+        assert!(
+            contig_id < self.index().records().len(),
+            "cannot seek to contig ID"
+        );
+
+        self.try_seek(contig_id)
+    }
+
+    /// Seeks to start of contig, returning an error if the contig is not defined in the index.
+    ///
+    /// The `contig_id` refers to the position of records in the index. Returns an error with
+    /// [`io::ErrorKind::InvalidInput`] if `contig_id` is larger than the number of records defined
+    /// in the index, in which case the reader is left unchanged.
+    pub fn try_seek(&mut self, contig_id: usize) -> io::Result<()> {
+        let contigs = self.index().records().len();
+        if contig_id >= contigs {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot seek to contig ID {contig_id} in index with {contigs} contigs"),
+            ));
+        }
+
         self.location
             .set_contig(contig_id)
-            .expect("cannot seek to contig ID");
+            .expect("contig ID checked against index");
 
         let record = &self.index().records()[contig_id];
         let position_offset = record.position_offset();
@@ -200,30 +1266,219 @@ where
         let position_vpos = bgzf::VirtualPosition::from(position_offset);
         self.position_reader.seek(position_vpos)?;
 
-        let item_vpos = bgzf::VirtualPosition::from(item_offset);
-        self.item_reader.seek(item_vpos)?;
+        self.current_contig_id = Some(contig_id);
 
-        Ok(())
+        if self.item_resolver.is_some() {
+            self.resolve_item_reader(contig_id)
+        } else {
+            let item_vpos = bgzf::VirtualPosition::from(item_offset);
+            self.item_reader.seek(item_vpos)?;
+
+            Ok(())
+        }
     }
 
-    /// Seeks to start of contig by name.
+    /// Seeks to the first site at or after a position on a contig.
     ///
-    /// Note that this requires a linear search of names in the index with worst time complexity
-    /// linear in the index size.. If the index is large, and the contig ID is known, prefer
-    /// [`Self::seek`] is more efficient.
+    /// The `contig_id` refers to the position of records in the index. Returns `true` if the
+    /// contig contains a site with position at least `position`, in which case the next record
+    /// read will be the first such site. Otherwise, returns `false` and leaves the reader at the
+    /// start of the next contig.
     ///
-    /// # Panics
+    /// Positions are sorted within a contig, but the position file is not indexed beyond the start
+    /// of contigs. Therefore, this seeks to the start of the contig and then reads records one by
+    /// one, and so has a cost linear in the number of sites before `position` on the contig.
     ///
-    /// Panics if sequence name is not defined in index.
-    pub fn seek_by_name(&mut self, name: &str) -> io::Result<()> {
-        let contig_id = self
+    /// Returns an error with [`io::ErrorKind::InvalidInput`] if `contig_id` is larger than the
+    /// number of records defined in the index.
+    pub fn seek_to_position(&mut self, contig_id: usize, position: u32) -> io::Result<bool> {
+        self.try_seek(contig_id)?;
+
+        let mut record = self.create_record_buf();
+        loop {
+            let checkpoint = self.checkpoint();
+
+            let status = self.read_record(&mut record)?;
+            let is_on_contig = status.is_not_done() && *record.contig_id() == contig_id;
+
+            if !is_on_contig || record.position() >= position {
+                self.restore(checkpoint)?;
+                if is_on_contig {
+                    self.current_contig_id = Some(contig_id);
+                }
+
+                return Ok(is_on_contig);
+            }
+        }
+    }
+
+    /// Returns an iterator over the records in a region.
+    ///
+    /// The reader seeks to the first site in the region using [`Self::seek_to_position`], and the
+    /// iterator then yields owned records until the end of the region or the end of the contig.
+    /// Since the end of the region is only detected by reading past it, the record following the
+    /// region will have been consumed from the reader once the iterator is exhausted.
+    ///
+    /// Returns an error with [`io::ErrorKind::InvalidInput`] if the region contig is not defined
+    /// in the index.
+    pub fn reader_for_region(
+        &mut self,
+        region: &Region,
+    ) -> io::Result<impl Iterator<Item = io::Result<Record<Id, V::Item>>> + '_> {
+        let contig_id = self.index().contig_id(region.name()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("region contig name '{}' not found in index", region.name()),
+            )
+        })?;
+
+        let mut is_done = !self.seek_to_position(contig_id, region.start().unwrap_or(0))?;
+        let end = region.end();
+
+        Ok(iter::from_fn(move || {
+            if is_done {
+                return None;
+            }
+
+            let mut record = self.create_record_buf();
+            match self.read_record(&mut record) {
+                Ok(ReadStatus::NotDone)
+                    if *record.contig_id() == contig_id
+                        && end.is_none_or(|end| record.position() <= end) =>
+                {
+                    Some(Ok(record))
+                }
+                Ok(_) => {
+                    is_done = true;
+                    None
+                }
+                Err(e) => {
+                    is_done = true;
+                    Some(Err(e))
+                }
+            }
+        }))
+    }
+
+    /// Returns the number of sites in non-overlapping windows along each contig.
+    ///
+    /// Each contig is divided into windows of `window` base pairs starting from position zero,
+    /// and a `(contig, window_start, count)` tuple is returned for each window up to and including
+    /// the last window containing a site on the contig. Windows without sites are included with a
+    /// count of zero, while contigs without sites are not included at all.
+    ///
+    /// Only positions are decoded. The position of the inner position reader is restored
+    /// afterwards, and the item reader is not touched, so that reading may continue as before.
+    ///
+    /// Returns an error if `window` is zero, or if the position file ends before the number of
+    /// sites given in the index have been read.
+    pub fn window_counts(&mut self, window: u32) -> io::Result<Vec<(String, u32, usize)>> {
+        if window == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "window size must be non-zero",
+            ));
+        }
+
+        let mut counts = vec![Vec::new(); self.index().records().len()];
+        self.scan_positions(|contig_id, position| {
+            let contig_counts: &mut Vec<usize> = &mut counts[contig_id];
+
+            let i = (position / window) as usize;
+            if i >= contig_counts.len() {
+                contig_counts.resize(i + 1, 0);
+            }
+            contig_counts[i] += 1;
+        })?;
+
+        let windows = self
             .index()
             .records()
             .iter()
-            .position(|x| x.name() == name)
-            .expect("name not found in index");
+            .zip(counts)
+            .flat_map(|(record, contig_counts)| {
+                contig_counts
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, count)| (record.name().to_owned(), i as u32 * window, count))
+            })
+            .collect();
 
-        self.seek(contig_id)
+        Ok(windows)
+    }
+
+    /// Calls the provided function with the contig ID and position of each site in the file.
+    ///
+    /// Only positions are decoded. The position of the inner position reader is restored
+    /// afterwards, also on error, and the item reader is not touched.
+    fn scan_positions<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(usize, u32),
+    {
+        let restore_vpos = self.position_reader.virtual_position();
+
+        let width = self.location.index.position_width();
+        let base = self.location.index.position_base();
+
+        let mut scan = || {
+            for (contig_id, record) in self.location.index.records().iter().enumerate() {
+                let position_vpos = bgzf::VirtualPosition::from(record.position_offset());
+                self.position_reader.seek(position_vpos)?;
+
+                for _ in 0..record.sites() {
+                    let position = self
+                        .position_reader
+                        .read_position_with_width(width)?
+                        .ok_or_else(|| {
+                            eof_err("reached EoF in SAF position file before end of index")
+                        })
+                        .and_then(|position| base.to_zero_based(position))
+                        .and_then(narrow_position)?;
+
+                    f(contig_id, position);
+                }
+            }
+
+            Ok(())
+        };
+        let result = scan();
+
+        let restored = self.position_reader.seek(restore_vpos).map(|_| ());
+        result.and(restored)
+    }
+
+    /// Seeks to start of contig by name.
+    ///
+    /// Contig IDs are looked up by name using [`Index::contig_id`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if sequence name is not defined in index. See [`Self::try_seek_by_name`] for a
+    /// non-panicking alternative.
+    pub fn seek_by_name(&mut self, name: &str) -> io::Result<()> {
+        assert!(
+            self.index().contig_id(name).is_some(),
+            "name not found in index"
+        );
+
+        self.try_seek_by_name(name)
+    }
+
+    /// Seeks to start of contig by name, returning an error if the name is not defined in the
+    /// index.
+    ///
+    /// Contig IDs are looked up by name using [`Index::contig_id`]. Returns an error with
+    /// [`io::ErrorKind::InvalidInput`] if the name is not found, in which case the reader is left
+    /// unchanged.
+    pub fn try_seek_by_name(&mut self, name: &str) -> io::Result<()> {
+        let contig_id = self.index().contig_id(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("contig name '{name}' not found in index"),
+            )
+        })?;
+
+        self.try_seek(contig_id)
     }
 }
 
@@ -231,6 +1486,11 @@ where
 ///
 /// The location tracks the current location of the reader relative to its index file in terms
 /// of which contig is currently being read, and how many sites are left on that contig.
+///
+/// Since the location is advanced once per site read, and files may contain billions of sites,
+/// the number of sites left on the current contig is used as a countdown: advancing within a contig
+/// costs only a single comparison, and the comparatively rare contig transitions are kept out of
+/// line in [`Location::next_nonempty_contig`].
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct Location<V> {
     pub index: Index<V>,
@@ -247,9 +1507,33 @@ where
         0 == self.sites_left_on_contig
     }
 
-    /// Decrements the number of sites left to read on current contig.
-    pub fn next_site_on_contig(&mut self) {
-        self.sites_left_on_contig -= 1
+    /// Moves the location to the next site, returning the ID of the contig of that site.
+    ///
+    /// Contigs without any sites in the index are skipped. Returns `None` if no more sites are
+    /// left in the index.
+    #[inline]
+    pub fn next_site(&mut self) -> Option<usize> {
+        if self.contig_is_finished() {
+            self.next_nonempty_contig()?;
+        }
+
+        self.sites_left_on_contig -= 1;
+
+        Some(self.contig_id)
+    }
+
+    /// Moves the location to the first site on the next contig in index with any sites.
+    ///
+    /// Returns `None` if no more such contigs exist in the index.
+    #[cold]
+    fn next_nonempty_contig(&mut self) -> Option<()> {
+        loop {
+            self.next_contig()?;
+
+            if !self.contig_is_finished() {
+                return Some(());
+            }
+        }
     }
 
     /// Moves the location first site on the next contig in index.
@@ -284,10 +1568,129 @@ where
     }
 }
 
-fn eof_err(msg: &str) -> io::Error {
-    io::Error::new(io::ErrorKind::UnexpectedEof, msg)
+fn eof_err(msg: &'static str) -> io::Error {
+    Error::UnexpectedEof(msg).into()
+}
+
+/// Returns the length of the inner reader of a BGZF reader, restoring its position afterwards.
+fn compressed_len<R>(reader: &mut bgzf::Reader<R>) -> io::Result<u64>
+where
+    R: io::Read + io::Seek,
+{
+    let virtual_position = reader.virtual_position();
+    let len = reader.get_mut().seek(io::SeekFrom::End(0))?;
+    reader.seek(virtual_position)?;
+
+    Ok(len)
+}
+
+/// An inner BGZF reader of a [`Reader`].
+///
+/// The BGZF reader is only ever taken out while being rebuilt in place, see
+/// [`InnerReader::rebuild`], so that dereferencing never fails otherwise.
+struct InnerReader<R>(Option<bgzf::Reader<R>>);
+
+impl<R> InnerReader<R> {
+    fn into_inner(self) -> bgzf::Reader<R> {
+        self.0.expect("inner reader present outside rebuild")
+    }
+
+    fn new(reader: bgzf::Reader<R>) -> Self {
+        Self(Some(reader))
+    }
+}
+
+impl<R> InnerReader<R>
+where
+    R: io::Read + io::Seek,
+{
+    /// Rebuilds the BGZF reader with the provided worker count, seeking to its current position.
+    ///
+    /// The rebuilt reader is kept even if seeking fails, in which case its position is unspecified.
+    fn rebuild(&mut self, worker_count: NonZeroUsize) -> io::Result<()> {
+        let reader = self.0.take().expect("inner reader present outside rebuild");
+        let virtual_position = reader.virtual_position();
+
+        let mut new = bgzf::reader::Builder::default()
+            .set_worker_count(worker_count)
+            .build_from_reader(reader.into_inner());
+        let result = new.seek(virtual_position).map(|_| ());
+        self.0 = Some(new);
+
+        result
+    }
+}
+
+impl<R> Deref for InnerReader<R> {
+    type Target = bgzf::Reader<R>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+            .as_ref()
+            .expect("inner reader present outside rebuild")
+    }
+}
+
+impl<R> DerefMut for InnerReader<R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+            .as_mut()
+            .expect("inner reader present outside rebuild")
+    }
+}
+
+/// Converts a position read from a position file to a 32-bit position.
+///
+/// Returns an error if the position does not fit in 32 bits.
+fn narrow_position(position: u64) -> io::Result<u32> {
+    u32::try_from(position).map_err(|_| {
+        data_err(&format!(
+            "position {position} does not fit in 32 bits; use 64-bit reading methods instead"
+        ))
+    })
 }
 
 fn data_err(msg: &str) -> io::Error {
-    io::Error::new(io::ErrorKind::InvalidData, msg)
+    Error::InvalidData(msg.to_owned()).into()
+}
+
+fn index_exhausted_err(files: &'static str) -> io::Error {
+    Error::IndexExhausted(files).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::iter;
+
+    use crate::index;
+
+    fn index_with_sites(sites: &[usize]) -> Index<V3> {
+        let records = sites
+            .iter()
+            .enumerate()
+            .map(|(i, &sites)| index::Record::new(format!("chr{i}"), sites, 0, 0))
+            .collect();
+
+        Index::new(0, records)
+    }
+
+    #[test]
+    fn test_location_next_site() {
+        let mut location = Location::setup(index_with_sites(&[2, 1, 3])).unwrap();
+
+        let contig_ids = iter::from_fn(|| location.next_site()).collect::<Vec<_>>();
+        assert_eq!(contig_ids, vec![0, 0, 1, 2, 2, 2]);
+        assert_eq!(location.next_site(), None);
+    }
+
+    #[test]
+    fn test_location_next_site_skips_empty_contigs() {
+        let mut location = Location::setup(index_with_sites(&[0, 1, 0, 0, 2, 0])).unwrap();
+
+        let contig_ids = iter::from_fn(|| location.next_site()).collect::<Vec<_>>();
+        assert_eq!(contig_ids, vec![1, 4, 4]);
+        assert_eq!(location.next_site(), None);
+    }
 }
@@ -12,11 +12,18 @@ use super::Reader;
 #[derive(Debug)]
 pub struct Builder<V> {
     threads: NonZeroUsize,
+    prefetch: bool,
     v: PhantomData<V>,
 }
 
 type DefaultReader<V> = Reader<io::BufReader<File>, V>;
 
+/// A SAF reader over memory-mapped position and item files.
+///
+/// Created using [`Builder::build_from_paths_mmap`] or [`Builder::build_from_prefix_mmap`].
+#[cfg(feature = "mmap")]
+pub type MmapReader<V> = Reader<io::Cursor<memmap2::Mmap>, V>;
+
 impl<V> Builder<V>
 where
     V: Version,
@@ -37,15 +44,24 @@ where
         R: io::BufRead,
         V: Version,
     {
-        Reader::from_bgzf(
+        let item_worker_count = if self.prefetch {
+            self.threads.saturating_add(1)
+        } else {
+            self.threads
+        };
+
+        let mut reader = Reader::from_bgzf(
             index,
             bgzf::reader::Builder::default()
                 .set_worker_count(self.threads)
                 .build_from_reader(position_reader),
             bgzf::reader::Builder::default()
-                .set_worker_count(self.threads)
+                .set_worker_count(item_worker_count)
                 .build_from_reader(item_reader),
-        )
+        )?;
+        reader.worker_count = Some(self.threads);
+
+        Some(reader)
     }
 
     /// Builds a new reader from any member path.
@@ -122,6 +138,86 @@ where
         self.build_from_paths(index_path, position_path, item_path)
     }
 
+    /// Builds a new reader from the paths of its components, memory-mapping the position and
+    /// item files.
+    ///
+    /// This is equivalent to [`Self::build_from_paths`], except that the position and item files
+    /// are memory-mapped rather than read through buffered file handles. Seeking still goes
+    /// through BGZF virtual positions, but without the system calls otherwise required on each
+    /// seek, which makes heavy random access such as repeated [`Reader::seek`] cheaper.
+    ///
+    /// The magic numbers will be read, and so [`Reader::read_magic`] should *not* be called
+    /// manually. Requires the `mmap` feature.
+    ///
+    /// # Safety
+    ///
+    /// The position and item files must not be modified or truncated while the reader exists,
+    /// see [`memmap2::Mmap::map`].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn build_from_paths_mmap<P>(
+        self,
+        index_path: P,
+        position_path: P,
+        item_path: P,
+    ) -> io::Result<MmapReader<V>>
+    where
+        P: AsRef<Path>,
+    {
+        let index = Index::read_from_path(index_path)?;
+        let position_reader = File::open(position_path)
+            .and_then(|file| unsafe { memmap2::Mmap::map(&file) })
+            .map(io::Cursor::new)?;
+        let item_reader = File::open(item_path)
+            .and_then(|file| unsafe { memmap2::Mmap::map(&file) })
+            .map(io::Cursor::new)?;
+
+        let mut new = self
+            .build(index, position_reader, item_reader)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "empty index in reader construction",
+                )
+            })?;
+        new.read_magic()?;
+        Ok(new)
+    }
+
+    /// Builds a new reader from a shared prefix, memory-mapping the position and item files.
+    ///
+    /// See [`Self::build_from_prefix`] and [`Self::build_from_paths_mmap`] for details. Requires
+    /// the `mmap` feature.
+    ///
+    /// # Safety
+    ///
+    /// The position and item files must not be modified or truncated while the reader exists,
+    /// see [`memmap2::Mmap::map`].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn build_from_prefix_mmap<P>(self, prefix: P) -> io::Result<MmapReader<V>>
+    where
+        P: AsRef<Path>,
+    {
+        let [index_path, position_path, item_path] =
+            member_paths_from_prefix(&prefix.as_ref().to_string_lossy());
+
+        unsafe { self.build_from_paths_mmap(index_path, position_path, item_path) }
+    }
+
+    /// Sets whether to prefetch items in the reader.
+    ///
+    /// When reading a record, the position and item files are otherwise decompressed in turn, so
+    /// that decompression of the two is serialised when using a single thread. With prefetching,
+    /// the item file is read using one BGZF worker thread more than set by [`Self::set_threads`],
+    /// so that item blocks are decompressed on background threads ahead of being read while
+    /// positions are consumed. Records are still read in order, and EoF checks are unchanged.
+    ///
+    /// Note that [`Reader::set_worker_count`] sets the same worker count for both files, and so
+    /// disables prefetching. By default, items are not prefetched.
+    pub fn set_prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
     /// Sets the number of threads to use in the reader.
     ///
     /// By default, the number of threads is 1.
@@ -152,6 +248,7 @@ where
     fn default() -> Self {
         Self {
             threads: NonZeroUsize::new(1).unwrap(),
+            prefetch: false,
             v: PhantomData,
         }
     }
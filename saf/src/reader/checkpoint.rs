@@ -0,0 +1,54 @@
+/// A checkpoint of the state of a SAF reader.
+///
+/// The checkpoint captures the virtual positions of the inner BGZF readers, as well as the
+/// location of the reader relative to the index. This allows reading to be resumed where it left
+/// off, possibly by a different reader for the same files, for instance in a later run of a
+/// long-running job. The checkpoint can be persisted using its accessors, and recreated using
+/// [`Self::new`].
+///
+/// Created using [`Reader::checkpoint`](super::Reader::checkpoint), and restored using
+/// [`Reader::restore`](super::Reader::restore).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ReaderCheckpoint {
+    position_offset: u64,
+    item_offset: u64,
+    contig_id: usize,
+    sites_left_on_contig: usize,
+}
+
+impl ReaderCheckpoint {
+    /// Returns the contig ID of the reader location.
+    pub fn contig_id(&self) -> usize {
+        self.contig_id
+    }
+
+    /// Returns the BGZF virtual position of the item reader.
+    pub fn item_offset(&self) -> u64 {
+        self.item_offset
+    }
+
+    /// Creates a new checkpoint from its parts.
+    pub fn new(
+        position_offset: u64,
+        item_offset: u64,
+        contig_id: usize,
+        sites_left_on_contig: usize,
+    ) -> Self {
+        Self {
+            position_offset,
+            item_offset,
+            contig_id,
+            sites_left_on_contig,
+        }
+    }
+
+    /// Returns the BGZF virtual position of the position reader.
+    pub fn position_offset(&self) -> u64 {
+        self.position_offset
+    }
+
+    /// Returns the number of sites left to read on the contig of the reader location.
+    pub fn sites_left_on_contig(&self) -> usize {
+        self.sites_left_on_contig
+    }
+}
@@ -0,0 +1,85 @@
+use std::io;
+
+use crate::{
+    record::{Id, Record},
+    version::Version,
+    ReadStatus,
+};
+
+use super::Reader;
+
+/// A SAF reader that clamps likelihoods to a floor.
+///
+/// Any likelihood below the floor is replaced by the floor when read, which prevents negative
+/// infinite or extreme log-likelihoods from destabilising downstream computations. A count of
+/// such clamped values is kept. For [`V4`] items, only the likelihoods in the band are clamped,
+/// and the band itself is not changed. Note that `NaN` values are never clamped.
+///
+/// Created using [`Reader::clamp_likelihoods`].
+///
+/// [`V4`]: crate::version::V4
+pub struct ClampLikelihoods<R, V> {
+    inner: Reader<R, V>,
+    floor: f32,
+    clamped: usize,
+}
+
+impl<R, V> ClampLikelihoods<R, V> {
+    /// Returns the number of likelihoods clamped so far.
+    pub fn clamped(&self) -> usize {
+        self.clamped
+    }
+
+    /// Returns the floor to which likelihoods are clamped.
+    pub fn floor(&self) -> f32 {
+        self.floor
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &Reader<R, V> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R, V> {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, consuming `self`.
+    pub fn into_inner(self) -> Reader<R, V> {
+        self.inner
+    }
+
+    pub(super) fn new(inner: Reader<R, V>, floor: f32) -> Self {
+        Self {
+            inner,
+            floor,
+            clamped: 0,
+        }
+    }
+}
+
+impl<R, V> ClampLikelihoods<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+    V::Item: AsMut<[f32]>,
+{
+    /// Reads a single record with likelihoods clamped to the floor.
+    ///
+    /// See [`Reader::read_record`] for details.
+    pub fn read_record(&mut self, record: &mut Record<Id, V::Item>) -> io::Result<ReadStatus> {
+        if self.inner.read_record(record)?.is_done() {
+            return Ok(ReadStatus::Done);
+        }
+
+        for v in record.item_mut().as_mut().iter_mut() {
+            if *v < self.floor {
+                *v = self.floor;
+                self.clamped += 1;
+            }
+        }
+
+        Ok(ReadStatus::NotDone)
+    }
+}
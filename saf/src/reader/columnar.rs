@@ -0,0 +1,92 @@
+use crate::record::Id;
+
+/// A batch of SAF sites in a columnar layout.
+///
+/// Rather than a record for each site, the contig IDs, positions, and likelihoods of all sites
+/// are each stored contiguously. In particular, the likelihoods of all sites are stored in a single
+/// flat buffer, with the `alleles + 1` likelihoods of each site following each other. This avoids
+/// an allocation per site, and is suitable for vectorised processing.
+///
+/// Created using [`Reader::read_columnar`](super::Reader::read_columnar).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Columnar {
+    alleles: usize,
+    contig_ids: Vec<Id>,
+    positions: Vec<u32>,
+    likelihoods: Vec<f32>,
+}
+
+impl Columnar {
+    /// Returns the number of alleles of each site.
+    ///
+    /// Each site has `alleles + 1` likelihoods.
+    pub fn alleles(&self) -> usize {
+        self.alleles
+    }
+
+    /// Returns the contig IDs of all sites.
+    pub fn contig_ids(&self) -> &[Id] {
+        &self.contig_ids
+    }
+
+    /// Returns `true` if the batch contains no sites.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the number of sites.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the flat likelihoods of all sites.
+    pub fn likelihoods(&self) -> &[f32] {
+        &self.likelihoods
+    }
+
+    /// Returns the likelihoods of the site with the provided index in the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn site(&self, i: usize) -> &[f32] {
+        let width = self.alleles + 1;
+
+        &self.likelihoods[i * width..(i + 1) * width]
+    }
+
+    /// Returns an iterator over the likelihoods of each site in the batch.
+    pub fn sites(&self) -> impl Iterator<Item = &[f32]> {
+        self.likelihoods.chunks_exact(self.alleles + 1)
+    }
+
+    /// Returns the positions of all sites.
+    pub fn positions(&self) -> &[u32] {
+        &self.positions
+    }
+
+    pub(super) fn with_capacity(alleles: usize, sites: usize) -> Self {
+        Self {
+            alleles,
+            contig_ids: Vec::with_capacity(sites),
+            positions: Vec::with_capacity(sites),
+            likelihoods: Vec::with_capacity(sites * (alleles + 1)),
+        }
+    }
+
+    /// Pushes the contig ID and position of a new site.
+    ///
+    /// The likelihoods of the site should be pushed separately using [`Self::push_likelihoods`].
+    pub(super) fn push(&mut self, contig_id: Id, position: u32) {
+        self.contig_ids.push(contig_id);
+        self.positions.push(position);
+    }
+
+    /// Pushes zero-initialised likelihoods for a new site, returning a mutable reference to them.
+    pub(super) fn push_likelihoods(&mut self) -> &mut [f32] {
+        let start = self.likelihoods.len();
+        self.likelihoods.resize(start + self.alleles + 1, 0.0);
+
+        &mut self.likelihoods[start..]
+    }
+}
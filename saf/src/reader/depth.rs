@@ -0,0 +1,111 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    ext::DEPTH_FILE_EXT,
+    record::{Id, Record},
+    version::Version,
+    ReadStatus,
+};
+
+use super::{index_exhausted_err, Reader, ReaderExt};
+
+/// A SAF reader that reads the depth of each site along with records.
+///
+/// The depth is read from an optional fourth member file, conventionally with the extension
+/// [`DEPTH_FILE_EXT`], containing the depth of each site as a little-endian `u32`, in the same
+/// order as the positions. The inner depth reader will typically be a [`bgzf::Reader`]. Where no
+/// depth file is available, the depth of each site is read as missing, so that the adapter can be
+/// used regardless. See also [`depth_reader_from_prefix`].
+///
+/// Created using [`Reader::with_depth`].
+pub struct WithDepth<R, V, D> {
+    inner: Reader<R, V>,
+    depth_reader: Option<D>,
+}
+
+impl<R, V, D> WithDepth<R, V, D> {
+    /// Returns the inner depth reader, if any.
+    pub fn depth_reader(&self) -> Option<&D> {
+        self.depth_reader.as_ref()
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &Reader<R, V> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R, V> {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader and depth reader, consuming `self`.
+    pub fn into_parts(self) -> (Reader<R, V>, Option<D>) {
+        (self.inner, self.depth_reader)
+    }
+
+    pub(super) fn new(inner: Reader<R, V>, depth_reader: Option<D>) -> Self {
+        Self {
+            inner,
+            depth_reader,
+        }
+    }
+}
+
+impl<R, V, D> WithDepth<R, V, D>
+where
+    R: io::BufRead,
+    V: Version,
+    D: io::BufRead,
+{
+    /// Reads a single record and the depth of the site.
+    ///
+    /// The depth is set to `None` if no depth reader is set. Returns an error if the depth file
+    /// ends before the record is read, or if data remains in the depth file once all records have
+    /// been read. See [`Reader::read_record`] for details.
+    pub fn read_record(
+        &mut self,
+        record: &mut Record<Id, V::Item>,
+        depth: &mut Option<u32>,
+    ) -> io::Result<ReadStatus> {
+        let status = self.inner.read_record(record)?;
+
+        if status.is_not_done() {
+            // Depths are stored in the same way as 32-bit positions
+            *depth = match self.depth_reader.as_mut() {
+                Some(depth_reader) => Some(depth_reader.read_position()?.ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "reached EoF in SAF depth file before end of index",
+                    )
+                })?),
+                None => None,
+            };
+        } else if let Some(depth_reader) = self.depth_reader.as_mut() {
+            if ReadStatus::check(depth_reader)?.is_not_done() {
+                return Err(index_exhausted_err("SAF depth file"));
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+/// Opens the depth file with the provided shared prefix, if it exists.
+///
+/// The path is constructed by appending [`DEPTH_FILE_EXT`] to the prefix. Returns `None` if the
+/// file does not exist, so that the result may be passed directly to [`Reader::with_depth`].
+pub fn depth_reader_from_prefix<P>(
+    prefix: P,
+) -> io::Result<Option<bgzf::Reader<io::BufReader<fs::File>>>>
+where
+    P: AsRef<Path>,
+{
+    let path = format!("{}.{DEPTH_FILE_EXT}", prefix.as_ref().to_string_lossy());
+
+    match fs::File::open(path) {
+        Ok(file) => Ok(Some(bgzf::Reader::new(io::BufReader::new(file)))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,97 @@
+use std::io;
+
+use crate::{
+    record::{Id, Record},
+    version::Version,
+};
+
+use super::Reader;
+
+/// An event in a SAF file.
+///
+/// See [`Events`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SafEvent<T> {
+    /// The start of a new contig with the provided name.
+    ContigStart {
+        /// The contig name.
+        name: String,
+    },
+    /// A site on the current contig.
+    Site(Record<Id, T>),
+    /// The end of the file.
+    End,
+}
+
+/// An iterator over the events in a SAF file.
+///
+/// This provides a streaming interface to a reader, where each contig is announced by a
+/// [`SafEvent::ContigStart`] before the [`SafEvent::Site`]s on the contig, and reading finishes
+/// with a [`SafEvent::End`]. Contigs without sites are skipped. The iterator stops after the end
+/// event, or after returning an error.
+///
+/// Created using [`Reader::events`].
+pub struct Events<'a, R, V>
+where
+    V: Version,
+{
+    inner: &'a mut Reader<R, V>,
+    contig_id: Option<usize>,
+    pending: Option<Record<Id, V::Item>>,
+    is_done: bool,
+}
+
+impl<'a, R, V> Events<'a, R, V>
+where
+    R: io::BufRead,
+    V: Version,
+{
+    pub(super) fn new(inner: &'a mut Reader<R, V>) -> Self {
+        Self {
+            inner,
+            contig_id: None,
+            pending: None,
+            is_done: false,
+        }
+    }
+}
+
+impl<R, V> Iterator for Events<'_, R, V>
+where
+    R: io::BufRead,
+    V: Version,
+{
+    type Item = io::Result<SafEvent<V::Item>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        if let Some(record) = self.pending.take() {
+            return Some(Ok(SafEvent::Site(record)));
+        }
+
+        let mut record = self.inner.create_record_buf();
+        match self.inner.read_record(&mut record) {
+            Ok(status) if status.is_done() => {
+                self.is_done = true;
+                Some(Ok(SafEvent::End))
+            }
+            Ok(_) if self.contig_id != Some(*record.contig_id()) => {
+                self.contig_id = Some(*record.contig_id());
+                let name = self.inner.index().records()[*record.contig_id()]
+                    .name()
+                    .to_owned();
+                self.pending = Some(record);
+
+                Some(Ok(SafEvent::ContigStart { name }))
+            }
+            Ok(_) => Some(Ok(SafEvent::Site(record))),
+            Err(e) => {
+                self.is_done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
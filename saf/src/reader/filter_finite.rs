@@ -0,0 +1,69 @@
+use std::io;
+
+use crate::{
+    record::{Id, Record},
+    version::Version,
+    ReadStatus,
+};
+
+use super::Reader;
+
+/// A SAF reader that skips sites with any non-finite likelihood.
+///
+/// Sites where the item contains a `NaN` or infinite value are read and discarded, and a count of
+/// such dropped sites is kept. Note that negative infinity is also dropped, even though it may be
+/// valid in log-space.
+///
+/// Created using [`Reader::filter_finite`].
+pub struct FilterFinite<R, V> {
+    inner: Reader<R, V>,
+    dropped: usize,
+}
+
+impl<R, V> FilterFinite<R, V> {
+    /// Returns the number of sites dropped so far.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &Reader<R, V> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R, V> {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, consuming `self`.
+    pub fn into_inner(self) -> Reader<R, V> {
+        self.inner
+    }
+
+    pub(super) fn new(inner: Reader<R, V>) -> Self {
+        Self { inner, dropped: 0 }
+    }
+}
+
+impl<R, V> FilterFinite<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+    V::Item: AsRef<[f32]>,
+{
+    /// Reads a single record with only finite likelihoods.
+    ///
+    /// See [`Reader::read_record`] for details.
+    pub fn read_record(&mut self, record: &mut Record<Id, V::Item>) -> io::Result<ReadStatus> {
+        while self.inner.read_record(record)?.is_not_done() {
+            if record.item().as_ref().iter().all(|v| v.is_finite()) {
+                return Ok(ReadStatus::NotDone);
+            }
+
+            self.dropped += 1;
+        }
+
+        Ok(ReadStatus::Done)
+    }
+}
@@ -0,0 +1,83 @@
+use std::io;
+
+use crate::{
+    record::{is_informative, Id, Record},
+    version::Version,
+    ReadStatus,
+};
+
+use super::Reader;
+
+/// A SAF reader that skips uninformative sites.
+///
+/// Sites where the difference between the greatest and smallest likelihood does not exceed a
+/// minimum spread are read and discarded, and a count of such dropped sites is kept. For [`V4`]
+/// items, only the likelihoods in the band are considered. See also
+/// [`Record::<I, Likelihoods>::is_informative`].
+///
+/// Created using [`Reader::filter_informative`].
+///
+/// [`V4`]: crate::version::V4
+/// [`Record::<I, Likelihoods>::is_informative`]: crate::Record::is_informative
+pub struct FilterInformative<R, V> {
+    inner: Reader<R, V>,
+    min_spread: f32,
+    dropped: usize,
+}
+
+impl<R, V> FilterInformative<R, V> {
+    /// Returns the number of sites dropped so far.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &Reader<R, V> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R, V> {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, consuming `self`.
+    pub fn into_inner(self) -> Reader<R, V> {
+        self.inner
+    }
+
+    /// Returns the minimum spread of likelihoods for a site to be kept.
+    pub fn min_spread(&self) -> f32 {
+        self.min_spread
+    }
+
+    pub(super) fn new(inner: Reader<R, V>, min_spread: f32) -> Self {
+        Self {
+            inner,
+            min_spread,
+            dropped: 0,
+        }
+    }
+}
+
+impl<R, V> FilterInformative<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+    V::Item: AsRef<[f32]>,
+{
+    /// Reads a single informative record.
+    ///
+    /// See [`Reader::read_record`] for details.
+    pub fn read_record(&mut self, record: &mut Record<Id, V::Item>) -> io::Result<ReadStatus> {
+        while self.inner.read_record(record)?.is_not_done() {
+            if is_informative(record.item().as_ref(), self.min_spread) {
+                return Ok(ReadStatus::NotDone);
+            }
+
+            self.dropped += 1;
+        }
+
+        Ok(ReadStatus::Done)
+    }
+}
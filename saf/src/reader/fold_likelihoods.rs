@@ -0,0 +1,110 @@
+use std::io;
+
+use crate::{
+    record::{fold_into, Id, Likelihoods, Record},
+    version::{V3, V4},
+    ReadStatus,
+};
+
+use super::Reader;
+
+/// A SAF reader that folds likelihoods.
+///
+/// Each record read has its likelihoods folded, as described in
+/// [`Record::<I, Likelihoods>::fold`]. Records are read into a scratch buffer sized using the
+/// number of alleles in the index, which is reused between records, and folded into the provided
+/// record buffer. For [`V4`] items, the band is first expanded into the full set of likelihoods
+/// with negative infinity outside the band, see [`ReaderV4::read_record_full`].
+///
+/// Created using [`Reader::fold_likelihoods`].
+///
+/// [`ReaderV4::read_record_full`]: super::ReaderV4::read_record_full
+pub struct FoldingReader<R, V> {
+    inner: Reader<R, V>,
+    full: Record<Id, Likelihoods>,
+}
+
+impl<R, V> FoldingReader<R, V> {
+    /// Returns a new record suitable for use in reading.
+    ///
+    /// The record will have the number of folded likelihoods corresponding to the number of
+    /// alleles in the index.
+    pub fn create_record_buf(&self) -> Record<Id, Likelihoods> {
+        Record::from_alleles(0, 0, self.full.item().len().div_ceil(2) - 1)
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &Reader<R, V> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R, V> {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, consuming `self`.
+    pub fn into_inner(self) -> Reader<R, V> {
+        self.inner
+    }
+
+    pub(super) fn new(inner: Reader<R, V>, alleles: usize) -> Self {
+        Self {
+            inner,
+            full: Record::from_alleles(0, 0, alleles),
+        }
+    }
+
+    /// Folds the likelihoods in the scratch buffer into the provided record buffer.
+    fn fold_full_into(&self, buf: &mut Record<Id, Likelihoods>) {
+        *buf.contig_id_mut() = *self.full.contig_id();
+        *buf.position_mut() = self.full.position();
+
+        let folded_len = self.full.item().len().div_ceil(2);
+        if buf.item().len() != folded_len {
+            *buf.item_mut() = vec![0.0; folded_len].into();
+        }
+
+        fold_into(self.full.item(), buf.item_mut());
+    }
+}
+
+impl<R> FoldingReader<R, V3>
+where
+    R: io::BufRead,
+{
+    /// Reads a single record with folded likelihoods.
+    ///
+    /// See [`Reader::read_record`] for details.
+    pub fn read_record(&mut self, buf: &mut Record<Id, Likelihoods>) -> io::Result<ReadStatus> {
+        if self.inner.read_record(&mut self.full)?.is_done() {
+            return Ok(ReadStatus::Done);
+        }
+
+        self.fold_full_into(buf);
+
+        Ok(ReadStatus::NotDone)
+    }
+}
+
+impl<R> FoldingReader<R, V4>
+where
+    R: io::BufRead,
+{
+    /// Reads a single record with folded likelihoods.
+    ///
+    /// See [`Reader::read_record`] for details.
+    pub fn read_record(&mut self, buf: &mut Record<Id, Likelihoods>) -> io::Result<ReadStatus> {
+        if self
+            .inner
+            .read_record_full(&mut self.full, f32::NEG_INFINITY)?
+            .is_done()
+        {
+            return Ok(ReadStatus::Done);
+        }
+
+        self.fold_full_into(buf);
+
+        Ok(ReadStatus::NotDone)
+    }
+}
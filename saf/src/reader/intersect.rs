@@ -1,11 +1,11 @@
-use std::{cmp::Ordering, io};
+use std::{cmp::Ordering, error, fmt, io, iter, mem};
 
 use indexmap::IndexMap;
 
 use crate::{
     record::{Id, Record},
-    version::Version,
-    ReadStatus,
+    version::{Version, V3, V4},
+    ReadStatus, Writer,
 };
 
 use super::{Index, Reader};
@@ -15,10 +15,28 @@ use super::{Index, Reader};
 /// The intersection takes an arbitrary number of readers and returns data where all readers
 /// contain data for the same contig and position. It is assumed that contigs are sorted in the
 /// same order in each file, and that positions are sorted numerically within each contig.
+///
+/// If the contigs are not sorted in the same order in each file, see
+/// [`Intersect::new_unordered`].
+///
+/// If the readers have no contigs in common, reading will by default finish immediately. Since
+/// this cannot be distinguished from an intersection that has been fully read, an error may be
+/// returned instead, see [`Intersect::set_error_on_no_shared_contigs`].
 pub struct Intersect<R, V> {
     readers: Vec<Reader<R, V>>,
     shared_contigs: SharedContigs,
     ids: Vec<usize>, // Current reader contig IDs
+    error_on_no_shared_contigs: bool,
+    unordered: Option<UnorderedState>,
+}
+
+/// The state of an intersection of readers with contigs in arbitrary order.
+///
+/// See [`Intersect::new_unordered`].
+#[derive(Clone, Copy, Debug, Default)]
+struct UnorderedState {
+    next_shared: usize, // Index of the next shared contig to seek to
+    on_contig: bool,    // Whether the readers are on the shared contig preceding the next
 }
 
 impl<R, V> Intersect<R, V>
@@ -34,6 +52,11 @@ where
             .collect()
     }
 
+    /// Returns `true` if all inner readers have at least one contig in common.
+    pub fn has_shared_contigs(&self) -> bool {
+        !self.shared_contigs.0.is_empty()
+    }
+
     /// Creates a new intersecting reader with an additional reader, consuming `self`.
     ///
     /// Since `self` is consumed, rather than mutated, this can be chained to build intersections
@@ -81,11 +104,34 @@ where
                     readers,
                     shared_contigs: contigs,
                     ids,
+                    error_on_no_shared_contigs: false,
+                    unordered: None,
                 }
             }
         }
     }
 
+    /// Creates a new intersecting reader from a collection of readers with contigs in arbitrary
+    /// order.
+    ///
+    /// Unlike [`Self::new`], contigs are not assumed to be sorted in the same order in each file.
+    /// Instead, the contigs shared by all readers are read one at a time, in the order of the
+    /// first reader, by seeking each reader to its own start of the shared contig. This costs a
+    /// seek in each reader per shared contig, in addition to the up-front scan of the indices
+    /// required to find the shared contigs, which is also done by [`Self::new`]. Positions must
+    /// still be sorted numerically within each contig.
+    ///
+    /// Readers added using [`Self::intersect`] are likewise not assumed to share the contig order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `readers` is empty.
+    pub fn new_unordered(readers: Vec<Reader<R, V>>) -> Self {
+        let mut new = Self::new(readers);
+        new.unordered = Some(UnorderedState::default());
+        new
+    }
+
     /// Reads a set of intersecting records, one from each contained reader.
     ///
     /// If successful, a record from each inner reader will be read into the corresponding buffer
@@ -93,7 +139,18 @@ where
     ///
     /// Note that the record buffer needs to be correctly set up. Use [`Self::create_record_bufs`]
     /// for a correctly initialised record buffers to use for reading.
+    ///
+    /// If the readers have no shared contigs and [`Self::set_error_on_no_shared_contigs`] is
+    /// enabled, an error wrapping [`NoSharedContigs`] is returned.
     pub fn read_records(&mut self, bufs: &mut [Record<Id, V::Item>]) -> io::Result<ReadStatus> {
+        if self.error_on_no_shared_contigs && !self.has_shared_contigs() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, NoSharedContigs));
+        }
+
+        if self.unordered.is_some() {
+            return self.read_records_unordered(bufs);
+        }
+
         for ((reader, record), id) in self
             .readers
             .iter_mut()
@@ -118,11 +175,207 @@ where
         }
     }
 
+    /// Returns an iterator over the remaining sets of intersecting records.
+    ///
+    /// Each set is read into new buffers created using [`Self::create_record_bufs`], and yielded
+    /// as an owned vector with a record from each inner reader, in order. This is a convenience
+    /// over manually calling [`Self::read_records`] in a loop. The iterator stops after the end of
+    /// any reader, or after returning an error.
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Vec<Record<Id, V::Item>>>> + '_ {
+        let mut is_done = false;
+
+        iter::from_fn(move || {
+            if is_done {
+                return None;
+            }
+
+            let mut bufs = self.create_record_bufs();
+            match self.read_records(&mut bufs) {
+                Ok(ReadStatus::NotDone) => Some(Ok(bufs)),
+                Ok(ReadStatus::Done) => {
+                    is_done = true;
+                    None
+                }
+                Err(e) => {
+                    is_done = true;
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// Sets whether reading should return an error if the readers have no shared contigs.
+    ///
+    /// By default, reading an intersection without shared contigs simply returns
+    /// [`ReadStatus::Done`]. If enabled, [`Self::read_records`] instead returns an error of kind
+    /// [`io::ErrorKind::InvalidInput`] wrapping [`NoSharedContigs`].
+    pub fn set_error_on_no_shared_contigs(&mut self, error_on_no_shared_contigs: bool) {
+        self.error_on_no_shared_contigs = error_on_no_shared_contigs;
+    }
+
+    /// Writes all remaining sets of intersecting records, one writer per inner reader.
+    ///
+    /// Each record read from an inner reader is written to the writer at the same index, with its
+    /// contig ID mapped to its name in the index of the reader. This creates SAF files restricted
+    /// to the sites shared by all readers. The number of alleles of each writer must be written
+    /// before writing the intersection, and should match that of the corresponding reader. Note
+    /// that the writers are not finished, so that further records may be written: use
+    /// [`Writer::finish`] when done.
+    ///
+    /// Returns an error with [`io::ErrorKind::InvalidInput`] if the number of writers does not
+    /// match the number of readers.
+    pub fn write_intersection<W>(&mut self, writers: &mut [Writer<W, V>]) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        if writers.len() != self.readers.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "number of writers ({}) does not match number of intersecting readers ({})",
+                    writers.len(),
+                    self.readers.len()
+                ),
+            ));
+        }
+
+        let mut bufs = self.create_record_bufs();
+        let mut spares = self
+            .create_record_bufs()
+            .into_iter()
+            .map(|record| Some(record.into_item()))
+            .collect::<Vec<_>>();
+
+        while self.read_records(&mut bufs)?.is_not_done() {
+            for (((reader, writer), buf), spare) in self
+                .readers
+                .iter()
+                .zip(writers.iter_mut())
+                .zip(bufs.iter_mut())
+                .zip(spares.iter_mut())
+            {
+                // Swap the item out of the buffer to write it as a named record without cloning
+                let spare_item = spare.take().expect("spare item returned after writing");
+                let item = mem::replace(buf.item_mut(), spare_item);
+
+                let name = reader.index().records()[*buf.contig_id()].name();
+                let named = Record::new(name, buf.position(), item);
+                writer.write_record(&named)?;
+
+                *spare = Some(named.into_item());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Seeks all readers to the start of the shared contig with the provided name.
+    ///
+    /// Subsequent reads will start from the shared contig, skipping any contigs preceding it.
+    ///
+    /// Returns `false` if the contig is not shared by all readers, in which case the readers are
+    /// not moved.
+    pub fn skip_to_contig(&mut self, name: &str) -> io::Result<bool> {
+        let next_ids = match self.shared_contigs.0.get(name) {
+            Some(next_ids) => next_ids,
+            None => return Ok(false),
+        };
+
+        for ((reader, next_id), id) in self
+            .readers
+            .iter_mut()
+            .zip(next_ids.iter())
+            .zip(self.ids.iter_mut())
+        {
+            reader.seek(*next_id)?;
+            *id = *next_id;
+        }
+
+        if let Some(unordered) = self.unordered.as_mut() {
+            let idx = self
+                .shared_contigs
+                .0
+                .get_index_of(name)
+                .expect("shared contig name checked above");
+
+            *unordered = UnorderedState {
+                next_shared: idx + 1,
+                on_contig: true,
+            };
+        }
+
+        Ok(true)
+    }
+
     pub(super) fn from_reader(reader: Reader<R, V>) -> Self {
         Self {
             shared_contigs: SharedContigs::from(reader.index()),
             readers: vec![reader],
             ids: vec![0],
+            error_on_no_shared_contigs: false,
+            unordered: None,
+        }
+    }
+
+    /// Reads a set of intersecting records for readers with contigs in arbitrary order.
+    ///
+    /// See [`Self::new_unordered`] and [`Self::read_records`].
+    fn read_records_unordered(
+        &mut self,
+        bufs: &mut [Record<Id, V::Item>],
+    ) -> io::Result<ReadStatus> {
+        let mut state = self.unordered.expect("unordered intersection");
+
+        loop {
+            if state.on_contig {
+                // Read a record from each reader, checking that all remain on the shared contig
+                let mut is_on_contig = true;
+                for ((reader, record), id) in self
+                    .readers
+                    .iter_mut()
+                    .zip(bufs.iter_mut())
+                    .zip(self.ids.iter())
+                {
+                    if reader.read_record(record)?.is_done() || record.contig_id() != id {
+                        is_on_contig = false;
+                        break;
+                    }
+                }
+
+                if is_on_contig {
+                    if let Some(ReadStatus::NotDone) =
+                        self.read_until_shared_position_on_contig(bufs)?
+                    {
+                        self.unordered = Some(state);
+                        return Ok(ReadStatus::NotDone);
+                    }
+                }
+
+                // Some reader reached the end of the shared contig
+                state.on_contig = false;
+            }
+
+            // Seek all readers to the start of the next shared contig, if any
+            let next_ids = match self.shared_contigs.0.get_index(state.next_shared) {
+                Some((_, next_ids)) => next_ids,
+                None => {
+                    self.unordered = Some(state);
+                    return Ok(ReadStatus::Done);
+                }
+            };
+
+            for ((reader, next_id), id) in self
+                .readers
+                .iter_mut()
+                .zip(next_ids.iter())
+                .zip(self.ids.iter_mut())
+            {
+                reader.seek(*next_id)?;
+                *id = *next_id;
+            }
+
+            state.next_shared += 1;
+            state.on_contig = true;
         }
     }
 
@@ -242,6 +495,80 @@ where
     }
 }
 
+impl<R> Intersect<R, V3>
+where
+    R: io::BufRead + io::Seek,
+{
+    /// Reads all remaining intersecting records into dense matrices, one for each reader.
+    ///
+    /// The matrix for each reader has a row for each intersecting site, where each row contains
+    /// the likelihoods for the `alleles + 1` sample frequencies of that site. Note that this holds
+    /// all intersecting likelihoods in memory at once, and so is intended for intersections of
+    /// modest size.
+    pub fn collect_matrices(&mut self) -> io::Result<Vec<Vec<Vec<f32>>>> {
+        self.collect_matrices_with(|_, item| item.to_vec())
+    }
+}
+
+impl<R> Intersect<R, V4>
+where
+    R: io::BufRead + io::Seek,
+{
+    /// Reads all remaining intersecting records into dense matrices, one for each reader.
+    ///
+    /// The matrix for each reader has a row for each intersecting site, where each row contains
+    /// the likelihoods for the `alleles + 1` sample frequencies of that site. The bands are
+    /// expanded to the full set of likelihoods using `fill` for values outside the band, see
+    /// [`Band::into_full`]. Note that this holds all intersecting likelihoods in memory at once,
+    /// and so is intended for intersections of modest size.
+    ///
+    /// [`Band::into_full`]: crate::record::Band::into_full
+    pub fn collect_matrices(&mut self, fill: f32) -> io::Result<Vec<Vec<Vec<f32>>>> {
+        self.collect_matrices_with(|alleles, item| {
+            Box::<[f32]>::from(item.clone().into_full(alleles, fill)).into_vec()
+        })
+    }
+}
+
+impl<R, V> Intersect<R, V>
+where
+    R: io::BufRead + io::Seek,
+    V: Version,
+{
+    /// Reads all remaining intersecting records into dense matrices, using the provided function
+    /// to expand an item into a row given the number of alleles of its contig.
+    fn collect_matrices_with<F>(&mut self, mut f: F) -> io::Result<Vec<Vec<Vec<f32>>>>
+    where
+        F: FnMut(usize, &V::Item) -> Vec<f32>,
+    {
+        let mut bufs = self.create_record_bufs();
+        let mut matrices = vec![Vec::new(); self.readers.len()];
+
+        while self.read_records(&mut bufs)?.is_not_done() {
+            for ((matrix, buf), reader) in matrices.iter_mut().zip(bufs.iter()).zip(&self.readers) {
+                let alleles = reader.index().contig_alleles(*buf.contig_id());
+                matrix.push(f(alleles, buf.item()));
+            }
+        }
+
+        Ok(matrices)
+    }
+}
+
+/// An error indicating that the readers of an intersection have no contigs in common.
+///
+/// See [`Intersect::set_error_on_no_shared_contigs`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NoSharedContigs;
+
+impl fmt::Display for NoSharedContigs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("intersecting readers have no shared contigs")
+    }
+}
+
+impl error::Error for NoSharedContigs {}
+
 /// Shared contigs for readers based on their indexes.
 ///
 /// The representation used is an ordered map from contig names to a vector of contig IDs,
@@ -250,10 +577,10 @@ where
 /// ("chr1", vec![1, 2]), then "chr1" has ID 1 in the first reader, and ID 2 in the second reader.
 /// As elsewhere here, the ID is based on the position in the index.
 ///
-/// Note that as for `Intersect` generally, we assume that contigs occur in the same order in each
-/// index. That is, the same contigs may not be represented in each index, and the same contig may
-/// have a different IDs, but where two or more contigs occur in multiple indices, their ordering
-/// must be constant.
+/// Note that as for `Intersect` generally, unless created using `Intersect::new_unordered`, we
+/// assume that contigs occur in the same order in each index. That is, the same contigs may not
+/// be represented in each index, and the same contig may have a different IDs, but where two or
+/// more contigs occur in multiple indices, their ordering must be constant.
 #[derive(Clone, Debug)]
 struct SharedContigs(IndexMap<String, Vec<usize>>);
 
@@ -265,21 +592,13 @@ impl SharedContigs {
     where
         V: Version,
     {
-        // Create a temporary mapping from names to IDs in the new index
-        let map: IndexMap<&str, usize> = index
-            .records()
-            .iter()
-            .enumerate()
-            .map(|(i, record)| (record.name(), i))
-            .collect();
-
         // For each name in the current selection, check if the name exists in the new index:
         // if so, (1) add its ID in the new index to the collection of IDs for this shared contig;
         // if not, (2) the contig is no longer shared, and should be removed
         self.0.retain(|name, ids| {
-            if let Some(new_id) = map.get(name.as_str()) {
+            if let Some(new_id) = index.contig_id(name) {
                 // (1)
-                ids.push(*new_id);
+                ids.push(new_id);
                 true
             } else {
                 // (2)
@@ -325,6 +644,6 @@ impl FromIterator<(String, Vec<usize>)> for SharedContigs {
     where
         I: IntoIterator<Item = (String, Vec<usize>)>,
     {
-        Self(iter.into_iter().map(|(s, a)| (s, a)).collect())
+        Self(iter.into_iter().collect())
     }
 }
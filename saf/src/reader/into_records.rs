@@ -0,0 +1,85 @@
+use std::io;
+
+use crate::{
+    record::{Id, Record},
+    version::Version,
+};
+
+use super::Reader;
+
+/// An iterator over the owned records of a reader.
+///
+/// A single record buffer is reused for reading, and each record is cloned from the buffer before
+/// being yielded. The iterator stops after the end of the index, or after returning an error.
+///
+/// Created using [`Reader::into_records`], or by using the reader as an [`IntoIterator`].
+pub struct IntoRecords<R, V>
+where
+    V: Version,
+{
+    inner: Reader<R, V>,
+    buf: Record<Id, V::Item>,
+    is_done: bool,
+}
+
+impl<R, V> IntoRecords<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+{
+    /// Returns the inner reader, consuming `self`.
+    pub fn into_inner(self) -> Reader<R, V> {
+        self.inner
+    }
+
+    pub(super) fn new(inner: Reader<R, V>) -> Self {
+        let buf = inner.create_record_buf();
+
+        Self {
+            inner,
+            buf,
+            is_done: false,
+        }
+    }
+}
+
+impl<R, V> Iterator for IntoRecords<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+    V::Item: Clone,
+{
+    type Item = io::Result<Record<Id, V::Item>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        match self.inner.read_record(&mut self.buf) {
+            Ok(status) if status.is_not_done() => Some(Ok(self.buf.clone())),
+            Ok(_) => {
+                self.is_done = true;
+                None
+            }
+            Err(e) => {
+                self.is_done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<R, V> IntoIterator for Reader<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+    V::Item: Clone,
+{
+    type Item = io::Result<Record<Id, V::Item>>;
+    type IntoIter = IntoRecords<R, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_records()
+    }
+}
@@ -0,0 +1,241 @@
+use std::{io, sync::mpsc, thread, vec};
+
+use crate::{
+    record::{Id, Record},
+    version::Version,
+    ReadStatus,
+};
+
+use super::{eof_err, index_exhausted_err, Index, Location, ReaderExt};
+
+/// The number of sites sent between threads at a time.
+const BATCH_SIZE: usize = 4096;
+
+/// The number of batches that may be buffered between threads at a time.
+const CHANNEL_CAPACITY: usize = 4;
+
+/// A SAF reader decompressing positions and items on separate threads.
+///
+/// The position and item files are read and decompressed on their own background threads, which
+/// send batches of sites over bounded channels to be combined by [`Self::read_record`]. This
+/// overlaps I/O and decompression of the two files with each other and with the caller's work on
+/// the records read.
+///
+/// This comes at the cost of two additional threads, and some latency before the first records
+/// become available. Moreover, items are allocated anew for each site rather than read into a
+/// reused buffer. Hence, the parallel reader is mostly beneficial when reading through large files,
+/// where decompression is the bottleneck. Note that this is independent of the BGZF worker threads
+/// set on the inner readers, and the two may be combined.
+///
+/// Created using [`Reader::into_parallel`](super::Reader::into_parallel).
+pub struct ParallelReader<V>
+where
+    V: Version,
+{
+    location: Location<V>,
+    positions: Batches<u32>,
+    items: Batches<V::Item>,
+}
+
+impl<V> ParallelReader<V>
+where
+    V: Version,
+{
+    /// Returns a new record suitable for use in reading.
+    pub fn create_record_buf(&self) -> Record<Id, V::Item> {
+        V::create_record_buf(self.index())
+    }
+
+    /// Returns the index.
+    pub fn index(&self) -> &Index<V> {
+        &self.location.index
+    }
+
+    pub(super) fn new<R>(
+        location: Location<V>,
+        mut position_reader: bgzf::Reader<R>,
+        mut item_reader: bgzf::Reader<R>,
+    ) -> Self
+    where
+        R: io::Read + Send + 'static,
+        V::Item: Clone + Send + 'static,
+    {
+        // Number of sites and alleles for each remaining contig, starting from the current one;
+        // if the reader has already reached the end of the index, no contigs remain
+        let contigs = if location.contig_id < location.index.records().len() {
+            std::iter::once((
+                location.sites_left_on_contig,
+                location.index.contig_alleles(location.contig_id),
+            ))
+            .chain(
+                location.index.records()[location.contig_id + 1..]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, record)| {
+                        let alleles = location.index.contig_alleles(location.contig_id + 1 + i);
+                        (record.sites(), alleles)
+                    }),
+            )
+            .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        let sites = contigs.iter().map(|(sites, _)| sites).sum();
+        let positions = Batches::spawn(move |sender| {
+            send_batches(&sender, sites, || {
+                position_reader
+                    .read_position()?
+                    .ok_or_else(|| eof_err("reached EoF in SAF position file before end of index"))
+            })?;
+
+            check_done(&mut position_reader, "SAF position file")
+        });
+
+        let template = V::create_record_buf(&location.index).into_item();
+        let items = Batches::spawn(move |sender| {
+            for (sites, alleles) in contigs {
+                let mut item = template.clone();
+                V::resize_item(&mut item, alleles);
+
+                send_batches(&sender, sites, || {
+                    let mut buf = item.clone();
+
+                    match V::read_item(&mut item_reader, &mut buf)? {
+                        ReadStatus::NotDone => Ok(buf),
+                        ReadStatus::Done => {
+                            Err(eof_err("reached EoF in SAF item file before end of index"))
+                        }
+                    }
+                })?;
+            }
+
+            check_done(&mut item_reader, "SAF item file")
+        });
+
+        Self {
+            location,
+            positions,
+            items,
+        }
+    }
+
+    /// Reads a single record.
+    ///
+    /// See [`Reader::read_record`](super::Reader::read_record) for details. Unlike the regular
+    /// reader, the item of the record buffer is replaced rather than read into.
+    pub fn read_record(&mut self, record: &mut Record<Id, V::Item>) -> io::Result<ReadStatus> {
+        if let Some(contig_id) = self.location.next_site() {
+            match (self.positions.next()?, self.items.next()?) {
+                (Some(position), Some(item)) => {
+                    *record.contig_id_mut() = contig_id;
+                    *record.position_mut() = position;
+                    *record.item_mut() = item;
+
+                    Ok(ReadStatus::NotDone)
+                }
+                _ => Err(eof_err("reached EoF in SAF files before end of index")),
+            }
+        } else {
+            // Reached end of index, wait for readers to report whether they are at EoF
+            match (self.positions.next()?, self.items.next()?) {
+                (None, None) => Ok(ReadStatus::Done),
+                _ => Err(index_exhausted_err("SAF files")),
+            }
+        }
+    }
+}
+
+/// Batches of values received from a background thread.
+struct Batches<T> {
+    receiver: mpsc::Receiver<io::Result<Vec<T>>>,
+    batch: vec::IntoIter<T>,
+}
+
+impl<T> Batches<T>
+where
+    T: Send + 'static,
+{
+    /// Spawns a thread sending batches using the provided function.
+    ///
+    /// Any error returned by the function is sent as well.
+    fn spawn<F>(f: F) -> Self
+    where
+        F: FnOnce(mpsc::SyncSender<io::Result<Vec<T>>>) -> io::Result<()> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+
+        thread::spawn(move || {
+            let error_sender = sender.clone();
+
+            if let Err(e) = f(sender) {
+                // Receiver may have been dropped, in which case there's no one to report to
+                let _ = error_sender.send(Err(e));
+            }
+        });
+
+        Self {
+            receiver,
+            batch: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<T> Batches<T> {
+    /// Returns the next value.
+    ///
+    /// Returns `None` if the thread has finished sending values.
+    fn next(&mut self) -> io::Result<Option<T>> {
+        loop {
+            if let Some(value) = self.batch.next() {
+                return Ok(Some(value));
+            }
+
+            match self.receiver.recv() {
+                Ok(batch) => self.batch = batch?.into_iter(),
+                Err(mpsc::RecvError) => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Sends `n` values created by the provided function in batches.
+///
+/// Returns an error if creating a value fails. If the receiver has been dropped, stops silently.
+fn send_batches<T, F>(
+    sender: &mpsc::SyncSender<io::Result<Vec<T>>>,
+    n: usize,
+    mut f: F,
+) -> io::Result<()>
+where
+    F: FnMut() -> io::Result<T>,
+{
+    let mut left = n;
+
+    while left > 0 {
+        let batch_size = left.min(BATCH_SIZE);
+        let batch = (0..batch_size)
+            .map(|_| f())
+            .collect::<io::Result<Vec<_>>>()?;
+
+        if sender.send(Ok(batch)).is_err() {
+            break;
+        }
+
+        left -= batch_size;
+    }
+
+    Ok(())
+}
+
+/// Returns an error if the reader is not at EoF.
+fn check_done<R>(reader: &mut R, file: &'static str) -> io::Result<()>
+where
+    R: io::BufRead,
+{
+    if ReadStatus::check(reader)?.is_done() {
+        Ok(())
+    } else {
+        Err(index_exhausted_err(file))
+    }
+}
@@ -0,0 +1,233 @@
+use std::io;
+
+use crate::{version::Version, Index, POSITION_BYTES};
+
+use super::{narrow_position, ReaderExt};
+
+/// A SAF position file reader.
+///
+/// The position reader reads only from the SAF position file, without reference to the index or
+/// the item file. The inner reader will typically be a [`bgzf::Reader`].
+pub struct PositionReader<R> {
+    inner: R,
+}
+
+impl<R> PositionReader<R>
+where
+    R: io::BufRead,
+{
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader, consuming `self.`
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Creates a new position reader.
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads and checks the magic number for the provided version.
+    ///
+    /// Assumes the stream is positioned at the beginning of the file.
+    pub fn read_magic<V>(&mut self) -> io::Result<()>
+    where
+        V: Version,
+    {
+        V::read_magic(&mut self.inner)
+    }
+
+    /// Reads a single position.
+    ///
+    /// Returns `None` if reader is at end of file.
+    pub fn read_position(&mut self) -> io::Result<Option<u32>> {
+        self.inner.read_position()
+    }
+
+    /// Creates a position reader tracking contigs using the provided index, consuming `self`.
+    ///
+    /// See [`IndexedPositionReader`] for details.
+    pub fn with_index<V>(self, index: Index<V>) -> IndexedPositionReader<R, V>
+    where
+        V: Version,
+    {
+        IndexedPositionReader::new(self, index)
+    }
+
+    /// Reads as many positions as fit in the provided buffer.
+    ///
+    /// Positions are decoded directly from the buffer of the inner reader, which is faster than
+    /// reading them one at a time. Returns the number of positions read, which will be less than
+    /// the length of `out` only if the end of the file was reached.
+    pub fn read_positions(&mut self, out: &mut [u32]) -> io::Result<usize> {
+        let mut n = 0;
+
+        while n < out.len() {
+            let buf = self.inner.fill_buf()?;
+
+            if buf.is_empty() {
+                break;
+            } else if buf.len() < POSITION_BYTES {
+                // Position is split across inner buffer boundary, fall back to reading it alone
+                match self.inner.read_position()? {
+                    Some(position) => {
+                        out[n] = position;
+                        n += 1;
+                    }
+                    None => break,
+                }
+            } else {
+                let chunks = buf.chunks_exact(POSITION_BYTES).take(out.len() - n);
+                let read = chunks.len();
+
+                for (x, chunk) in out[n..].iter_mut().zip(chunks) {
+                    *x = u32::from_le_bytes(chunk.try_into().unwrap());
+                }
+
+                self.inner.consume(read * POSITION_BYTES);
+                n += read;
+            }
+        }
+
+        Ok(n)
+    }
+}
+
+/// A SAF position file reader tracking contigs.
+///
+/// Since the position file contains no contig information, the current contig is determined by
+/// counting the positions read against the number of sites for each contig in the index. This
+/// assumes that the inner reader is positioned at the first position of the first contig in the
+/// index, typically immediately after the magic number. Positions are read according to the
+/// position width and base of the index, and are returned 0-based.
+///
+/// Created using [`PositionReader::with_index`].
+pub struct IndexedPositionReader<R, V> {
+    inner: PositionReader<R>,
+    index: Index<V>,
+    contig_id: usize,
+    sites_read: usize,
+}
+
+impl<R, V> IndexedPositionReader<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+{
+    /// Returns the index.
+    pub fn index(&self) -> &Index<V> {
+        &self.index
+    }
+
+    /// Returns the inner position reader.
+    pub fn get_ref(&self) -> &PositionReader<R> {
+        &self.inner
+    }
+
+    /// Returns the inner position reader and the index, consuming `self`.
+    pub fn into_parts(self) -> (PositionReader<R>, Index<V>) {
+        (self.inner, self.index)
+    }
+
+    fn new(inner: PositionReader<R>, index: Index<V>) -> Self {
+        Self {
+            inner,
+            index,
+            contig_id: 0,
+            sites_read: 0,
+        }
+    }
+
+    /// Reads a single position, along with the ID of the contig it belongs to.
+    ///
+    /// The contig ID is given by the position of the contig in the index. Returns `None` if the
+    /// reader is at end of file. Returns an error if more positions remain than the index accounts
+    /// for, or if a position does not fit in 32 bits.
+    pub fn read_position(&mut self) -> io::Result<Option<(usize, u32)>> {
+        let width = self.index.position_width();
+        let Some(position) = self.inner.inner.read_position_with_width(width)? else {
+            return Ok(None);
+        };
+        let position = self.index.position_base().to_zero_based(position)?;
+
+        let records = self.index.records();
+        while records
+            .get(self.contig_id)
+            .is_some_and(|record| self.sites_read >= record.sites())
+        {
+            self.contig_id += 1;
+            self.sites_read = 0;
+        }
+
+        if self.contig_id >= records.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "more positions in position file than sites in index",
+            ));
+        }
+
+        self.sites_read += 1;
+
+        Ok(Some((self.contig_id, narrow_position(position)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position_data(positions: &[u32]) -> Vec<u8> {
+        positions.iter().flat_map(|x| x.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_read_positions_matches_single_reads() -> io::Result<()> {
+        let positions = (0..1000).map(|x| x * 3 + 1).collect::<Vec<u32>>();
+        let data = position_data(&positions);
+
+        // Odd capacity ensures positions are split across inner buffer boundaries
+        for capacity in [4, 7, 64, 8192] {
+            let mut single = PositionReader::new(io::BufReader::with_capacity(capacity, &data[..]));
+            let mut bulk = PositionReader::new(io::BufReader::with_capacity(capacity, &data[..]));
+
+            let mut expected = Vec::new();
+            while let Some(position) = single.read_position()? {
+                expected.push(position);
+            }
+
+            let mut read = Vec::new();
+            let mut out = [0; 33];
+            loop {
+                let n = bulk.read_positions(&mut out)?;
+                read.extend_from_slice(&out[..n]);
+
+                if n < out.len() {
+                    break;
+                }
+            }
+
+            assert_eq!(read, expected);
+            assert_eq!(read, positions);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_positions_truncated() {
+        let mut data = position_data(&[1, 2]);
+        data.pop();
+
+        let mut reader = PositionReader::new(&data[..]);
+        assert!(reader.read_positions(&mut [0; 2]).is_err());
+    }
+}
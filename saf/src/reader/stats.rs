@@ -0,0 +1,61 @@
+/// Statistics on the compression of SAF position and item files.
+///
+/// Created using [`Reader::compression_stats`](super::Reader::compression_stats). All sizes are in
+/// bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CompressionStats {
+    compressed_position_bytes: u64,
+    uncompressed_position_bytes: u64,
+    compressed_item_bytes: u64,
+    uncompressed_item_bytes: u64,
+}
+
+impl CompressionStats {
+    /// Returns the compressed size of the item file.
+    pub fn compressed_item_bytes(&self) -> u64 {
+        self.compressed_item_bytes
+    }
+
+    /// Returns the compressed size of the position file.
+    pub fn compressed_position_bytes(&self) -> u64 {
+        self.compressed_position_bytes
+    }
+
+    /// Returns the compression ratio of the item file.
+    ///
+    /// This is the uncompressed size divided by the compressed size.
+    pub fn item_ratio(&self) -> f64 {
+        self.uncompressed_item_bytes as f64 / self.compressed_item_bytes as f64
+    }
+
+    pub(super) fn new(
+        compressed_position_bytes: u64,
+        uncompressed_position_bytes: u64,
+        compressed_item_bytes: u64,
+        uncompressed_item_bytes: u64,
+    ) -> Self {
+        Self {
+            compressed_position_bytes,
+            uncompressed_position_bytes,
+            compressed_item_bytes,
+            uncompressed_item_bytes,
+        }
+    }
+
+    /// Returns the compression ratio of the position file.
+    ///
+    /// This is the uncompressed size divided by the compressed size.
+    pub fn position_ratio(&self) -> f64 {
+        self.uncompressed_position_bytes as f64 / self.compressed_position_bytes as f64
+    }
+
+    /// Returns the uncompressed size of the item file.
+    pub fn uncompressed_item_bytes(&self) -> u64 {
+        self.uncompressed_item_bytes
+    }
+
+    /// Returns the uncompressed size of the position file.
+    pub fn uncompressed_position_bytes(&self) -> u64 {
+        self.uncompressed_position_bytes
+    }
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use crate::{
+    record::{Id, Record},
+    version::Version,
+    ReadStatus,
+};
+
+use super::Reader;
+
+/// A SAF reader that stops after a number of contigs.
+///
+/// Reading finishes once all sites on the last allowed contig have been read, without reading any
+/// data from the following contig. Contigs without sites are not counted. Reading continues from
+/// the current location of the inner reader when the adapter is created, and only contigs read
+/// through the adapter are counted.
+///
+/// Created using [`Reader::take_contigs`].
+pub struct TakeContigs<R, V> {
+    inner: Reader<R, V>,
+    n: usize,
+    contigs_read: usize,
+    last_contig_id: Option<usize>,
+}
+
+impl<R, V> TakeContigs<R, V> {
+    /// Returns the number of contigs read so far.
+    pub fn contigs_read(&self) -> usize {
+        self.contigs_read
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &Reader<R, V> {
+        &self.inner
+    }
+
+    /// Returns the inner reader, consuming `self`.
+    pub fn into_inner(self) -> Reader<R, V> {
+        self.inner
+    }
+
+    pub(super) fn new(inner: Reader<R, V>, n: usize) -> Self {
+        Self {
+            inner,
+            n,
+            contigs_read: 0,
+            last_contig_id: None,
+        }
+    }
+}
+
+impl<R, V> TakeContigs<R, V>
+where
+    R: io::BufRead,
+    V: Version,
+{
+    /// Reads a single record, unless the number of contigs has been reached.
+    ///
+    /// See [`Reader::read_record`] for details.
+    pub fn read_record(&mut self, record: &mut Record<Id, V::Item>) -> io::Result<ReadStatus> {
+        if self.contigs_read >= self.n
+            && (self.last_contig_id.is_none() || self.inner.location.contig_is_finished())
+        {
+            return Ok(ReadStatus::Done);
+        }
+
+        let status = self.inner.read_record(record)?;
+
+        if status.is_not_done() && self.last_contig_id != Some(*record.contig_id()) {
+            self.last_contig_id = Some(*record.contig_id());
+            self.contigs_read += 1;
+        }
+
+        Ok(status)
+    }
+}
@@ -2,7 +2,7 @@ use std::{io, mem};
 
 use byteorder::{ReadBytesExt, LE};
 
-use crate::ReadStatus;
+use crate::{index::PositionWidth, ReadStatus, POSITION_BYTES};
 
 /// An extension trait for reading.
 pub trait ReaderExt {
@@ -16,8 +16,21 @@ pub trait ReaderExt {
     /// Returns `None` if reader is at end of file.
     fn read_position(&mut self) -> io::Result<Option<u32>>;
 
+    /// Read a single 64-bit position.
+    ///
+    /// Returns `None` if reader is at end of file.
+    fn read_position_u64(&mut self) -> io::Result<Option<u64>>;
+
+    /// Read a single position of the provided width.
+    ///
+    /// Returns `None` if reader is at end of file.
+    fn read_position_with_width(&mut self, width: PositionWidth) -> io::Result<Option<u64>>;
+
     /// Read likelihoods.
     fn read_likelihoods(&mut self, buf: &mut [f32]) -> io::Result<ReadStatus>;
+
+    /// Skips a number of likelihoods without decoding them.
+    fn skip_likelihoods(&mut self, n: usize) -> io::Result<()>;
 }
 
 impl<R> ReaderExt for R
@@ -29,31 +42,17 @@ where
     }
 
     fn read_position(&mut self) -> io::Result<Option<u32>> {
-        // Modified from std::io::default_read_exact
-        let mut arr = [0; mem::size_of::<u32>()];
-        let mut buf = &mut arr[..];
-
-        while !buf.is_empty() {
-            match self.read(buf) {
-                Ok(0) => break,
-                Ok(n) => {
-                    let tmp = buf;
-                    buf = &mut tmp[n..];
-                }
-                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
-                Err(e) => return Err(e),
-            }
-        }
+        read_position_bytes::<_, POSITION_BYTES>(self).map(|arr| arr.map(u32::from_le_bytes))
+    }
 
-        if buf.len() == 4 {
-            Ok(None)
-        } else if !buf.is_empty() {
-            Err(io::Error::new(
-                io::ErrorKind::UnexpectedEof,
-                "failed to read position",
-            ))
-        } else {
-            Ok(Some(u32::from_le_bytes(arr)))
+    fn read_position_u64(&mut self) -> io::Result<Option<u64>> {
+        read_position_bytes::<_, 8>(self).map(|arr| arr.map(u64::from_le_bytes))
+    }
+
+    fn read_position_with_width(&mut self, width: PositionWidth) -> io::Result<Option<u64>> {
+        match width {
+            PositionWidth::U32 => self.read_position().map(|pos| pos.map(u64::from)),
+            PositionWidth::U64 => self.read_position_u64(),
         }
     }
 
@@ -64,4 +63,54 @@ where
 
         self.read_f32_into::<LE>(buf).map(|_| ReadStatus::NotDone)
     }
+
+    fn skip_likelihoods(&mut self, n: usize) -> io::Result<()> {
+        let skip_bytes = (n * mem::size_of::<f32>()) as u64;
+
+        let skipped = io::copy(&mut io::Read::take(self, skip_bytes), &mut io::sink())?;
+
+        if skipped == skip_bytes {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to skip likelihoods",
+            ))
+        }
+    }
+}
+
+/// Reads the bytes of a single position of `N` bytes.
+///
+/// Returns `None` if reader is at end of file.
+fn read_position_bytes<R, const N: usize>(reader: &mut R) -> io::Result<Option<[u8; N]>>
+where
+    R: io::BufRead + ?Sized,
+{
+    // Modified from std::io::default_read_exact
+    let mut arr = [0; N];
+    let mut buf = &mut arr[..];
+
+    while !buf.is_empty() {
+        match reader.read(buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                let tmp = buf;
+                buf = &mut tmp[n..];
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    if buf.len() == N {
+        Ok(None)
+    } else if !buf.is_empty() {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "failed to read position",
+        ))
+    } else {
+        Ok(Some(arr))
+    }
 }
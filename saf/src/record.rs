@@ -11,6 +11,15 @@ use super::{index::Index, version::Version};
 
 const SEP: &str = "\t";
 
+/// The base of the natural logarithm, for use with `convert_log_base`.
+pub const LN: f32 = std::f32::consts::E;
+
+/// The base of the base-10 logarithm, for use with `convert_log_base`.
+pub const LOG10: f32 = 10.0;
+
+/// The base of the base-2 logarithm, for use with `convert_log_base`.
+pub const LOG2: f32 = 2.0;
+
 /// A SAF index contig ID.
 ///
 /// The ID has no meaning other than that it may be used to index the SAF index records.
@@ -18,8 +27,44 @@ pub type Id = usize;
 
 /// SAF likelihoods values.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Likelihoods(Box<[f32]>);
 
+impl Likelihoods {
+    /// Creates a band from the likelihoods.
+    ///
+    /// The band covers the contiguous region around the maximum likelihood where all values are
+    /// greater than `threshold`. The maximum is always retained, even if it does not exceed the
+    /// threshold, so that the band is empty only if the likelihoods are empty. This is the inverse
+    /// of [`Band::into_full`], where values outside the band are dropped. Hence, `threshold` should
+    /// typically be at least the `fill` value used when expanding the band.
+    pub fn to_band(&self, threshold: f32) -> Band {
+        let argmax = match self
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        {
+            Some((i, _)) => i,
+            None => return Band::new(0, Vec::new()),
+        };
+
+        let start = self[..argmax]
+            .iter()
+            .rposition(|&v| v <= threshold)
+            .map_or(0, |i| i + 1);
+        let end = self[argmax + 1..]
+            .iter()
+            .position(|&v| v <= threshold)
+            .map_or(self.len(), |i| argmax + 1 + i);
+
+        Band::new(start, self[start..end].to_vec())
+    }
+}
+
 impl AsRef<[f32]> for Likelihoods {
     fn as_ref(&self) -> &[f32] {
         &self.0
@@ -71,12 +116,52 @@ impl From<Vec<f32>> for Likelihoods {
 /// The band describes the start of the band, as well as its length, and contains the
 /// likelihoods within the band. All values outside the band are implicitly zero.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Band {
     start: usize,
     likelihoods: Vec<f32>,
 }
 
 impl Band {
+    /// Returns the sample frequency of the greatest likelihood in the band.
+    ///
+    /// The frequency is absolute, that is, it accounts for the start of the band. In case of ties,
+    /// the lowest frequency is returned. If the band is empty, the start of the band is returned.
+    pub fn argmax(&self) -> usize {
+        let (argmax, _) = self.likelihoods.iter().enumerate().fold(
+            (0, f32::NEG_INFINITY),
+            |(argmax, max), (i, &v)| if v > max { (i, v) } else { (argmax, max) },
+        );
+
+        self.start + argmax
+    }
+
+    /// Returns the end of the band.
+    ///
+    /// This corresponds to one past the last sample frequency that is represented in the band.
+    pub fn end(&self) -> usize {
+        self.start + self.likelihoods.len()
+    }
+
+    /// Creates the smallest band containing all likelihoods not equal to `fill`.
+    ///
+    /// The band starts at the first and ends at the last likelihood not equal to `fill`, and
+    /// values equal to `fill` between these are retained to keep the band contiguous. If all
+    /// likelihoods equal `fill`, the band is empty and starts at zero. This is the inverse of
+    /// [`Band::into_full`] with the same `fill`. See also [`Likelihoods::to_band`] to create a
+    /// band using a threshold.
+    pub fn from_likelihoods(likelihoods: &[f32], fill: f32) -> Self {
+        let is_not_fill = |&v: &f32| v != fill;
+
+        match (
+            likelihoods.iter().position(is_not_fill),
+            likelihoods.iter().rposition(is_not_fill),
+        ) {
+            (Some(first), Some(last)) => Self::new(first, likelihoods[first..=last].to_vec()),
+            _ => Self::new(0, Vec::new()),
+        }
+    }
+
     /// Converts the band into a full set of likelihoods.
     ///
     /// The `alleles` argument here corresponds to the alleles argument defined in the [`Index`],
@@ -85,10 +170,8 @@ impl Band {
     /// Likelihoods that are not explicitly represented in the band will be set to `fill`.
     /// This would typically be `0.0` when not in log-space.
     pub fn into_full(self, alleles: usize, fill: f32) -> Likelihoods {
-        let mut v = self.likelihoods;
-
-        v.splice(0..0, iter::repeat(fill).take(self.start));
-        v.extend(iter::repeat(fill).take(alleles + 1 - v.len()));
+        let mut v = Vec::with_capacity(alleles + 1);
+        self.write_full_into(&mut v, alleles, fill);
 
         v.into()
     }
@@ -132,6 +215,37 @@ impl Band {
     pub fn start_mut(&mut self) -> &mut usize {
         &mut self.start
     }
+
+    /// Returns the sum of the band likelihoods.
+    ///
+    /// When likelihoods are not in log-space, this should be close to one for a band containing
+    /// all non-negligible likelihoods.
+    pub fn sum(&self) -> f32 {
+        self.likelihoods.iter().sum()
+    }
+
+    /// Writes the band as a full set of likelihoods into a buffer.
+    ///
+    /// The buffer is cleared and filled in place, so that its allocation may be reused across
+    /// many bands. Otherwise, this is equivalent to [`Band::into_full`].
+    pub fn write_full_into(&self, out: &mut Vec<f32>, alleles: usize, fill: f32) {
+        out.clear();
+        out.extend(iter::repeat_n(fill, self.start));
+        out.extend_from_slice(&self.likelihoods);
+        out.extend(iter::repeat_n(fill, alleles + 1 - out.len()));
+    }
+}
+
+impl AsRef<[f32]> for Band {
+    fn as_ref(&self) -> &[f32] {
+        &self.likelihoods
+    }
+}
+
+impl AsMut<[f32]> for Band {
+    fn as_mut(&mut self) -> &mut [f32] {
+        &mut self.likelihoods
+    }
 }
 
 /// A SAF record.
@@ -140,6 +254,7 @@ impl Band {
 /// ID will be an [`Id`]. When writing, the contig ID will be string-like. The contained item can
 /// either be a full set of [`Likelihoods`], or only a smaller [`Band`] of likelihoods.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Record<I, T> {
     contig_id: I,
     position: u32,
@@ -206,9 +321,137 @@ impl<I> Record<I, Likelihoods> {
 
         Self::new(contig_id, position, item)
     }
+
+    /// Converts the log-likelihoods from log base `from` to log base `to`.
+    ///
+    /// See [`LN`], [`LOG10`], and [`LOG2`] for common bases.
+    pub fn convert_log_base(&mut self, from: f32, to: f32) {
+        convert_log_base(&mut self.item, from, to)
+    }
+
+    /// Returns a new record with folded likelihoods.
+    ///
+    /// Folding is used when the ancestral state is unknown, so that only the minor allele count can
+    /// be distinguished. For `alleles` alleles, the likelihood of sample frequency `i` is combined
+    /// with that of `alleles - i`, and only the first `alleles / 2 + 1` categories are kept. Since
+    /// the likelihoods are assumed to be in log-space, they are summed in linear space. When
+    /// `alleles` is even, the middle category `alleles / 2` is its own mirror image, and is kept
+    /// as is. When `alleles` is odd, there is no middle category, and all categories are summed in
+    /// pairs.
+    pub fn fold(&self) -> Record<I, Likelihoods>
+    where
+        I: Clone,
+    {
+        let mut folded = vec![0.0; self.item.len().div_ceil(2)];
+        fold_into(&self.item, &mut folded);
+
+        Record::new(self.contig_id.clone(), self.position, folded.into())
+    }
+
+    /// Returns the posterior mean sample frequency given a prior over sample frequencies.
+    ///
+    /// The likelihoods are assumed to be in log-space, while the prior is assumed to be in linear
+    /// space. The posterior is obtained by multiplying the likelihoods and the prior in linear
+    /// space and normalising, and the expected sample frequency under the posterior is returned.
+    /// The prior need not be normalised.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the prior does not match the number of likelihoods.
+    pub fn posterior_mean_frequency(&self, prior: &[f32]) -> f32 {
+        assert_eq!(
+            prior.len(),
+            self.item.len(),
+            "prior length does not match number of likelihoods"
+        );
+
+        let max = self.item.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        let (sum, weighted_sum) = self.item.iter().zip(prior).enumerate().fold(
+            (0.0, 0.0),
+            |(sum, weighted_sum), (i, (&v, &p))| {
+                let posterior = (v - max).exp() * p;
+                (sum + posterior, weighted_sum + i as f32 * posterior)
+            },
+        );
+
+        weighted_sum / sum
+    }
+
+    /// Returns the posterior mean sample frequency given a uniform prior over sample frequencies.
+    ///
+    /// See [`Self::posterior_mean_frequency`] for details.
+    pub fn posterior_mean_frequency_uniform(&self) -> f32 {
+        self.posterior_mean_frequency(&vec![1.0; self.item.len()])
+    }
+
+    /// Returns whether the difference between the greatest and smallest likelihood exceeds
+    /// `min_spread`.
+    ///
+    /// This can be used to filter out uninformative sites, where all sample frequencies are
+    /// (close to) equally likely.
+    pub fn is_informative(&self, min_spread: f32) -> bool {
+        is_informative(&self.item, min_spread)
+    }
 }
 
 impl<I> Record<I, Band> {
+    /// Converts the log-likelihoods in the band from log base `from` to log base `to`.
+    ///
+    /// See [`LN`], [`LOG10`], and [`LOG2`] for common bases.
+    pub fn convert_log_base(&mut self, from: f32, to: f32) {
+        convert_log_base(self.item.likelihoods_mut(), from, to)
+    }
+
+    /// Creates a new record, checking that the band fits within the provided number of alleles.
+    ///
+    /// The `alleles` argument here corresponds to the alleles argument defined in the [`Index`].
+    /// The band fits if it ends no later than `alleles + 1`, which is required by
+    /// [`Band::into_full`]. See [`Record::new`] for an unchecked constructor.
+    pub fn from_band_checked(
+        contig_id: I,
+        position: u32,
+        band: Band,
+        alleles: usize,
+    ) -> Result<Self, BandError> {
+        if band.start() + band.len() <= alleles + 1 {
+            Ok(Self::new(contig_id, position, band))
+        } else {
+            Err(BandError {
+                start: band.start(),
+                len: band.len(),
+                alleles,
+            })
+        }
+    }
+
+    /// Returns a new record with folded likelihoods.
+    ///
+    /// The band is first expanded to the full set of likelihoods using [`Band::into_full`] with
+    /// the provided `alleles` and `fill`, and then folded as described in
+    /// [`Record::<I, Likelihoods>::fold`]. Since the likelihoods are assumed to be in log-space,
+    /// `fill` should typically be [`f32::NEG_INFINITY`].
+    pub fn fold(&self, alleles: usize, fill: f32) -> Record<I, Likelihoods>
+    where
+        I: Clone,
+    {
+        let full = self.item.clone().into_full(alleles, fill);
+
+        let mut folded = vec![0.0; full.len().div_ceil(2)];
+        fold_into(&full, &mut folded);
+
+        Record::new(self.contig_id.clone(), self.position, folded.into())
+    }
+
+    /// Returns whether the difference between the greatest and smallest likelihood in the band
+    /// exceeds `min_spread`.
+    ///
+    /// Only the likelihoods explicitly represented in the band are considered. See also
+    /// [`Record::<I, Likelihoods>::is_informative`].
+    pub fn is_informative(&self, min_spread: f32) -> bool {
+        is_informative(self.item.likelihoods(), min_spread)
+    }
+
     /// Converts the record into a record with the full set of likelihoods.
     ///
     /// See also [`Band::into_full`] for more documentation.
@@ -221,6 +464,52 @@ impl<I> Record<I, Band> {
     }
 }
 
+/// Converts values from log base `from` to log base `to`.
+fn convert_log_base(values: &mut [f32], from: f32, to: f32) {
+    let factor = from.ln() / to.ln();
+
+    values.iter_mut().for_each(|v| *v *= factor);
+}
+
+/// Returns whether the difference between the greatest and smallest value exceeds `min_spread`.
+///
+/// Returns `false` for empty values.
+pub(crate) fn is_informative(values: &[f32], min_spread: f32) -> bool {
+    let (min, max) = values
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| {
+            (min.min(v), max.max(v))
+        });
+
+    max - min > min_spread
+}
+
+/// Folds log-likelihoods into the provided buffer.
+///
+/// The buffer must have length `likelihoods.len().div_ceil(2)`. See
+/// [`Record::<I, Likelihoods>::fold`].
+pub(crate) fn fold_into(likelihoods: &[f32], folded: &mut [f32]) {
+    debug_assert_eq!(folded.len(), likelihoods.len().div_ceil(2));
+
+    let n = likelihoods.len();
+    for (i, v) in folded.iter_mut().enumerate() {
+        let (a, b) = (likelihoods[i], likelihoods[n - 1 - i]);
+
+        *v = if i == n - 1 - i { a } else { log_add(a, b) };
+    }
+}
+
+/// Returns the logarithm of the sum of two values given their logarithms.
+fn log_add(a: f32, b: f32) -> f32 {
+    let (max, min) = if a >= b { (a, b) } else { (b, a) };
+
+    if max == f32::NEG_INFINITY {
+        max
+    } else {
+        max + (min - max).exp().ln_1p()
+    }
+}
+
 impl<T> Record<Id, T> {
     /// Creates a new record with a named contig ID, consuming `self`.
     ///
@@ -237,6 +526,289 @@ impl<T> Record<Id, T> {
     }
 }
 
+impl Record<Id, Likelihoods> {
+    /// Returns a borrowed view of the record.
+    ///
+    /// See [`RecordView`].
+    pub fn as_view(&self) -> RecordView<'_> {
+        RecordView {
+            contig_id: self.contig_id,
+            position: self.position,
+            likelihoods: &self.item,
+        }
+    }
+}
+
+/// A borrowed view of a SAF record with full likelihoods.
+///
+/// The view borrows the likelihoods of the record it was created from, and so may be passed
+/// around and displayed without cloning the record. See [`Record::as_view`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordView<'a> {
+    contig_id: Id,
+    position: u32,
+    likelihoods: &'a [f32],
+}
+
+impl<'a> RecordView<'a> {
+    /// Returns the contig ID of the record.
+    pub fn contig_id(&self) -> Id {
+        self.contig_id
+    }
+
+    /// Returns the likelihoods of the record.
+    pub fn likelihoods(&self) -> &'a [f32] {
+        self.likelihoods
+    }
+
+    /// Returns the position of the record.
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+}
+
+/// A SAF record with a 64-bit position.
+///
+/// This is equivalent to [`Record`], except that the position is wide enough for files with
+/// 64-bit positions, see [`Index::position_width`]. Records are freely convertible between the
+/// two, where conversion to a [`Record`] fails if the position does not fit in 32 bits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record64<I, T> {
+    contig_id: I,
+    position: u64,
+    item: T,
+}
+
+impl<I, T> Record64<I, T> {
+    /// Returns the record contig ID.
+    pub fn contig_id(&self) -> &I {
+        &self.contig_id
+    }
+
+    /// Returns a mutable reference to the record contig ID.
+    pub fn contig_id_mut(&mut self) -> &mut I {
+        &mut self.contig_id
+    }
+
+    /// Returns the record item, consuming `self`.
+    pub fn into_item(self) -> T {
+        self.item
+    }
+
+    /// Returns a reference to the record item.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Returns a mutable reference to the record item.
+    pub fn item_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+
+    /// Creates a new record.
+    pub fn new(contig_id: I, position: u64, item: T) -> Self {
+        Self {
+            contig_id,
+            position,
+            item,
+        }
+    }
+
+    /// Returns the record position.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns a mutable reference to the record position.
+    pub fn position_mut(&mut self) -> &mut u64 {
+        &mut self.position
+    }
+}
+
+impl<I, T> From<Record<I, T>> for Record64<I, T> {
+    fn from(record: Record<I, T>) -> Self {
+        Self::new(record.contig_id, u64::from(record.position), record.item)
+    }
+}
+
+impl<I, T> TryFrom<Record64<I, T>> for Record<I, T> {
+    type Error = Record64<I, T>;
+
+    /// Converts a record with a 64-bit position into a record.
+    ///
+    /// Returns the record unchanged if the position does not fit in 32 bits.
+    fn try_from(record: Record64<I, T>) -> Result<Self, Self::Error> {
+        match u32::try_from(record.position) {
+            Ok(position) => Ok(Self::new(record.contig_id, position, record.item)),
+            Err(_) => Err(record),
+        }
+    }
+}
+
+/// A SAF site.
+///
+/// The site holds the position and item of a single record, without its contig. Together with
+/// [`Contig`], this provides a hierarchical representation of SAF data, as an alternative to the
+/// flat [`Record`].
+pub struct Site<V>
+where
+    V: Version,
+{
+    position: u32,
+    item: V::Item,
+}
+
+impl<V> Site<V>
+where
+    V: Version,
+{
+    /// Returns the site item, consuming `self`.
+    pub fn into_item(self) -> V::Item {
+        self.item
+    }
+
+    /// Returns a reference to the site item.
+    pub fn item(&self) -> &V::Item {
+        &self.item
+    }
+
+    /// Returns a mutable reference to the site item.
+    pub fn item_mut(&mut self) -> &mut V::Item {
+        &mut self.item
+    }
+
+    /// Creates a new site.
+    pub fn new(position: u32, item: V::Item) -> Self {
+        Self { position, item }
+    }
+
+    /// Returns the site position.
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    /// Returns a mutable reference to the site position.
+    pub fn position_mut(&mut self) -> &mut u32 {
+        &mut self.position
+    }
+}
+
+impl<V> Clone for Site<V>
+where
+    V: Version,
+    V::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.position, self.item.clone())
+    }
+}
+
+impl<V> fmt::Debug for Site<V>
+where
+    V: Version,
+    V::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Site")
+            .field("position", &self.position)
+            .field("item", &self.item)
+            .finish()
+    }
+}
+
+impl<V> PartialEq for Site<V>
+where
+    V: Version,
+    V::Item: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position && self.item == other.item
+    }
+}
+
+impl<I, V> From<Record<I, V::Item>> for Site<V>
+where
+    V: Version,
+{
+    fn from(record: Record<I, V::Item>) -> Self {
+        Self::new(record.position, record.item)
+    }
+}
+
+/// A SAF contig.
+///
+/// The contig holds a contig name and all [`Site`]s on the contig.
+pub struct Contig<V>
+where
+    V: Version,
+{
+    name: String,
+    sites: Vec<Site<V>>,
+}
+
+impl<V> Contig<V>
+where
+    V: Version,
+{
+    /// Returns the contig sites, consuming `self`.
+    pub fn into_sites(self) -> Vec<Site<V>> {
+        self.sites
+    }
+
+    /// Returns the contig name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Creates a new contig.
+    pub fn new(name: String, sites: Vec<Site<V>>) -> Self {
+        Self { name, sites }
+    }
+
+    /// Returns the contig sites.
+    pub fn sites(&self) -> &[Site<V>] {
+        &self.sites
+    }
+
+    /// Returns a mutable reference to the contig sites.
+    pub fn sites_mut(&mut self) -> &mut Vec<Site<V>> {
+        &mut self.sites
+    }
+}
+
+impl<V> Clone for Contig<V>
+where
+    V: Version,
+    V::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.name.clone(), self.sites.clone())
+    }
+}
+
+impl<V> fmt::Debug for Contig<V>
+where
+    V: Version,
+    V::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Contig")
+            .field("name", &self.name)
+            .field("sites", &self.sites)
+            .finish()
+    }
+}
+
+impl<V> PartialEq for Contig<V>
+where
+    V: Version,
+    V::Item: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.sites == other.sites
+    }
+}
+
 impl<I> fmt::Display for Record<I, Likelihoods>
 where
     I: fmt::Display,
@@ -254,6 +826,20 @@ where
     }
 }
 
+impl fmt::Display for RecordView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.contig_id)?;
+        write!(f, "{SEP}{}", self.position)?;
+
+        for v in self.likelihoods.iter() {
+            f.write_str(SEP)?;
+            v.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<I> fmt::Display for Record<I, Band>
 where
     I: fmt::Display,
@@ -345,10 +931,216 @@ impl From<ParseRecordError> for io::Error {
     }
 }
 
+/// An error associated with a band not fitting within the number of alleles.
+///
+/// See [`Record::<I, Band>::from_band_checked`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BandError {
+    /// The band start.
+    pub start: usize,
+    /// The band length.
+    pub len: usize,
+    /// The number of alleles.
+    pub alleles: usize,
+}
+
+impl fmt::Display for BandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "band with start {} and length {} exceeds {} alleles",
+            self.start, self.len, self.alleles
+        )
+    }
+}
+
+impl Error for BandError {}
+
+impl From<BandError> for io::Error {
+    fn from(error: BandError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn assert_approx_eq(a: &[f32], b: &[f32]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x == y) || (x - y).abs() < 1e-5, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_fold_even_alleles() {
+        let ln = |v: &[f32]| v.iter().map(|x| x.ln()).collect::<Vec<_>>();
+
+        let record = Record::new(
+            "chr1",
+            1,
+            Likelihoods::from(ln(&[0.1, 0.2, 0.3, 0.15, 0.25])),
+        );
+        let folded = record.fold();
+
+        assert_eq!(folded.contig_id(), &"chr1");
+        assert_eq!(folded.position(), 1);
+        assert_approx_eq(folded.item(), &ln(&[0.35, 0.35, 0.3]));
+    }
+
+    #[test]
+    fn test_fold_odd_alleles() {
+        let ln = |v: &[f32]| v.iter().map(|x| x.ln()).collect::<Vec<_>>();
+
+        let record = Record::new("chr1", 1, Likelihoods::from(ln(&[0.1, 0.2, 0.3, 0.4])));
+
+        assert_approx_eq(record.fold().item(), &ln(&[0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_fold_with_neg_infinity() {
+        let record = Record::new(
+            0,
+            1,
+            Likelihoods::from(vec![f32::NEG_INFINITY, 0., f32::NEG_INFINITY]),
+        );
+
+        assert_approx_eq(record.fold().item(), &[f32::NEG_INFINITY, 0.]);
+    }
+
+    #[test]
+    fn test_fold_band() {
+        let ln = |v: &[f32]| v.iter().map(|x| x.ln()).collect::<Vec<_>>();
+
+        let record = Record::new(0, 1, Band::new(1, ln(&[0.2, 0.3])));
+        let folded = record.fold(4, f32::NEG_INFINITY);
+
+        assert_approx_eq(folded.item(), &ln(&[0., 0.2, 0.3]));
+    }
+
+    #[test]
+    fn test_band_from_likelihoods() {
+        let fill = f32::NEG_INFINITY;
+
+        assert_eq!(
+            Band::from_likelihoods(&[fill, -1., fill, 0., fill, fill], fill),
+            Band::new(1, vec![-1., fill, 0.])
+        );
+        assert_eq!(
+            Band::from_likelihoods(&[fill, fill, -2.], fill),
+            Band::new(2, vec![-2.])
+        );
+        assert_eq!(
+            Band::from_likelihoods(&[fill, fill], fill),
+            Band::new(0, Vec::new())
+        );
+        assert_eq!(Band::from_likelihoods(&[], fill), Band::new(0, Vec::new()));
+    }
+
+    #[test]
+    fn test_band_from_likelihoods_roundtrip() {
+        let full = vec![0., 0., 0.5, 0.25, 0., 0.25, 0.];
+        let band = Band::from_likelihoods(&full, 0.);
+        assert_eq!(band, Band::new(2, vec![0.5, 0.25, 0., 0.25]));
+        assert_eq!(band.clone().into_full(6, 0.), Likelihoods::from(full));
+        assert_eq!(
+            Band::from_likelihoods(&band.clone().into_full(6, 0.), 0.),
+            band
+        );
+    }
+
+    #[test]
+    fn test_from_band_checked_valid() {
+        for (start, len) in [(0, 0), (0, 3), (1, 2), (2, 1), (3, 0)] {
+            let band = Band::new(start, vec![0.; len]);
+            let record = Record::from_band_checked("chr1", 1, band.clone(), 2).unwrap();
+            assert_eq!(record.item(), &band);
+        }
+    }
+
+    #[test]
+    fn test_from_band_checked_over_range() {
+        assert_eq!(
+            Record::from_band_checked("chr1", 1, Band::new(0, vec![0.; 4]), 2),
+            Err(BandError {
+                start: 0,
+                len: 4,
+                alleles: 2
+            })
+        );
+        assert_eq!(
+            Record::from_band_checked("chr1", 1, Band::new(2, vec![0.; 2]), 2),
+            Err(BandError {
+                start: 2,
+                len: 2,
+                alleles: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_log_base() {
+        let original = vec![0.0, -1.0, -2.5, f32::NEG_INFINITY];
+
+        let mut record = Record::new("chr1", 1, Likelihoods::from(original.clone()));
+        record.convert_log_base(LN, LOG10);
+        let expected = original.iter().map(|v| v * std::f32::consts::LOG10_E);
+        for (v, expected) in record.item().iter().zip(expected) {
+            assert!(v == &expected || (v - expected).abs() < 1e-6);
+        }
+
+        record.convert_log_base(LOG10, LN);
+        for (v, expected) in record.item().iter().zip(original.iter()) {
+            assert!(v == expected || (v - expected).abs() < 1e-6);
+        }
+
+        let mut band = Record::new("chr1", 1, Band::new(1, vec![-1.0, 0.0]));
+        band.convert_log_base(LOG2, LN);
+        assert!((band.item().likelihoods()[0] + std::f32::consts::LN_2).abs() < 1e-6);
+        assert_eq!(band.item().likelihoods()[1], 0.0);
+        assert_eq!(band.item().start(), 1);
+    }
+
+    #[test]
+    fn test_posterior_mean_frequency() {
+        let likelihoods = [0.1f32, 0.2, 0.3, 0.4]
+            .iter()
+            .map(|v| v.ln())
+            .collect::<Vec<_>>();
+        let record = Record::new("chr1", 1, Likelihoods::from(likelihoods));
+
+        // 0 * 0.1 + 1 * 0.2 + 2 * 0.3 + 3 * 0.4
+        assert!((record.posterior_mean_frequency_uniform() - 2.0).abs() < 1e-6);
+        assert!(
+            (record.posterior_mean_frequency_uniform()
+                - record.posterior_mean_frequency(&[0.25; 4]))
+            .abs()
+                < 1e-6
+        );
+
+        // Posterior proportional to [0.1, 0.2, 0.0, 0.0]
+        let prior = [1.0, 1.0, 0.0, 0.0];
+        assert!((record.posterior_mean_frequency(&prior) - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_is_informative() {
+        let flat = Record::new("chr1", 1, Likelihoods::from(vec![-1.0, -1.0, -1.0]));
+        assert!(!flat.is_informative(0.0));
+
+        let peaked = Record::new("chr1", 1, Likelihoods::from(vec![0.0, -2.0, -5.0]));
+        assert!(peaked.is_informative(4.0));
+        assert!(!peaked.is_informative(5.0));
+
+        let band = Record::new("chr1", 1, Band::new(1, vec![-0.5, 0.0]));
+        assert!(band.is_informative(0.1));
+        assert!(!band.is_informative(0.5));
+
+        let empty = Record::new("chr1", 1, Band::new(0, Vec::new()));
+        assert!(!empty.is_informative(0.0));
+    }
+
     #[test]
     fn test_into_full_basic() {
         assert_eq!(
@@ -380,4 +1172,79 @@ mod tests {
             Record::new("2", 2, Likelihoods::from(vec![0., 1., 2.]))
         );
     }
+
+    #[test]
+    fn test_record_view() {
+        let mut record = Record::new(1, 10, Likelihoods::from(vec![0., -1., -2.]));
+
+        let view = record.as_view();
+        assert_eq!(view.contig_id(), 1);
+        assert_eq!(view.position(), 10);
+        assert_eq!(view.likelihoods(), &[0., -1., -2.]);
+        assert_eq!(view.likelihoods().as_ptr(), record.item().as_ptr());
+        assert_eq!(view.to_string(), record.to_string());
+
+        record.item_mut()[1] = -3.;
+        assert_eq!(record.as_view().likelihoods(), &[0., -3., -2.]);
+    }
+
+    #[test]
+    fn test_band_sum_argmax_end() {
+        let band = Band::new(3, vec![0.1, 0.5, 0.3, 0.5]);
+        assert!((band.sum() - 1.4).abs() < 1e-6);
+        assert_eq!(band.argmax(), 4);
+        assert_eq!(band.end(), 7);
+
+        let band = Band::new(2, vec![-5., -1., -3.]);
+        assert_eq!(band.argmax(), 3);
+        assert_eq!(band.end(), 5);
+
+        let band = Band::new(4, Vec::new());
+        assert_eq!(band.sum(), 0.);
+        assert_eq!(band.argmax(), 4);
+        assert_eq!(band.end(), 4);
+    }
+
+    #[test]
+    fn test_write_full_into() {
+        let mut buf = vec![9.; 10];
+
+        for (band, alleles, fill) in [
+            (Band::new(2, vec![1.; 2]), 6, 0.),
+            (Band::new(0, vec![2.; 3]), 4, -1.),
+            (Band::new(2, vec![2.; 3]), 4, -1.),
+            (Band::new(0, vec![0., 1., 2.]), 2, 0.),
+        ] {
+            band.write_full_into(&mut buf, alleles, fill);
+            assert_eq!(buf, band.into_full(alleles, fill).as_ref());
+        }
+    }
+
+    #[test]
+    fn test_likelihoods_to_band() {
+        let likelihoods = Likelihoods::from(vec![0.0, 0.0, 0.2, 0.5, 0.3, 0.0, 0.1]);
+
+        let band = likelihoods.to_band(0.0);
+        assert_eq!(band, Band::new(2, vec![0.2, 0.5, 0.3]));
+        assert_eq!(
+            band.into_full(6, 0.0).as_ref(),
+            &[0.0, 0.0, 0.2, 0.5, 0.3, 0.0, 0.0]
+        );
+
+        assert_eq!(likelihoods.to_band(0.25), Band::new(3, vec![0.5, 0.3]));
+        assert_eq!(likelihoods.to_band(1.0), Band::new(3, vec![0.5]));
+        assert_eq!(likelihoods.to_band(-1.0).into_full(6, 0.0), likelihoods);
+    }
+
+    #[test]
+    fn test_likelihoods_to_band_edges() {
+        let likelihoods = Likelihoods::from(vec![-1.0, -2.0, f32::NEG_INFINITY]);
+        assert_eq!(
+            likelihoods.to_band(f32::NEG_INFINITY),
+            Band::new(0, vec![-1.0, -2.0])
+        );
+
+        let likelihoods = Likelihoods::from(Vec::new());
+        assert_eq!(likelihoods.to_band(0.0), Band::new(0, Vec::new()));
+    }
 }
@@ -0,0 +1,205 @@
+//! Genomic regions.
+
+use std::{error::Error, fmt, io, str::FromStr};
+
+/// A genomic region.
+///
+/// A region consists of a contig name, and an optional range of positions on the contig. Regions
+/// are typically parsed from strings of the form `"chr1"`, `"chr1:1000"`, or `"chr1:1000-2000"`,
+/// where the first covers the whole contig, the second covers all positions from 1000 onwards, and
+/// the third covers positions from 1000 to 2000. Positions are interpreted in the same way as
+/// record positions, that is 0-based, and both ends of the range are inclusive.
+///
+/// See [`Reader::reader_for_region`](crate::Reader::reader_for_region).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Region {
+    name: String,
+    start: Option<u32>,
+    end: Option<u32>,
+}
+
+impl Region {
+    /// Returns whether the region contains the provided position.
+    ///
+    /// Only the range of the region is considered, not the contig.
+    pub fn contains(&self, position: u32) -> bool {
+        self.start.is_none_or(|start| start <= position)
+            && self.end.is_none_or(|end| position <= end)
+    }
+
+    /// Returns the region end, if any.
+    ///
+    /// The end is inclusive. If no end is set, the region extends to the end of the contig.
+    pub fn end(&self) -> Option<u32> {
+        self.end
+    }
+
+    /// Returns the region contig name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the region start, if any.
+    ///
+    /// The start is inclusive. If no start is set, the region starts at the start of the contig.
+    pub fn start(&self) -> Option<u32> {
+        self.start
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => write!(f, ":{start}-{end}"),
+            (Some(start), None) => write!(f, ":{start}"),
+            (None, Some(end)) => write!(f, ":0-{end}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Region {
+    type Err = ParseRegionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, range) = match s.rsplit_once(':') {
+            Some((name, range)) => (name, Some(range)),
+            None => (s, None),
+        };
+
+        if name.is_empty() {
+            return Err(ParseRegionError::EmptyName);
+        }
+
+        let parse_position = |position: &str| {
+            position
+                .parse::<u32>()
+                .map_err(|_| ParseRegionError::InvalidPosition(position.to_owned()))
+        };
+
+        let (start, end) = match range {
+            Some(range) => match range.split_once('-') {
+                Some((start, end)) => (Some(parse_position(start)?), Some(parse_position(end)?)),
+                None => (Some(parse_position(range)?), None),
+            },
+            None => (None, None),
+        };
+
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(ParseRegionError::StartAfterEnd { start, end });
+            }
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            start,
+            end,
+        })
+    }
+}
+
+/// An error associated with parsing a [`Region`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseRegionError {
+    /// The contig name is empty.
+    EmptyName,
+    /// A position could not be parsed.
+    InvalidPosition(String),
+    /// The start of the range is after the end.
+    StartAfterEnd {
+        /// The region start.
+        start: u32,
+        /// The region end.
+        end: u32,
+    },
+}
+
+impl fmt::Display for ParseRegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyName => f.write_str("empty contig name in region"),
+            Self::InvalidPosition(position) => {
+                write!(f, "invalid position '{position}' in region")
+            }
+            Self::StartAfterEnd { start, end } => {
+                write!(f, "region start {start} is after region end {end}")
+            }
+        }
+    }
+}
+
+impl Error for ParseRegionError {}
+
+impl From<ParseRegionError> for io::Error {
+    fn from(error: ParseRegionError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(name: &str, start: Option<u32>, end: Option<u32>) -> Region {
+        Region {
+            name: name.to_owned(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_parse_region() {
+        assert_eq!("chr1".parse(), Ok(region("chr1", None, None)));
+        assert_eq!("chr1:1000".parse(), Ok(region("chr1", Some(1000), None)));
+        assert_eq!(
+            "chr1:1000-2000".parse(),
+            Ok(region("chr1", Some(1000), Some(2000)))
+        );
+        assert_eq!("chr1:5-5".parse(), Ok(region("chr1", Some(5), Some(5))));
+        assert_eq!(
+            "HLA:A:10-20".parse(),
+            Ok(region("HLA:A", Some(10), Some(20)))
+        );
+    }
+
+    #[test]
+    fn test_parse_region_errors() {
+        assert_eq!("".parse::<Region>(), Err(ParseRegionError::EmptyName));
+        assert_eq!(":10".parse::<Region>(), Err(ParseRegionError::EmptyName));
+        assert_eq!(
+            "chr1:".parse::<Region>(),
+            Err(ParseRegionError::InvalidPosition(String::new()))
+        );
+        assert_eq!(
+            "chr1:a-10".parse::<Region>(),
+            Err(ParseRegionError::InvalidPosition("a".to_owned()))
+        );
+        assert_eq!(
+            "chr1:20-10".parse::<Region>(),
+            Err(ParseRegionError::StartAfterEnd { start: 20, end: 10 })
+        );
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for s in ["chr1", "chr1:1000", "chr1:1000-2000"] {
+            assert_eq!(s.parse::<Region>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let region: Region = "chr1:10-20".parse().unwrap();
+        assert!(!region.contains(9));
+        assert!(region.contains(10));
+        assert!(region.contains(20));
+        assert!(!region.contains(21));
+
+        let region: Region = "chr1:10".parse().unwrap();
+        assert!(region.contains(u32::MAX));
+    }
+}
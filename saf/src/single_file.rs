@@ -0,0 +1,179 @@
+//! Reading and writing of single-file SAF.
+//!
+//! A SAF dataset normally consists of separate index, position, and item files. For distribution
+//! of small datasets, it can be more convenient to have these in a single file. The single-file
+//! format concatenates the three files into one BGZF file, which remains readable by tools that
+//! read BGZF or gzip. Each file is stored as a separate section starting at a BGZF block boundary,
+//! where the index is compressed in the same way as the position and item files. A trailer at the
+//! end of the file records the byte offsets of each section.
+//!
+//! Note that the single-file format is not understood by other tools reading SAF files, and that
+//! the whole file is held in memory when reading. See [`Writer`] and [`read`].
+
+use std::io::{self, Read};
+
+use crate::{
+    version::{Version, MAGIC_LEN},
+    writer::BGZF_EOF,
+    Index, Reader,
+};
+
+/// The magic number starting the single-file trailer.
+const TRAILER_MAGIC: [u8; MAGIC_LEN] = *b"safsngl\0";
+
+/// The uncompressed length of the single-file trailer.
+const TRAILER_LEN: usize = MAGIC_LEN + 3 * 8;
+
+/// A single-file SAF writer.
+///
+/// Records are written using an inner [`crate::Writer`], where the index, positions, and items
+/// are kept in memory until the single file is written on [`Self::finish`].
+pub struct Writer<W, V> {
+    inner: crate::Writer<Vec<u8>, V>,
+    writer: W,
+}
+
+impl<W, V> Writer<W, V>
+where
+    W: io::Write,
+    V: Version,
+{
+    /// Finishes writing the single file, returning the inner writer.
+    pub fn finish(self) -> io::Result<W> {
+        let Self { inner, mut writer } = self;
+
+        let (index, positions, items) = inner.finish()?;
+
+        let mut index_writer = bgzf::Writer::new(Vec::new());
+        io::Write::write_all(&mut index_writer, &index)?;
+        let index = index_writer.finish()?;
+
+        let [index, positions, items] = [&index, &positions, &items].map(|x| strip_bgzf_eof(x));
+
+        let position_offset = index.len() as u64;
+        let item_offset = position_offset + positions.len() as u64;
+        let trailer_offset = item_offset + items.len() as u64;
+
+        writer.write_all(index)?;
+        writer.write_all(positions)?;
+        writer.write_all(items)?;
+
+        let mut trailer_writer = bgzf::Writer::new(Vec::new());
+        io::Write::write_all(&mut trailer_writer, &TRAILER_MAGIC)?;
+        for offset in [position_offset, item_offset, trailer_offset] {
+            io::Write::write_all(&mut trailer_writer, &offset.to_le_bytes())?;
+        }
+        writer.write_all(&trailer_writer.finish()?)?;
+
+        Ok(writer)
+    }
+
+    /// Returns the inner SAF writer.
+    pub fn get_ref(&self) -> &crate::Writer<Vec<u8>, V> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner SAF writer.
+    ///
+    /// This may be used to configure the inner writer, or to write sites in other ways than
+    /// [`Self::write_record`].
+    pub fn get_mut(&mut self) -> &mut crate::Writer<Vec<u8>, V> {
+        &mut self.inner
+    }
+
+    /// Creates a new single-file writer.
+    ///
+    /// The magic numbers and the provided number of alleles are written to the inner writer.
+    pub fn new(writer: W, alleles: usize) -> io::Result<Self> {
+        let mut inner = crate::Writer::new(Vec::new(), Vec::new(), Vec::new());
+        inner.write_magic()?;
+        inner.write_alleles(alleles)?;
+
+        Ok(Self { inner, writer })
+    }
+
+    /// Writes a single record.
+    pub fn write_record<I>(&mut self, record: &crate::Record<I, V::Item>) -> io::Result<()>
+    where
+        I: AsRef<str>,
+    {
+        self.inner.write_record(record)
+    }
+}
+
+/// Reads a single-file SAF.
+///
+/// The whole file is read into memory, and a reader is created over its position and item
+/// sections. The magic numbers will be read, and so [`Reader::read_magic`] should *not* be called
+/// manually.
+///
+/// Returns an error if the file does not end with a valid single-file trailer.
+pub fn read<R, V>(mut reader: R) -> io::Result<Reader<io::Cursor<Vec<u8>>, V>>
+where
+    R: io::Read,
+    V: Version,
+{
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let [index_offset, position_offset, item_offset, trailer_offset] = read_trailer(&bytes)?;
+
+    let index_bytes = &bytes[index_offset..position_offset];
+    let mut index_reader = bgzf::Reader::new(index_bytes);
+    let index = Index::read(&mut index_reader)?;
+
+    let section_reader =
+        |start: usize, end: usize| bgzf::Reader::new(io::Cursor::new(bytes[start..end].to_vec()));
+
+    let mut new = Reader::from_bgzf(
+        index,
+        section_reader(position_offset, item_offset),
+        section_reader(item_offset, trailer_offset),
+    )
+    .ok_or_else(|| single_file_err("empty index in single-file SAF"))?;
+    new.read_magic()?;
+
+    Ok(new)
+}
+
+/// Reads the section offsets from the trailer of a single-file SAF.
+///
+/// Returns the offsets of the index, position, item, and trailer sections, in that order.
+fn read_trailer(bytes: &[u8]) -> io::Result<[usize; 4]> {
+    let mut uncompressed = Vec::new();
+    bgzf::Reader::new(bytes).read_to_end(&mut uncompressed)?;
+
+    let trailer = uncompressed
+        .len()
+        .checked_sub(TRAILER_LEN)
+        .map(|start| &uncompressed[start..])
+        .filter(|trailer| trailer[..MAGIC_LEN] == TRAILER_MAGIC)
+        .ok_or_else(|| single_file_err("missing or invalid single-file SAF trailer"))?;
+
+    let mut offsets = [0; 4];
+    for (offset, chunk) in offsets[1..]
+        .iter_mut()
+        .zip(trailer[MAGIC_LEN..].chunks_exact(8))
+    {
+        let value = u64::from_le_bytes(chunk.try_into().expect("chunk of 8 bytes"));
+        *offset = usize::try_from(value)
+            .map_err(|_| single_file_err("invalid single-file SAF section offset"))?;
+    }
+
+    if offsets.windows(2).all(|w| w[0] <= w[1]) && offsets[3] <= bytes.len() {
+        Ok(offsets)
+    } else {
+        Err(single_file_err("invalid single-file SAF section offsets"))
+    }
+}
+
+/// Returns the BGZF data with the end-of-file marker block removed, if present.
+///
+/// This allows concatenating BGZF data without readers stopping at the marker.
+fn strip_bgzf_eof(data: &[u8]) -> &[u8] {
+    data.strip_suffix(&BGZF_EOF).unwrap_or(data)
+}
+
+fn single_file_err(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
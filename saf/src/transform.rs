@@ -0,0 +1,39 @@
+use std::io;
+
+use crate::{
+    record::{Id, Likelihoods, Record},
+    ReaderV3, WriterV3,
+};
+
+/// Streams all records from a reader through a transform into a writer.
+///
+/// Each record read is passed to `f`, which may mutate it, before being written. This allows
+/// applying a correction or other transformation to the likelihoods of each site in a single
+/// streaming pass. The contig ID of each record is mapped to its name in the index of the reader
+/// for writing, and the index of the writer is built as records are written.
+///
+/// Reading continues from the current location of the reader until the end. Note that the writer
+/// is not finished, so that further records may be written: use [`Writer::finish`] when done.
+///
+/// [`Writer::finish`]: crate::Writer::finish
+pub fn transform<R, W, F>(
+    reader: &mut ReaderV3<R>,
+    writer: &mut WriterV3<W>,
+    mut f: F,
+) -> io::Result<()>
+where
+    R: io::BufRead,
+    W: io::Write,
+    F: FnMut(&mut Record<Id, Likelihoods>),
+{
+    let mut record = reader.create_record_buf();
+
+    while reader.read_record(&mut record)?.is_not_done() {
+        f(&mut record);
+
+        let contig = reader.index().records()[*record.contig_id()].name();
+        writer.write_site(contig, record.position(), record.item())?;
+    }
+
+    Ok(())
+}
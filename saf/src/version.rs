@@ -15,7 +15,7 @@ use std::{io, mem};
 
 use byteorder::{ReadBytesExt, LE};
 
-use crate::ReadStatus;
+use crate::{Error, ReadStatus};
 
 use super::{
     index::{self, Index, IndexReaderExt, IndexWriterExt},
@@ -24,7 +24,8 @@ use super::{
     writer::{Writer, WriterExt},
 };
 
-const MAGIC_LEN: usize = 8;
+/// The number of bytes in a SAF magic number.
+pub const MAGIC_LEN: usize = 8;
 
 /// A type that describes a SAF file version.
 ///
@@ -40,11 +41,45 @@ pub trait Version: Sized {
     /// The items contained in the SAF item file for this version.
     type Item;
 
+    /// Returns the name used for this version internally in ANGSD.
+    ///
+    /// See the [module-level documentation](self) for a note on naming of versions.
+    fn angsd_name() -> &'static str;
+
+    /// Returns the number of bytes used to store a single item in the uncompressed SAF item file.
+    ///
+    /// Returns `None` if items are variable-width for this version.
+    fn item_bytes(alleles: usize) -> Option<usize>;
+
+    /// Returns the number of bytes used to store all items of a contig in the uncompressed SAF item
+    /// file, given the index record of the contig and its number of alleles.
+    fn contig_item_bytes(record: &index::Record<Self>, alleles: usize) -> usize;
+
     /// Creates a SAF record buffer suitable for reading from a reader for this version.
     fn create_record_buf(index: &Index<Self>) -> Record<Id, Self::Item>;
 
+    /// Resizes an item buffer to hold an item with the provided number of alleles.
+    ///
+    /// This is a no-op for versions where items are resized during reading.
+    fn resize_item(buf: &mut Self::Item, alleles: usize);
+
     /// Reads the SAF index record for this version from a reader.
     fn read_index_record<R>(reader: &mut R) -> io::Result<index::Record<Self>>
+    where
+        R: io::BufRead,
+    {
+        let name = reader.read_contig_name()?;
+
+        Self::read_index_record_with_name(reader, name)
+    }
+
+    /// Reads the SAF index record for this version from a reader, using an already read name.
+    ///
+    /// The stream is assumed to be positioned immediately after the contig name of the record.
+    fn read_index_record_with_name<R>(
+        reader: &mut R,
+        name: String,
+    ) -> io::Result<index::Record<Self>>
     where
         R: io::BufRead;
 
@@ -82,12 +117,14 @@ pub trait Version: Sized {
         W: io::Write;
 
     /// Writes a single record to a writer.
-    fn write_record<W, I>(
-        writer: &mut Writer<W, Self>,
+    fn write_record<W, WP, WI, I>(
+        writer: &mut Writer<W, Self, WP, WI>,
         record: &Record<I, Self::Item>,
     ) -> io::Result<()>
     where
         W: io::Write,
+        WP: io::Write,
+        WI: io::Write,
         I: AsRef<str>;
 
     /// Reads the SAF version magic number from a reader.
@@ -98,17 +135,21 @@ pub trait Version: Sized {
         let mut magic = [0; MAGIC_LEN];
         reader.read_exact(&mut magic)?;
 
-        if magic == Self::MAGIC_NUMBER {
+        Self::check_magic(&magic)
+    }
+
+    /// Checks that a magic number read from a SAF file matches the version magic number.
+    fn check_magic(magic: &[u8; MAGIC_LEN]) -> io::Result<()> {
+        if *magic == Self::MAGIC_NUMBER {
             Ok(())
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "invalid or unsupported SAF magic number \
-                    (found '{magic:02x?}', expected '{:02x?}')",
-                    Self::MAGIC_NUMBER
-                ),
-            ))
+            Err(Error::Magic {
+                found: *magic,
+                expected: Self::MAGIC_NUMBER,
+                version: Self::VERSION,
+                angsd_name: Self::angsd_name(),
+            }
+            .into())
         }
     }
 
@@ -137,16 +178,36 @@ impl Version for V3 {
 
     type Item = Likelihoods;
 
+    fn angsd_name() -> &'static str {
+        "version 1"
+    }
+
+    fn item_bytes(alleles: usize) -> Option<usize> {
+        Some((alleles + 1) * mem::size_of::<f32>())
+    }
+
+    fn contig_item_bytes(record: &index::Record<Self>, alleles: usize) -> usize {
+        record.sites() * (alleles + 1) * mem::size_of::<f32>()
+    }
+
     fn create_record_buf(index: &Index<Self>) -> Record<Id, Self::Item> {
         // Record likelihoods must be set up to be correct size from beginning
-        Record::from_alleles(0, 1, index.alleles())
+        Record::from_alleles(0, 1, index.contig_alleles(0))
     }
 
-    fn read_index_record<R>(reader: &mut R) -> io::Result<index::Record<Self>>
+    fn resize_item(buf: &mut Self::Item, alleles: usize) {
+        if buf.len() != alleles + 1 {
+            *buf = Likelihoods::from(vec![0.0; alleles + 1]);
+        }
+    }
+
+    fn read_index_record_with_name<R>(
+        reader: &mut R,
+        name: String,
+    ) -> io::Result<index::Record<Self>>
     where
         R: io::BufRead,
     {
-        let name = reader.read_contig_name()?;
         let sites = reader.read_sites()?;
         let position_offset = reader.read_position_offset()?;
         let item_offset = reader.read_item_offset()?;
@@ -183,45 +244,21 @@ impl Version for V3 {
         writer.write_likelihoods(item)
     }
 
-    fn write_record<W, I>(
-        writer: &mut Writer<W, Self>,
+    fn write_record<W, WP, WI, I>(
+        writer: &mut Writer<W, Self, WP, WI>,
         record: &Record<I, Self::Item>,
     ) -> io::Result<()>
     where
         W: io::Write,
+        WP: io::Write,
+        WI: io::Write,
         I: AsRef<str>,
     {
-        let contig_id = record.contig_id().as_ref();
-
-        if let Some(index_record) = writer.index_record.as_mut() {
-            if index_record.name() == contig_id {
-                // We're on the same contig, so we can simply update index record
-                *index_record.sites_mut() += 1;
-            } else {
-                // We're on a new contig, which means we have to write the current record index
-                // and set up a new one
-                let position_offset = u64::from(writer.position_writer.virtual_position());
-                let item_offset = u64::from(writer.item_writer.virtual_position());
-
-                let new =
-                    index::Record::new(contig_id.to_string(), 1, position_offset, item_offset);
-
-                let old = mem::replace(index_record, new);
-                old.write(&mut writer.index_writer)?;
-            }
-        } else {
-            let offset = Self::MAGIC_NUMBER.len() as u64;
-            let index_record = index::Record::new(contig_id.to_string(), 0, offset, offset);
-            writer.index_record = Some(index_record);
-
-            return Self::write_record(writer, record);
-        }
-
-        // Write record
-        writer.position_writer.write_position(record.position())?;
-        Self::write_item(&mut writer.item_writer, record.item())?;
-
-        Ok(())
+        writer.write_site_unchecked(
+            record.contig_id().as_ref(),
+            u64::from(record.position()),
+            record.item(),
+        )
     }
 }
 
@@ -241,16 +278,36 @@ impl Version for V4 {
 
     type Item = Band;
 
+    fn angsd_name() -> &'static str {
+        "version 2"
+    }
+
+    fn item_bytes(_alleles: usize) -> Option<usize> {
+        // Band lengths vary between items
+        None
+    }
+
+    fn contig_item_bytes(record: &index::Record<Self>, _alleles: usize) -> usize {
+        // Each item stores band start and length, followed by the band likelihoods
+        record.sites() * 2 * mem::size_of::<u32>() + record.sum_band() * mem::size_of::<f32>()
+    }
+
     fn create_record_buf(_index: &Index<Self>) -> Record<Id, Self::Item> {
         // Band is resized during reading, so we can simplify initialise empty band
         Record::new(0, 1, Band::new(0, Vec::new()))
     }
 
-    fn read_index_record<R>(reader: &mut R) -> io::Result<index::Record<Self>>
+    fn resize_item(_buf: &mut Self::Item, _alleles: usize) {
+        // Band is resized during reading
+    }
+
+    fn read_index_record_with_name<R>(
+        reader: &mut R,
+        name: String,
+    ) -> io::Result<index::Record<Self>>
     where
         R: io::BufRead,
     {
-        let name = reader.read_contig_name()?;
         let sites = reader.read_sites()?;
         let sum_band = reader.read_sum_band()?;
         let position_offset = reader.read_position_offset()?;
@@ -308,51 +365,87 @@ impl Version for V4 {
         writer.write_band(item)
     }
 
-    fn write_record<W, I>(
-        writer: &mut Writer<W, Self>,
+    fn write_record<W, WP, WI, I>(
+        writer: &mut Writer<W, Self, WP, WI>,
         record: &Record<I, Self::Item>,
     ) -> io::Result<()>
     where
         W: io::Write,
+        WP: io::Write,
+        WI: io::Write,
         I: AsRef<str>,
     {
-        let contig_id = record.contig_id().as_ref();
-
-        if let Some(index_record) = writer.index_record.as_mut() {
-            if index_record.name() == contig_id {
-                // We're on the same contig, so we can simply update index record
-                *index_record.sum_band_mut() += record.item().likelihoods().len();
-                *index_record.sites_mut() += 1;
-            } else {
-                // We're on a new contig, which means we have to write the current record index
-                // and set up a new one
-                let position_offset = u64::from(writer.position_writer.virtual_position());
-                let item_offset = u64::from(writer.item_writer.virtual_position());
-
-                let new = index::Record::new_with_sum_band(
-                    contig_id.to_string(),
-                    1,
-                    0,
-                    position_offset,
-                    item_offset,
-                );
-
-                let old = mem::replace(index_record, new);
-                old.write(&mut writer.index_writer)?;
-            }
-        } else {
-            let offset = Self::MAGIC_NUMBER.len() as u64;
-            let index_record =
-                index::Record::new_with_sum_band(contig_id.to_string(), 0, 0, offset, offset);
-            writer.index_record = Some(index_record);
+        writer.write_band_unchecked(
+            record.contig_id().as_ref(),
+            u64::from(record.position()),
+            record.item(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            return Self::write_record(writer, record);
+    #[test]
+    fn test_magic_error_contains_both_names() {
+        let error = V4::read_magic(&mut &V3::MAGIC_NUMBER[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let msg = error.to_string();
+        assert!(msg.contains("safv4"));
+        assert!(msg.contains("version 2"));
+    }
+
+    #[test]
+    fn test_magic_error_structured() {
+        let error = V4::read_magic(&mut &V3::MAGIC_NUMBER[..]).unwrap_err();
+
+        match Error::from(error) {
+            Error::Magic {
+                found,
+                expected,
+                version,
+                ..
+            } => {
+                assert_eq!(found, V3::MAGIC_NUMBER);
+                assert_eq!(expected, V4::MAGIC_NUMBER);
+                assert_eq!(version, 4);
+            }
+            error => panic!("unexpected error {error:?}"),
         }
+    }
+
+    #[test]
+    fn test_v4_write_item_bytes() -> io::Result<()> {
+        let band = Band::new(2, vec![0., -1.]);
+
+        let mut bytes = Vec::new();
+        V4::write_item(&mut bytes, &band)?;
+
+        let mut expected = Vec::new();
+        expected.extend(2u32.to_le_bytes());
+        expected.extend(2u32.to_le_bytes());
+        expected.extend(0f32.to_le_bytes());
+        expected.extend((-1f32).to_le_bytes());
+        assert_eq!(bytes, expected);
 
-        // Write record
-        writer.position_writer.write_position(record.position())?;
-        Self::write_item(&mut writer.item_writer, record.item())?;
+        let mut read = Band::new(0, Vec::new());
+        assert!(V4::read_item(&mut &bytes[..], &mut read)?.is_not_done());
+        assert_eq!(read, band);
+
+        let mut rewritten = Vec::new();
+        V4::write_item(&mut rewritten, &read)?;
+        assert_eq!(rewritten, bytes);
 
         Ok(())
     }
+
+    #[test]
+    fn test_item_bytes() {
+        assert_eq!(V3::item_bytes(0), Some(4));
+        assert_eq!(V3::item_bytes(4), Some(20));
+        assert_eq!(V4::item_bytes(0), None);
+        assert_eq!(V4::item_bytes(4), None);
+    }
 }
@@ -0,0 +1,67 @@
+use std::{cmp::Ordering, collections::HashSet, io};
+
+use crate::{
+    record::{Likelihoods, Record},
+    WriterV3,
+};
+
+/// Sorts records using a comparator and writes them to a writer.
+///
+/// All records are buffered and sorted using `cmp` before being written, which is a convenience
+/// for producers with records in arbitrary order. After sorting, the records must be grouped by
+/// contig, and sorted by position within each contig, as required by the writer. Contigs are
+/// written in the order given by `cmp`.
+///
+/// Returns an error without writing any records if the sorted records are not grouped by contig
+/// and sorted by position. Note that the writer is not finished, so that further records may be
+/// written: use [`Writer::finish`] when done.
+///
+/// [`Writer::finish`]: crate::Writer::finish
+pub fn write_sorted<I, W, F>(records: I, writer: &mut WriterV3<W>, cmp: F) -> io::Result<()>
+where
+    I: IntoIterator<Item = Record<String, Likelihoods>>,
+    W: io::Write,
+    F: Fn(&Record<String, Likelihoods>, &Record<String, Likelihoods>) -> Ordering,
+{
+    let mut records = records.into_iter().collect::<Vec<_>>();
+    records.sort_by(cmp);
+
+    check_sorted(&records)?;
+
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that records are grouped by contig and sorted by position within each contig.
+fn check_sorted(records: &[Record<String, Likelihoods>]) -> io::Result<()> {
+    let mut finished_contigs = HashSet::new();
+
+    for pair in records.windows(2) {
+        let (previous, next) = (&pair[0], &pair[1]);
+
+        if previous.contig_id() != next.contig_id() {
+            finished_contigs.insert(previous.contig_id());
+
+            if finished_contigs.contains(next.contig_id()) {
+                return Err(unsorted_err(format!(
+                    "records on contig '{}' not grouped after sorting",
+                    next.contig_id()
+                )));
+            }
+        } else if previous.position() > next.position() {
+            return Err(unsorted_err(format!(
+                "records on contig '{}' not sorted by position after sorting",
+                next.contig_id()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn unsorted_err(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
@@ -1,14 +1,38 @@
 //! Writing of the SAF format.
 
-use std::{fs, io, path::Path};
+use std::{
+    collections::HashMap,
+    error, fmt, fs,
+    io::{self, Read, Seek},
+    mem,
+    path::Path,
+};
 
 use super::{
     ext::{member_paths_from_prefix, prefix_from_member_path},
-    index,
-    record::Record,
+    index::{self, IndexWriterExt, PositionBase, PositionWidth},
+    reader::ReaderExt,
+    record::{Band, Likelihoods, Record, Record64},
     version::{Version, V3, V4},
 };
 
+pub use bgzf::writer::CompressionLevel;
+
+/// The BGZF end-of-file marker block.
+pub(crate) const BGZF_EOF: [u8; 28] = [
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+mod builder;
+pub use builder::Builder;
+
+mod sfs;
+pub use sfs::SfsAccumulatingWriter;
+
+mod sparse;
+pub use sparse::SparseWriter;
+
 mod traits;
 pub(crate) use traits::WriterExt;
 
@@ -22,27 +46,79 @@ pub type WriterV4<R> = Writer<R, V4>;
 ///
 /// The writer is generic over the inner writer type and over the SAF [`Version`] being read.
 /// Version-specific aliases [`WriterV3`] and [`WriterV4`] are provided for convenience.
-pub struct Writer<W, V>
+///
+/// By default, the index, position, and item writers share the same inner writer type. Where this
+/// is not the case, the inner position and item writer types can be set separately, see
+/// [`Self::new_split`].
+pub struct Writer<W, V, WP = W, WI = W>
 where
     W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
 {
     pub(crate) index_writer: W,
-    pub(crate) position_writer: bgzf::Writer<W>,
-    pub(crate) item_writer: bgzf::Writer<W>,
+    pub(crate) position_writer: bgzf::Writer<WP>,
+    pub(crate) item_writer: bgzf::Writer<WI>,
     pub(crate) index_record: Option<index::Record<V>>,
+    position_offset_base: u64,
+    item_offset_base: u64,
+    sites: usize,
+    site_checksum: bool,
+    dedup_positions: bool,
+    validate_positions: bool,
+    last_position: Option<u64>,
+    duplicates_dropped: usize,
+    position_width: PositionWidth,
+    position_base: PositionBase,
+    block_per_contig: bool,
+    alleles_written: bool,
+    contig_alleles: HashMap<String, usize>,
 }
 
 impl<W, V> Writer<W, V>
 where
     W: io::Write,
     V: Version,
+{
+    /// Creates a new writer.
+    ///
+    /// The provided writers will be wrapped in [`bgzf::Writer`]s. To create a writer from existing
+    /// BGZF writers, see [`Self::from_bgzf`].
+    pub fn new(index_writer: W, position_writer: W, item_writer: W) -> Self {
+        Self::new_split(index_writer, position_writer, item_writer)
+    }
+}
+
+impl<W, V, WP, WI> Writer<W, V, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+    V: Version,
 {
     /// Finishes writing.
-    pub fn finish(mut self) -> io::Result<(W, W, W)> {
+    ///
+    /// If enabled, the site checksum is written to the end of the index here. See
+    /// [`Self::set_site_checksum`]. Likewise for a non-default position width and base, see
+    /// [`Self::set_position_width`] and [`Self::set_position_base`].
+    pub fn finish(mut self) -> io::Result<(W, WP, WI)> {
         if let Some(record) = self.index_record {
             record.write(&mut self.index_writer)?;
         }
 
+        if self.position_width != PositionWidth::U32 {
+            self.index_writer
+                .write_position_width(self.position_width)?;
+        }
+
+        if self.position_base != PositionBase::Zero {
+            self.index_writer.write_position_base(self.position_base)?;
+        }
+
+        if self.site_checksum {
+            self.index_writer.write_site_checksum(self.sites)?;
+        }
+
         Ok((
             self.index_writer,
             self.position_writer.finish()?,
@@ -53,17 +129,47 @@ where
     /// Creates a new writer from existing BGZF writers.
     pub fn from_bgzf(
         index_writer: W,
-        position_writer: bgzf::Writer<W>,
-        item_writer: bgzf::Writer<W>,
+        position_writer: bgzf::Writer<WP>,
+        item_writer: bgzf::Writer<WI>,
     ) -> Self {
         Self {
             index_writer,
             position_writer,
             item_writer,
             index_record: None,
+            position_offset_base: 0,
+            item_offset_base: 0,
+            sites: 0,
+            site_checksum: false,
+            dedup_positions: false,
+            validate_positions: false,
+            last_position: None,
+            duplicates_dropped: 0,
+            position_width: PositionWidth::U32,
+            position_base: PositionBase::Zero,
+            block_per_contig: false,
+            alleles_written: false,
+            contig_alleles: HashMap::new(),
         }
     }
 
+    /// Returns the number of duplicate records dropped so far.
+    ///
+    /// This is always zero unless position deduplication is enabled, see
+    /// [`Self::set_dedup_positions`].
+    pub fn duplicates_dropped(&self) -> usize {
+        self.duplicates_dropped
+    }
+
+    /// Ends the current BGZF blocks of the position and item writers.
+    ///
+    /// Data written after this will start in new blocks in both the position and item files. If
+    /// no data has been written since the last block ended, this is a no-op.
+    pub fn flush_block(&mut self) -> io::Result<()> {
+        io::Write::flush(&mut self.position_writer)?;
+        io::Write::flush(&mut self.item_writer)
+    }
+
     /// Returns the index writer.
     pub fn index_writer(&self) -> &W {
         &self.index_writer
@@ -75,25 +181,26 @@ where
     }
 
     /// Returns the inner index, position writer, and item writer, consuming `self`.
-    pub fn into_parts(self) -> (W, bgzf::Writer<W>, bgzf::Writer<W>) {
+    pub fn into_parts(self) -> (W, bgzf::Writer<WP>, bgzf::Writer<WI>) {
         (self.index_writer, self.position_writer, self.item_writer)
     }
 
     /// Returns the inner item writer.
-    pub fn item_writer(&self) -> &bgzf::Writer<W> {
+    pub fn item_writer(&self) -> &bgzf::Writer<WI> {
         &self.item_writer
     }
 
     /// Returns a mutable reference to the inner item writer.
-    pub fn item_writer_mut(&mut self) -> &mut bgzf::Writer<W> {
+    pub fn item_writer_mut(&mut self) -> &mut bgzf::Writer<WI> {
         &mut self.item_writer
     }
 
-    /// Creates a new writer.
+    /// Creates a new writer with separate types for each of the inner writers.
     ///
-    /// The provided writers will be wrapped in [`bgzf::Writer`]s. To create a writer from existing
-    /// BGZF writers, see [`Self::from_bgzf`].
-    pub fn new(index_writer: W, position_writer: W, item_writer: W) -> Self {
+    /// This allows, for instance, writing positions and items to non-seekable streams while
+    /// buffering the index in memory. The position and item writers will be wrapped in
+    /// [`bgzf::Writer`]s. Where all inner writers have the same type, see [`Self::new`].
+    pub fn new_split(index_writer: W, position_writer: WP, item_writer: WI) -> Self {
         Self::from_bgzf(
             index_writer,
             bgzf::Writer::new(position_writer),
@@ -102,20 +209,144 @@ where
     }
 
     /// Returns the inner position writer.
-    pub fn position_writer(&self) -> &bgzf::Writer<W> {
+    pub fn position_writer(&self) -> &bgzf::Writer<WP> {
         &self.position_writer
     }
 
     /// Returns a mutable reference to the inner position writer.
-    pub fn position_writer_mut(&mut self) -> &mut bgzf::Writer<W> {
+    pub fn position_writer_mut(&mut self) -> &mut bgzf::Writer<WP> {
         &mut self.position_writer
     }
 
+    /// Sets whether to start each contig in a new BGZF block.
+    ///
+    /// If enabled, [`Self::flush_block`] is called before writing the first record of each contig.
+    /// This guarantees that each contig starts at a block boundary in both the position and item
+    /// files, so that the compressed data for a contig can be extracted or copied without
+    /// decompression. This comes at the cost of slightly worse compression, particularly for
+    /// files with many small contigs.
+    pub fn set_block_per_contig(&mut self, block_per_contig: bool) {
+        self.block_per_contig = block_per_contig;
+    }
+
+    /// Sets the number of alleles for the contig with the provided name.
+    ///
+    /// This overrides the number of alleles written with [`Self::write_alleles`] for the contig,
+    /// and is written to the index record of the contig. It may be set before or while writing
+    /// records for the contig. See [`Record::alleles`] for details.
+    ///
+    /// [`Record::alleles`]: index::Record::alleles
+    pub fn set_contig_alleles(&mut self, contig: &str, alleles: usize) {
+        match self
+            .index_record
+            .as_mut()
+            .filter(|record| record.name() == contig)
+        {
+            Some(record) => *record.alleles_mut() = Some(alleles),
+            None => {
+                self.contig_alleles.insert(contig.to_string(), alleles);
+            }
+        }
+    }
+
+    /// Sets whether to drop consecutive records with duplicate positions.
+    ///
+    /// If enabled, a record is dropped if its contig and position are equal to those of the
+    /// immediately preceding record written. Only such consecutive duplicates are dropped: records
+    /// are never merged, and non-consecutive duplicates are written as usual. The number of records
+    /// dropped can be retrieved using [`Self::duplicates_dropped`].
+    pub fn set_dedup_positions(&mut self, dedup_positions: bool) {
+        self.dedup_positions = dedup_positions;
+    }
+
+    /// Sets whether to validate that positions are strictly increasing within contigs.
+    ///
+    /// If enabled, writing a record with a position less than or equal to that of the preceding
+    /// record written on the same contig returns an error with [`io::ErrorKind::InvalidInput`],
+    /// and the record is not written. Readers assume sorted positions, for instance when
+    /// intersecting, so this guards against writing files that will be silently misread. Note
+    /// that if position deduplication is also enabled, consecutive duplicates are dropped rather
+    /// than rejected, see [`Self::set_dedup_positions`].
+    pub fn set_validate_positions(&mut self, validate_positions: bool) {
+        self.validate_positions = validate_positions;
+    }
+
+    /// Sets the base of positions in the position file.
+    ///
+    /// Positions of records written are always taken to be 0-based. By default, they are also
+    /// stored 0-based. If set to [`PositionBase::One`], positions are converted to 1-based when
+    /// written, and the base is recorded in the index on [`Self::finish`], so that readers convert
+    /// them back. See [`Index::position_base`] for details.
+    ///
+    /// [`Index::position_base`]: crate::Index::position_base
+    ///
+    /// # Panics
+    ///
+    /// Panics if any records have already been written.
+    pub fn set_position_base(&mut self, position_base: PositionBase) {
+        assert!(
+            self.index_record.is_none(),
+            "cannot set position base after writing records"
+        );
+
+        self.position_base = position_base;
+    }
+
+    /// Sets the width of positions in the position file.
+    ///
+    /// By default, positions are written as 32-bit integers, and writing a position that does not
+    /// fit returns an error. If set to [`PositionWidth::U64`], all positions are written as 64-bit
+    /// integers, and the width is recorded in the index on [`Self::finish`]. Records with such
+    /// positions can be written using `write_record64`. See [`Index::position_width`] for details.
+    ///
+    /// [`Index::position_width`]: crate::Index::position_width
+    ///
+    /// # Panics
+    ///
+    /// Panics if any records have already been written.
+    pub fn set_position_width(&mut self, position_width: PositionWidth) {
+        assert!(
+            self.index_record.is_none(),
+            "cannot set position width after writing records"
+        );
+
+        self.position_width = position_width;
+    }
+
+    /// Checks that the writer has been used such that the output will be readable.
+    ///
+    /// This verifies that the number of alleles has been written, and that at least one record has
+    /// been written. Since [`Self::finish`] will succeed regardless, this may be used to fail
+    /// early on improper use of the writer, before finishing.
+    pub fn self_check(&self) -> Result<(), WriterStateError> {
+        if !self.alleles_written {
+            Err(WriterStateError::MissingAlleles)
+        } else if self.index_record.is_none() {
+            Err(WriterStateError::NoRecords)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets whether to write a site checksum to the index.
+    ///
+    /// If enabled, the total number of sites written is appended to the index on
+    /// [`Self::finish`]. When reading, this is checked against the number of sites read, as a
+    /// cheap integrity check. Note that the site checksum is an extension to the SAF index format,
+    /// and so the resulting index cannot be read by other tools. See also [`Index::site_checksum`].
+    ///
+    /// [`Index::site_checksum`]: crate::Index::site_checksum
+    pub fn set_site_checksum(&mut self, site_checksum: bool) {
+        self.site_checksum = site_checksum;
+    }
+
     /// Writes the number alleles to the index writer.
     ///
     /// The number of alleles should be written immediately after the magic number.
     pub fn write_alleles(&mut self, alleles: usize) -> io::Result<()> {
-        self.index_writer.write_all(&alleles.to_le_bytes())
+        self.index_writer.write_all(&alleles.to_le_bytes())?;
+        self.alleles_written = true;
+        Ok(())
     }
 
     /// Writes the magic numbers.
@@ -127,12 +358,309 @@ where
             .and_then(|_| V::write_magic(&mut self.item_writer))
     }
 
+    /// Converts the writer into a writer accumulating an SFS from the records written.
+    ///
+    /// See [`SfsAccumulatingWriter`] for details.
+    pub fn into_sfs_accumulating(self, alleles: usize) -> SfsAccumulatingWriter<W, V, WP, WI> {
+        SfsAccumulatingWriter::new(self, alleles)
+    }
+
+    /// Converts the writer into a writer for records with contigs in arbitrary order.
+    ///
+    /// See [`SparseWriter`] for details.
+    pub fn into_sparse(self) -> SparseWriter<W, V, WP, WI> {
+        SparseWriter::new(self)
+    }
+
     /// Writes a single record.
     pub fn write_record<I>(&mut self, record: &Record<I, V::Item>) -> io::Result<()>
     where
         I: AsRef<str>,
     {
-        V::write_record(self, record)
+        self.write_counted(
+            record.contig_id().as_ref(),
+            u64::from(record.position()),
+            |writer| V::write_record(writer, record),
+        )
+    }
+
+    /// Returns the stored position corresponding to a 0-based position, using the position width
+    /// and base of the writer.
+    ///
+    /// Returns an error if the stored position overflows, or if it does not fit in the position
+    /// width. This should be checked before updating the index, so that a failed write leaves the
+    /// writer unchanged.
+    fn stored_position(&self, position: u64) -> io::Result<u64> {
+        let position = self.position_base.to_stored(position)?;
+
+        if self.position_width == PositionWidth::U32 && u32::try_from(position).is_err() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("position {position} does not fit in 32-bit position width"),
+            ));
+        }
+
+        Ok(position)
+    }
+
+    /// Writes a single stored position to the position writer, using the position width of the
+    /// writer.
+    ///
+    /// The position should be obtained from [`Self::stored_position`].
+    fn write_stored_position(&mut self, position: u64) -> io::Result<()> {
+        match self.position_width {
+            PositionWidth::U32 => self.position_writer.write_position(position as u32),
+            PositionWidth::U64 => self.position_writer.write_position_u64(position),
+        }
+    }
+
+    /// Writes a single site using the provided function, unless it is dropped as a duplicate,
+    /// and updates the site count of the writer.
+    fn write_counted<F>(&mut self, contig: &str, position: u64, write: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut Self) -> io::Result<()>,
+    {
+        if self.skip_duplicate(contig, position) {
+            return Ok(());
+        }
+
+        let is_new_contig = self
+            .index_record
+            .as_ref()
+            .is_none_or(|record| record.name() != contig);
+
+        if self.validate_positions && !is_new_contig {
+            if let Some(last_position) = self.last_position.filter(|&last| last >= position) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "position {position} does not follow position {last_position} \
+                        on contig '{contig}'"
+                    ),
+                ));
+            }
+        }
+        if self.block_per_contig && is_new_contig {
+            self.flush_block()?;
+        }
+
+        write(self)?;
+        self.sites += 1;
+        self.last_position = Some(position);
+
+        if is_new_contig {
+            if let Some(record) = self.index_record.as_mut() {
+                *record.alleles_mut() = self.contig_alleles.get(contig).copied();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` and counts the drop if position deduplication is enabled, and the provided
+    /// contig and position are equal to those of the preceding record written.
+    fn skip_duplicate(&mut self, contig: &str, position: u64) -> bool {
+        let is_duplicate = self.dedup_positions
+            && self.last_position == Some(position)
+            && self
+                .index_record
+                .as_ref()
+                .is_some_and(|record| record.name() == contig);
+
+        if is_duplicate {
+            self.duplicates_dropped += 1;
+        }
+
+        is_duplicate
+    }
+}
+
+impl<W, WP, WI> Writer<W, V3, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+{
+    /// Writes a single site from its parts.
+    ///
+    /// This is equivalent to [`Self::write_record`], except that the likelihoods are written
+    /// directly from a borrowed slice. This avoids constructing an owned record for each site.
+    pub fn write_site(
+        &mut self,
+        contig: &str,
+        position: u32,
+        likelihoods: &[f32],
+    ) -> io::Result<()> {
+        self.write_counted(contig, u64::from(position), |writer| {
+            writer.write_site_unchecked(contig, u64::from(position), likelihoods)
+        })
+    }
+
+    /// Writes a single record with a 64-bit position.
+    ///
+    /// This is equivalent to [`Self::write_record`], except that positions that do not fit in 32
+    /// bits can be written if the position width is set accordingly, see
+    /// [`Self::set_position_width`].
+    pub fn write_record64<I>(&mut self, record: &Record64<I, Likelihoods>) -> io::Result<()>
+    where
+        I: AsRef<str>,
+    {
+        let contig = record.contig_id().as_ref();
+
+        self.write_counted(contig, record.position(), |writer| {
+            writer.write_site_unchecked(contig, record.position(), record.item())
+        })
+    }
+
+    /// Writes a single site from its parts, without updating the site count of the writer.
+    pub(crate) fn write_site_unchecked(
+        &mut self,
+        contig: &str,
+        position: u64,
+        likelihoods: &[f32],
+    ) -> io::Result<()> {
+        let position = self.stored_position(position)?;
+
+        if let Some(index_record) = self.index_record.as_mut() {
+            if index_record.name() == contig {
+                // We're on the same contig, so we can simply update index record
+                *index_record.sites_mut() += 1;
+            } else {
+                // We're on a new contig, which means we have to write the current record index
+                // and set up a new one
+                let position_offset =
+                    self.position_offset_base + u64::from(self.position_writer.virtual_position());
+                let item_offset =
+                    self.item_offset_base + u64::from(self.item_writer.virtual_position());
+
+                let new = index::Record::new(contig.to_string(), 1, position_offset, item_offset);
+
+                let old = mem::replace(index_record, new);
+                old.write(&mut self.index_writer)?;
+            }
+        } else {
+            let position_offset =
+                self.position_offset_base + u64::from(self.position_writer.virtual_position());
+            let item_offset =
+                self.item_offset_base + u64::from(self.item_writer.virtual_position());
+
+            let index_record =
+                index::Record::new(contig.to_string(), 1, position_offset, item_offset);
+            self.index_record = Some(index_record);
+        }
+
+        self.write_stored_position(position)?;
+        self.item_writer.write_likelihoods(likelihoods)
+    }
+}
+
+impl<W, WP, WI> Writer<W, V4, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+{
+    /// Writes a single site from a full set of likelihoods, storing only a band.
+    ///
+    /// The likelihoods are assumed to be in log-space. Leading and trailing likelihoods more than
+    /// `cutoff` below the maximum likelihood are dropped, and the remaining likelihoods are
+    /// written as a band. Likelihoods inside the band that are below the cutoff are still
+    /// retained, so as to keep the band contiguous. When reading, dropped likelihoods may then be
+    /// approximated by a low fill value, see [`Band::into_full`]. The `cutoff` should be
+    /// non-negative.
+    pub fn write_likelihoods_as_band(
+        &mut self,
+        contig: &str,
+        position: u32,
+        likelihoods: &[f32],
+        cutoff: f32,
+    ) -> io::Result<()> {
+        let max = likelihoods
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        let is_kept = |&v: &f32| v >= max - cutoff;
+
+        let band = match (
+            likelihoods.iter().position(is_kept),
+            likelihoods.iter().rposition(is_kept),
+        ) {
+            (Some(first), Some(last)) => Band::new(first, likelihoods[first..=last].to_vec()),
+            _ => Band::new(0, Vec::new()),
+        };
+
+        self.write_counted(contig, u64::from(position), |writer| {
+            writer.write_band_unchecked(contig, u64::from(position), &band)
+        })
+    }
+
+    /// Writes a single record with a 64-bit position.
+    ///
+    /// This is equivalent to [`Self::write_record`], except that positions that do not fit in 32
+    /// bits can be written if the position width is set accordingly, see
+    /// [`Self::set_position_width`].
+    pub fn write_record64<I>(&mut self, record: &Record64<I, Band>) -> io::Result<()>
+    where
+        I: AsRef<str>,
+    {
+        let contig = record.contig_id().as_ref();
+
+        self.write_counted(contig, record.position(), |writer| {
+            writer.write_band_unchecked(contig, record.position(), record.item())
+        })
+    }
+
+    /// Writes a single site from its parts, without updating the site count of the writer.
+    pub(crate) fn write_band_unchecked(
+        &mut self,
+        contig: &str,
+        position: u64,
+        band: &Band,
+    ) -> io::Result<()> {
+        let position = self.stored_position(position)?;
+
+        if let Some(index_record) = self.index_record.as_mut() {
+            if index_record.name() == contig {
+                // We're on the same contig, so we can simply update index record
+                *index_record.sum_band_mut() += band.likelihoods().len();
+                *index_record.sites_mut() += 1;
+            } else {
+                // We're on a new contig, which means we have to write the current record index
+                // and set up a new one
+                let position_offset =
+                    self.position_offset_base + u64::from(self.position_writer.virtual_position());
+                let item_offset =
+                    self.item_offset_base + u64::from(self.item_writer.virtual_position());
+
+                let new = index::Record::new_with_sum_band(
+                    contig.to_string(),
+                    1,
+                    band.likelihoods().len(),
+                    position_offset,
+                    item_offset,
+                );
+
+                let old = mem::replace(index_record, new);
+                old.write(&mut self.index_writer)?;
+            }
+        } else {
+            let position_offset =
+                self.position_offset_base + u64::from(self.position_writer.virtual_position());
+            let item_offset =
+                self.item_offset_base + u64::from(self.item_writer.virtual_position());
+
+            let index_record = index::Record::new_with_sum_band(
+                contig.to_string(),
+                1,
+                band.likelihoods().len(),
+                position_offset,
+                item_offset,
+            );
+            self.index_record = Some(index_record);
+        }
+
+        self.write_stored_position(position)?;
+        self.item_writer.write_band(band)
     }
 }
 
@@ -140,6 +668,89 @@ impl<V> Writer<io::BufWriter<fs::File>, V>
 where
     V: Version,
 {
+    /// Creates a new writer appending to existing files at paths.
+    ///
+    /// The magic numbers of the existing files are checked, and the existing index is read.
+    /// Records written will then be appended to the existing records, where writing a record on
+    /// the last contig of the existing index continues that contig. The number of alleles, as well
+    /// as the position width and base and whether to write a site checksum, are taken from the
+    /// existing index. The last position of the last contig is read from the existing position
+    /// file, so that position deduplication and validation apply across the append boundary.
+    ///
+    /// The existing index is rewritten without its last record, which is written again on
+    /// [`Self::finish`]. Therefore, the files will not be readable until the writer is finished.
+    /// Returns an error if the existing index contains reference lengths, which are not supported
+    /// when writing.
+    pub fn from_paths_append<P>(index_path: P, position_path: P, item_path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let index = index::Index::<V>::read_from_path(&index_path)?;
+
+        if index
+            .records()
+            .iter()
+            .any(|record| record.reference_length().is_some())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot append to SAF index with reference lengths",
+            ));
+        }
+
+        let last_position = index
+            .records()
+            .last()
+            .map(|record| read_last_position(&position_path, &index, record))
+            .transpose()?
+            .flatten();
+
+        let (position_writer, position_offset_base) = open_bgzf_append::<V, _>(position_path)?;
+        let (item_writer, item_offset_base) = open_bgzf_append::<V, _>(item_path)?;
+
+        let mut index_writer = fs::File::create(index_path).map(io::BufWriter::new)?;
+        V::write_magic(&mut index_writer)?;
+        index_writer.write_alleles(index.alleles())?;
+
+        let site_checksum = index.site_checksum();
+        let total_sites = index.total_sites();
+        let position_width = index.position_width();
+        let position_base = index.position_base();
+
+        let mut records = index.into_records();
+        let index_record = records.pop();
+        for record in records.iter() {
+            record.write(&mut index_writer)?;
+        }
+
+        let mut new = Self::new_split(index_writer, position_writer, item_writer);
+        new.index_record = index_record;
+        new.position_offset_base = position_offset_base;
+        new.item_offset_base = item_offset_base;
+        new.sites = total_sites;
+        new.site_checksum = site_checksum.is_some();
+        new.position_width = position_width;
+        new.position_base = position_base;
+        new.last_position = last_position;
+        new.alleles_written = true;
+
+        Ok(new)
+    }
+
+    /// Creates a new writer appending to existing files at a shared prefix.
+    ///
+    /// See [`Self::from_paths_append`] for details on appending, and [`Self::from_prefix`] for
+    /// details on conventional naming.
+    pub fn from_prefix_append<P>(prefix: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let [index_path, position_path, item_path] =
+            member_paths_from_prefix(&prefix.as_ref().to_string_lossy());
+
+        Self::from_paths_append(index_path, position_path, item_path)
+    }
+
     /// Creates a new writer from any member path.
     ///
     /// This method relies on stripping a conventional suffix from the member path and
@@ -171,7 +782,8 @@ where
 
     /// Creates a new writer from paths.
     ///
-    /// If the paths already exists, they will be overwritten.
+    /// A [`Builder`] can be used to configure the writer further. If the paths already exists,
+    /// they will be overwritten.
     ///
     /// The magic number will be written to the paths, and the alleles will be written to the index
     /// writer after the magic number.
@@ -184,14 +796,7 @@ where
     where
         P: AsRef<Path>,
     {
-        let index_writer = fs::File::create(index_path).map(io::BufWriter::new)?;
-        let position_writer = fs::File::create(position_path).map(io::BufWriter::new)?;
-        let item_writer = fs::File::create(item_path).map(io::BufWriter::new)?;
-
-        let mut new = Self::new(index_writer, position_writer, item_writer);
-        new.write_magic()?;
-        new.write_alleles(alleles)?;
-        Ok(new)
+        Builder::default().build_from_paths(alleles, index_path, position_path, item_path)
     }
 
     /// Creates a new writer from a shared prefix.
@@ -214,3 +819,92 @@ where
         Self::from_paths(alleles, index_path, position_path, item_path)
     }
 }
+
+/// Opens an existing BGZF file for appending.
+///
+/// The magic number is checked, and the BGZF end-of-file marker is removed if present. Returns the
+/// writer positioned at the end of the file, and the virtual position of the end of the file.
+fn open_bgzf_append<V, P>(path: P) -> io::Result<(io::BufWriter<fs::File>, u64)>
+where
+    V: Version,
+    P: AsRef<Path>,
+{
+    let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+
+    V::read_magic(&mut bgzf::Reader::new(&mut file))?;
+
+    let mut len = file.metadata()?.len();
+    if let Some(eof_start) = len.checked_sub(BGZF_EOF.len() as u64) {
+        let mut tail = [0; BGZF_EOF.len()];
+        file.seek(io::SeekFrom::Start(eof_start))?;
+        file.read_exact(&mut tail)?;
+
+        if tail == BGZF_EOF {
+            len = eof_start;
+        }
+    }
+
+    file.set_len(len)?;
+    file.seek(io::SeekFrom::End(0))?;
+
+    Ok((io::BufWriter::new(file), len << 16))
+}
+
+/// Reads the last 0-based position of an index record from the position file at path.
+///
+/// Returns `None` if the record has no sites.
+fn read_last_position<V, P>(
+    path: P,
+    index: &index::Index<V>,
+    record: &index::Record<V>,
+) -> io::Result<Option<u64>>
+where
+    V: Version,
+    P: AsRef<Path>,
+{
+    let mut reader = fs::File::open(path).map(bgzf::Reader::new)?;
+    reader.seek(bgzf::VirtualPosition::from(record.position_offset()))?;
+
+    let mut last_position = None;
+    for _ in 0..record.sites() {
+        let position = reader
+            .read_position_with_width(index.position_width())?
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "SAF position file ended before last position of contig '{}'",
+                        record.name()
+                    ),
+                )
+            })?;
+
+        last_position = Some(position);
+    }
+
+    last_position
+        .map(|position| index.position_base().to_zero_based(position))
+        .transpose()
+}
+
+/// An error indicating that a writer has been used such that its output will not be readable.
+///
+/// See [`Writer::self_check`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WriterStateError {
+    /// The number of alleles has not been written.
+    MissingAlleles,
+    /// No records have been written.
+    NoRecords,
+}
+
+impl fmt::Display for WriterStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAlleles => f.write_str("number of alleles not written"),
+            Self::NoRecords => f.write_str("no records written"),
+        }
+    }
+}
+
+impl error::Error for WriterStateError {}
@@ -0,0 +1,139 @@
+use std::{fs::File, io, marker::PhantomData, path::Path};
+
+use bgzf::writer::CompressionLevel;
+
+use crate::{
+    ext::member_paths_from_prefix,
+    version::{Version, V3, V4},
+};
+
+use super::Writer;
+
+/// A builder for a SAF writer.
+///
+/// Note that unlike the reader [`Builder`](crate::reader::Builder), there is no `set_threads`:
+/// the position and item files are always compressed on the calling thread. The BGZF writers
+/// used do not support multi-threaded compression, and the index is built from their virtual
+/// positions as records are written.
+#[derive(Debug)]
+pub struct Builder<V> {
+    compression_level: Option<CompressionLevel>,
+    v: PhantomData<V>,
+}
+
+type DefaultWriter<V> = Writer<io::BufWriter<File>, V>;
+
+impl<V> Builder<V>
+where
+    V: Version,
+{
+    /// Builds a new writer from its components.
+    ///
+    /// The position and item writers will be wrapped in [`bgzf::Writer`]s. The magic numbers and
+    /// alleles will *not* be written, so [`Writer::write_magic`] and [`Writer::write_alleles`]
+    /// should be called manually before writing records.
+    pub fn build<W>(self, index_writer: W, position_writer: W, item_writer: W) -> Writer<W, V>
+    where
+        W: io::Write,
+    {
+        Writer::from_bgzf(
+            index_writer,
+            self.build_bgzf(position_writer),
+            self.build_bgzf(item_writer),
+        )
+    }
+
+    /// Builds a new writer from paths.
+    ///
+    /// If the paths already exists, they will be overwritten.
+    ///
+    /// The magic number will be written to the paths, and the alleles will be written to the index
+    /// writer after the magic number.
+    pub fn build_from_paths<P>(
+        self,
+        alleles: usize,
+        index_path: P,
+        position_path: P,
+        item_path: P,
+    ) -> io::Result<DefaultWriter<V>>
+    where
+        P: AsRef<Path>,
+    {
+        let index_writer = File::create(index_path).map(io::BufWriter::new)?;
+        let position_writer = File::create(position_path).map(io::BufWriter::new)?;
+        let item_writer = File::create(item_path).map(io::BufWriter::new)?;
+
+        let mut new = self.build(index_writer, position_writer, item_writer);
+        new.write_magic()?;
+        new.write_alleles(alleles)?;
+        Ok(new)
+    }
+
+    /// Builds a new writer from a shared prefix.
+    ///
+    /// Conventionally, the SAF index, positions, and item files are named according to a shared
+    /// prefix and specific extensions for each file. See [`crate::ext`] for these extensions.
+    /// This method opens files for writing in accordance with these conventions.
+    ///
+    /// If the paths already exists, they will be overwritten.
+    ///
+    /// The magic number will be written to the paths, and the alleles will be written to the index
+    /// writer after the magic number.
+    pub fn build_from_prefix<P>(self, alleles: usize, prefix: P) -> io::Result<DefaultWriter<V>>
+    where
+        P: AsRef<Path>,
+    {
+        let [index_path, position_path, item_path] =
+            member_paths_from_prefix(&prefix.as_ref().to_string_lossy());
+
+        self.build_from_paths(alleles, index_path, position_path, item_path)
+    }
+
+    /// Sets the compression level of the position and item writers.
+    ///
+    /// Lower levels write faster at the cost of larger files, and vice versa. By default, the
+    /// default BGZF compression level is used.
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    fn build_bgzf<W>(&self, writer: W) -> bgzf::Writer<W>
+    where
+        W: io::Write,
+    {
+        let mut builder = bgzf::writer::Builder::default();
+
+        if let Some(compression_level) = self.compression_level {
+            builder = builder.set_compression_level(compression_level);
+        }
+
+        builder.build_with_writer(writer)
+    }
+}
+
+impl Builder<V3> {
+    /// Creates a builder for a new SAF V3 writer.
+    pub fn v3() -> Self {
+        Self::default()
+    }
+}
+
+impl Builder<V4> {
+    /// Creates a builder for a new SAF V4 writer.
+    pub fn v4() -> Self {
+        Self::default()
+    }
+}
+
+impl<V> Default for Builder<V>
+where
+    V: Version,
+{
+    fn default() -> Self {
+        Self {
+            compression_level: None,
+            v: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::io;
+
+use crate::{
+    record::{Band, Likelihoods, Record},
+    version::{Version, V3, V4},
+};
+
+use super::Writer;
+
+/// A SAF writer that accumulates an SFS from the records written.
+///
+/// As each record is written to the inner writer, its likelihoods are also normalised and added to
+/// an unfolded SFS. The likelihoods are assumed to be in log-space, and are normalised to sum to
+/// one in linear space. For [`V4`], values outside the band are taken to be zero in linear space.
+/// This allows a rough SFS to be obtained while writing, without a separate pass reading the data.
+///
+/// Only records actually written to the inner writer are accumulated: records rejected or dropped
+/// by the inner writer are not. Records where all likelihoods are zero in linear space carry no
+/// information, and are written without being accumulated.
+pub struct SfsAccumulatingWriter<W, V, WP = W, WI = W>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+    V: Version,
+{
+    inner: Writer<W, V, WP, WI>,
+    sfs: Vec<f64>,
+}
+
+impl<W, V, WP, WI> SfsAccumulatingWriter<W, V, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+    V: Version,
+{
+    /// Finishes writing.
+    ///
+    /// Returns the accumulated SFS along with the finished inner writers.
+    pub fn finish(self) -> io::Result<(Vec<f64>, (W, WP, WI))> {
+        let sfs = self.sfs;
+
+        self.inner.finish().map(|parts| (sfs, parts))
+    }
+
+    /// Returns the inner writer.
+    pub fn get_ref(&self) -> &Writer<W, V, WP, WI> {
+        &self.inner
+    }
+
+    /// Returns the inner writer, discarding the accumulated SFS.
+    pub fn into_inner(self) -> Writer<W, V, WP, WI> {
+        self.inner
+    }
+
+    /// Creates a new SFS accumulating writer.
+    ///
+    /// The SFS will have `alleles + 1` values. The magic numbers and alleles should already have
+    /// been written to the inner writer.
+    pub fn new(inner: Writer<W, V, WP, WI>, alleles: usize) -> Self {
+        Self {
+            inner,
+            sfs: vec![0.0; alleles + 1],
+        }
+    }
+
+    /// Returns the SFS accumulated so far.
+    pub fn sfs(&self) -> &[f64] {
+        &self.sfs
+    }
+
+    /// Writes a single record to the inner writer, accumulating the provided likelihoods into the
+    /// SFS only if the record was written.
+    fn write_and_accumulate<I>(
+        &mut self,
+        record: &Record<I, V::Item>,
+        start: usize,
+        likelihoods: &[f32],
+    ) -> io::Result<()>
+    where
+        I: AsRef<str>,
+    {
+        let duplicates_dropped = self.inner.duplicates_dropped();
+        self.inner.write_record(record)?;
+
+        if self.inner.duplicates_dropped() == duplicates_dropped {
+            accumulate(&mut self.sfs, start, likelihoods);
+        }
+
+        Ok(())
+    }
+}
+
+impl<W, WP, WI> SfsAccumulatingWriter<W, V3, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+{
+    /// Writes a single record, accumulating its likelihoods into the SFS.
+    ///
+    /// Returns an error if the record does not have a likelihood for each value in the SFS.
+    pub fn write_record<I>(&mut self, record: &Record<I, Likelihoods>) -> io::Result<()>
+    where
+        I: AsRef<str>,
+    {
+        if record.item().len() != self.sfs.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "record with {} likelihoods does not match SFS of length {}",
+                    record.item().len(),
+                    self.sfs.len()
+                ),
+            ));
+        }
+
+        self.write_and_accumulate(record, 0, record.item())
+    }
+}
+
+impl<W, WP, WI> SfsAccumulatingWriter<W, V4, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+{
+    /// Writes a single record, accumulating its band likelihoods into the SFS.
+    ///
+    /// Returns an error if the band extends beyond the end of the SFS.
+    pub fn write_record<I>(&mut self, record: &Record<I, Band>) -> io::Result<()>
+    where
+        I: AsRef<str>,
+    {
+        let band = record.item();
+        check_fits(&self.sfs, band.start(), band.likelihoods())?;
+
+        self.write_and_accumulate(record, band.start(), band.likelihoods())
+    }
+}
+
+/// Returns an error if the likelihoods starting from the provided index do not fit within the SFS.
+fn check_fits(sfs: &[f64], start: usize, likelihoods: &[f32]) -> io::Result<()> {
+    if start + likelihoods.len() > sfs.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "likelihoods for sample frequencies {start}..{} do not fit SFS of length {}",
+                start + likelihoods.len(),
+                sfs.len()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Normalises log-likelihoods and adds them to the SFS starting from the provided index.
+///
+/// The likelihoods must fit within the SFS, see [`check_fits`]. If all likelihoods are negative
+/// infinity, or there are none, the SFS is left unchanged.
+fn accumulate(sfs: &mut [f64], start: usize, likelihoods: &[f32]) {
+    let max = likelihoods
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    if max == f32::NEG_INFINITY {
+        return;
+    }
+
+    let sum: f64 = likelihoods.iter().map(|&v| f64::from(v - max).exp()).sum();
+
+    for (x, &v) in sfs[start..].iter_mut().zip(likelihoods) {
+        *x += f64::from(v - max).exp() / sum;
+    }
+}
@@ -0,0 +1,93 @@
+use std::{io, mem};
+
+use indexmap::IndexMap;
+
+use crate::{record::Record, version::Version};
+
+use super::Writer;
+
+/// A SAF writer for records with contigs in arbitrary order.
+///
+/// The regular [`Writer`] requires that all records on a contig are written in a single run.
+/// The sparse writer instead allows records on the same contig to be written in multiple,
+/// non-contiguous runs, and with positions in any order. To achieve this, all records are kept
+/// in memory until [`Self::finish`] is called, at which point the records on each contig are
+/// sorted by position and written to the inner writer. Contigs are written in the order in which
+/// they were first encountered.
+///
+/// Since all records must be kept in memory, this is only suitable for moderately sized data.
+pub struct SparseWriter<W, V, WP = W, WI = W>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+    V: Version,
+{
+    inner: Writer<W, V, WP, WI>,
+    contigs: IndexMap<String, Vec<(u32, V::Item)>>,
+}
+
+impl<W, V, WP, WI> SparseWriter<W, V, WP, WI>
+where
+    W: io::Write,
+    WP: io::Write,
+    WI: io::Write,
+    V: Version,
+{
+    /// Finishes writing.
+    ///
+    /// All buffered records are sorted and written to the inner writer, which is then finished.
+    /// Returns an error if the same position occurs more than once on a contig.
+    pub fn finish(mut self) -> io::Result<(W, WP, WI)> {
+        for (name, mut sites) in mem::take(&mut self.contigs) {
+            sites.sort_by_key(|(position, _)| *position);
+
+            if let Some(pair) = sites.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("duplicate position {} on contig '{name}'", pair[0].0),
+                ));
+            }
+
+            for (position, item) in sites {
+                self.inner
+                    .write_record(&Record::new(name.as_str(), position, item))?;
+            }
+        }
+
+        self.inner.finish()
+    }
+
+    /// Returns the inner writer, discarding any buffered records.
+    pub fn into_inner(self) -> Writer<W, V, WP, WI> {
+        self.inner
+    }
+
+    /// Creates a new sparse writer.
+    ///
+    /// The magic numbers and alleles should already have been written to the inner writer.
+    pub fn new(inner: Writer<W, V, WP, WI>) -> Self {
+        Self {
+            inner,
+            contigs: IndexMap::new(),
+        }
+    }
+
+    /// Buffers a single record for writing.
+    pub fn write_record<I>(&mut self, record: &Record<I, V::Item>) -> io::Result<()>
+    where
+        I: AsRef<str>,
+        V::Item: Clone,
+    {
+        let name = record.contig_id().as_ref();
+        let site = (record.position(), record.item().clone());
+
+        if let Some(sites) = self.contigs.get_mut(name) {
+            sites.push(site);
+        } else {
+            self.contigs.insert(name.to_string(), vec![site]);
+        }
+
+        Ok(())
+    }
+}
@@ -9,6 +9,9 @@ pub trait WriterExt {
     /// Writes a single position.
     fn write_position(&mut self, position: u32) -> io::Result<()>;
 
+    /// Writes a single 64-bit position.
+    fn write_position_u64(&mut self, position: u64) -> io::Result<()>;
+
     /// Write likelihoods.
     fn write_likelihoods(&mut self, likelihoods: &[f32]) -> io::Result<()>;
 
@@ -24,6 +27,10 @@ where
         self.write_u32::<LE>(position)
     }
 
+    fn write_position_u64(&mut self, position: u64) -> io::Result<()> {
+        self.write_u64::<LE>(position)
+    }
+
     fn write_likelihoods(&mut self, likelihoods: &[f32]) -> io::Result<()> {
         for &v in likelihoods {
             self.write_f32::<LE>(v)?;
@@ -0,0 +1,148 @@
+use std::{fmt, io};
+
+use angsd_saf::{
+    index::PositionBase,
+    reader::Builder,
+    record::{Id, Record},
+    version::{Version, V3, V4},
+    Index, Writer,
+};
+
+pub mod utils;
+use utils::{reader_from_records, TempPrefix};
+
+type Appended<V> = (Index<V>, Vec<Record<Id, <V as Version>::Item>>);
+
+fn write_then_append<V>(
+    name: &str,
+    alleles: usize,
+    records: &[Record<&str, V::Item>],
+    split: usize,
+) -> io::Result<Appended<V>>
+where
+    V: Version,
+{
+    let prefix = TempPrefix::new(&format!("append-{name}"));
+
+    let mut writer = Writer::<_, V>::from_prefix(alleles, &prefix)?;
+    for record in records[..split].iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    let mut writer = Writer::<_, V>::from_prefix_append(&prefix)?;
+    for record in records[split..].iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    let index = Index::read_from_path(format!("{}.saf.idx", prefix.as_ref().display()))?;
+    let mut reader = Builder::<V>::default().build_from_prefix(&prefix)?;
+    let records = reader.records().collect::<io::Result<Vec<_>>>()?;
+
+    Ok((index, records))
+}
+
+fn check_append<V>(name: &str, alleles: usize, records: &[Record<&str, V::Item>]) -> io::Result<()>
+where
+    V: Version,
+    V::Item: fmt::Debug + PartialEq,
+{
+    let mut expected_reader = reader_from_records::<V>(alleles, records, 1)?;
+    let summary = |index: &Index<V>| {
+        index
+            .records()
+            .iter()
+            .map(|record| (record.name().to_owned(), record.sites()))
+            .collect::<Vec<_>>()
+    };
+    let expected_summary = summary(expected_reader.index());
+    let expected = expected_reader.records().collect::<io::Result<Vec<_>>>()?;
+
+    for split in 1..records.len() {
+        let (index, read) =
+            write_then_append::<V>(&format!("{name}-{split}"), alleles, records, split)?;
+
+        assert_eq!(read, expected);
+        assert_eq!(summary(&index), expected_summary);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_append_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.],
+    ];
+
+    check_append::<V3>("v3", 2, records)
+}
+
+#[test]
+fn test_append_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+        chr2:7 [-1., 0.],
+    ];
+
+    check_append::<V4>("v4", 2, records)
+}
+
+#[test]
+fn test_append_keeps_sum_band_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+    ];
+
+    let (index, _) = write_then_append::<V4>("sum-band", 2, records, 1)?;
+    assert_eq!(index.records()[0].sum_band(), 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_append_checks_boundary_position() -> io::Result<()> {
+    let prefix = TempPrefix::new("append-boundary");
+
+    let mut writer = Writer::<_, V3>::from_prefix(2, &prefix)?;
+    writer.set_position_base(PositionBase::One);
+    for record in records_v3![chr1:1 [0., -1., -2.], chr1:5 [-1., 0., -2.]].iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    let mut writer = Writer::<_, V3>::from_prefix_append(&prefix)?;
+    writer.set_validate_positions(true);
+    for record in records_v3![chr1:3 [0., 0., 0.], chr1:5 [0., 0., 0.]].iter() {
+        let error = writer.write_record(record).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    writer.set_dedup_positions(true);
+    for record in records_v3![chr1:5 [0., 0., 0.], chr1:6 [-2., -1., 0.]].iter() {
+        writer.write_record(record)?;
+    }
+    assert_eq!(writer.duplicates_dropped(), 1);
+    writer.finish()?;
+
+    let mut reader = Builder::<V3>::default().build_from_prefix(&prefix)?;
+    let positions = reader
+        .records()
+        .map(|record| record.map(|record| record.position()))
+        .collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(positions, [1, 5, 6]);
+
+    Ok(())
+}
+
+#[test]
+fn test_append_missing_files() {
+    let prefix = TempPrefix::new("append-missing");
+    assert!(Writer::<_, V3>::from_prefix_append(&prefix).is_err());
+}
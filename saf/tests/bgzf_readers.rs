@@ -0,0 +1,88 @@
+use std::{
+    fmt,
+    io::{self, Seek},
+};
+
+use angsd_saf::{
+    index,
+    version::{Version, V3, V4},
+    Index, Reader, Record,
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, setup_writer};
+
+fn test_from_bgzf_readers<V>(alleles: usize, records: &[Record<&str, V::Item>])
+where
+    V: Version,
+    V::Item: fmt::Debug + PartialEq,
+{
+    let mut writer = setup_writer::<V>(alleles).unwrap();
+    for record in records.iter() {
+        writer.write_record(record).unwrap();
+    }
+    let (mut index_reader, mut position_reader, mut item_reader) = writer.finish().unwrap();
+    for reader in [&mut index_reader, &mut position_reader, &mut item_reader] {
+        reader.seek(io::SeekFrom::Start(0)).unwrap();
+    }
+    let index = Index::<V>::read(&mut index_reader).unwrap();
+
+    let mut reader = Reader::from_bgzf_readers(index, position_reader, item_reader).unwrap();
+    assert_eq!(reader.magic(), &V::MAGIC_NUMBER);
+
+    let read_records = reader.records().collect::<io::Result<Vec<_>>>().unwrap();
+    let read_records = read_records
+        .into_iter()
+        .map(|record| record.to_named(reader.index()))
+        .collect::<Vec<_>>();
+    assert_eq!(read_records, records);
+
+    // Seeking works over generic sources
+    reader.seek(1).unwrap();
+    let mut record = reader.create_record_buf();
+    assert!(reader.read_record(&mut record).unwrap().is_not_done());
+    assert_eq!(reader.index().records()[*record.contig_id()].name(), "chr2");
+}
+
+#[test]
+fn test_from_bgzf_readers_v3() {
+    let records = records_v3![chr1:1, chr1:2, chr2:3];
+    test_from_bgzf_readers::<V3>(get_alleles_v3(records), records);
+}
+
+#[test]
+fn test_from_bgzf_readers_v4() {
+    let records = records_v4![chr1:1, chr1:2, chr2:3];
+    test_from_bgzf_readers::<V4>(get_alleles_v4(records), records);
+}
+
+#[test]
+fn test_from_bgzf_readers_magic_mismatch() -> io::Result<()> {
+    let mut writer = setup_writer::<V4>(0)?;
+    for record in records_v4![chr1:1].iter() {
+        writer.write_record(record)?;
+    }
+    let (_, mut position_reader, mut item_reader) = writer.finish()?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+    item_reader.seek(io::SeekFrom::Start(0))?;
+
+    let index = Index::<V3>::new(0, vec![index::Record::new("chr1".to_string(), 1, 8, 8)]);
+    let result = Reader::from_bgzf_readers(index, position_reader, item_reader);
+    assert_eq!(
+        result.err().map(|e| e.kind()),
+        Some(io::ErrorKind::InvalidData)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_bgzf_readers_empty_index() {
+    let index = Index::<V3>::new(0, Vec::new());
+    let empty = || io::Cursor::new(Vec::new());
+    let result = Reader::from_bgzf_readers(index, empty(), empty());
+    assert_eq!(
+        result.err().map(|e| e.kind()),
+        Some(io::ErrorKind::InvalidData)
+    );
+}
@@ -0,0 +1,73 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{version::V3, Index};
+
+pub mod utils;
+use utils::{get_alleles_v3, reader_from_writer, setup_writer};
+
+#[test]
+fn test_block_per_contig_aligns_contigs_to_blocks() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.],
+    ];
+
+    let mut writer = setup_writer::<V3>(get_alleles_v3(records))?;
+    writer.set_block_per_contig(true);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let (mut index_reader, _, _) = writer.finish()?;
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    let index = Index::<V3>::read(&mut index_reader)?;
+
+    assert_eq!(index.records().len(), 3);
+    for record in index.records() {
+        let position_vpos = bgzf::VirtualPosition::from(record.position_offset());
+        assert_eq!(position_vpos.uncompressed(), 0);
+
+        let item_vpos = bgzf::VirtualPosition::from(record.item_offset());
+        assert_eq!(item_vpos.uncompressed(), 0);
+    }
+
+    // Contigs start in distinct blocks
+    let compressed_offsets = index
+        .records()
+        .iter()
+        .map(|record| bgzf::VirtualPosition::from(record.position_offset()).compressed())
+        .collect::<Vec<_>>();
+    assert!(compressed_offsets.windows(2).all(|w| w[0] < w[1]));
+
+    // Flushing does not affect data
+    let mut writer = setup_writer::<V3>(get_alleles_v3(records))?;
+    writer.set_block_per_contig(true);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let mut reader = reader_from_writer(writer, 1)?;
+    let mut record = reader.create_record_buf();
+    for expected in records.iter() {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+        assert_eq!(&record.clone().to_named(reader.index()), expected);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_flush_block() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(2)?;
+    writer.write_record(&records_v3![chr1:1 [0., -1., -2.]][0])?;
+    writer.flush_block()?;
+
+    assert_eq!(
+        writer.position_writer().virtual_position().uncompressed(),
+        0
+    );
+    assert_eq!(writer.item_writer().virtual_position().uncompressed(), 0);
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{
+    version::{Version, V3, V4},
+    Index, Reader,
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, setup_writer, MockWriter};
+
+type Finished = (
+    io::Cursor<Vec<u8>>,
+    io::Cursor<Vec<u8>>,
+    io::Cursor<Vec<u8>>,
+);
+
+fn canonical_bytes<V>(
+    (mut index_reader, position_reader, item_reader): Finished,
+) -> io::Result<Vec<u8>>
+where
+    V: Version,
+{
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    let index = Index::read(&mut index_reader)?;
+
+    let mut reader = Reader::<_, V>::from_bgzf(
+        index,
+        bgzf::Reader::new(io::Cursor::new(position_reader.into_inner())),
+        bgzf::Reader::new(io::Cursor::new(item_reader.into_inner())),
+    )
+    .expect("non-empty index");
+    reader.read_magic()?;
+    reader.to_canonical_bytes()
+}
+
+fn finish_pair<V>(
+    mut plain: MockWriter<V>,
+    mut blocked: MockWriter<V>,
+    write: impl Fn(&mut MockWriter<V>) -> io::Result<()>,
+) -> io::Result<(Finished, Finished)>
+where
+    V: Version,
+{
+    blocked.set_block_per_contig(true);
+
+    write(&mut plain)?;
+    write(&mut blocked)?;
+
+    Ok((plain.finish()?, blocked.finish()?))
+}
+
+#[test]
+fn test_canonical_bytes_independent_of_compression_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.],
+    ];
+    let alleles = get_alleles_v3(records);
+
+    let (plain, blocked) = finish_pair(
+        setup_writer::<V3>(alleles)?,
+        setup_writer::<V3>(alleles)?,
+        |writer| {
+            records
+                .iter()
+                .try_for_each(|record| writer.write_record(record))
+        },
+    )?;
+
+    let plain = canonical_bytes::<V3>(plain)?;
+    let blocked = canonical_bytes::<V3>(blocked)?;
+    assert_eq!(plain, blocked);
+
+    // Magic, alleles, and contig ID, position, and three likelihoods per site
+    assert_eq!(plain.len(), 8 + 8 + records.len() * (8 + 8 + 3 * 4));
+    assert_eq!(&plain[..8], b"safv3\0\0\0");
+
+    Ok(())
+}
+
+#[test]
+fn test_canonical_bytes_independent_of_compression_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1.], chr1:2 [nil, nil; 0.],
+        chr2:1 [-2., -1., 0.],
+    ];
+    let alleles = get_alleles_v4(records);
+
+    let (plain, blocked) = finish_pair(
+        setup_writer::<V4>(alleles)?,
+        setup_writer::<V4>(alleles)?,
+        |writer| {
+            records
+                .iter()
+                .try_for_each(|record| writer.write_record(record))
+        },
+    )?;
+
+    assert_eq!(
+        canonical_bytes::<V4>(plain)?,
+        canonical_bytes::<V4>(blocked)?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::io;
+
+use angsd_saf::{
+    reader::ReaderCheckpoint,
+    version::{V3, V4},
+};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_checkpoint_restore_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1.],
+        chr1:2 [-1., 0.],
+        chr2:1 [-2., 0.],
+        chr2:3 [0., -3.],
+        chr3:8 [0., -4.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(1, records, 1)?;
+    let mut record = reader.create_record_buf();
+    for _ in 0..3 {
+        reader.read_record(&mut record)?;
+    }
+    let checkpoint = reader.checkpoint();
+    drop(reader);
+
+    // Checkpoint survives being taken apart and put back together
+    let checkpoint = ReaderCheckpoint::new(
+        checkpoint.position_offset(),
+        checkpoint.item_offset(),
+        checkpoint.contig_id(),
+        checkpoint.sites_left_on_contig(),
+    );
+
+    let mut reader = reader_from_records::<V3>(1, records, 1)?;
+    reader.restore(checkpoint)?;
+
+    for expected in &records[3..] {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+        assert_eq!(&record.clone().to_named(reader.index()), expected);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_checkpoint_restore_at_contig_end_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1.],
+        chr1:2 [nil; -1., 0.],
+        chr2:1 [-2., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?;
+    let mut record = reader.create_record_buf();
+    for _ in 0..2 {
+        reader.read_record(&mut record)?;
+    }
+    let checkpoint = reader.checkpoint();
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?;
+    reader.restore(checkpoint)?;
+
+    assert!(reader.read_record(&mut record)?.is_not_done());
+    assert_eq!(&record.clone().to_named(reader.index()), &records[2]);
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_restore_invalid_checkpoint() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let checkpoint = ReaderCheckpoint::new(8, 8, 0, 3);
+    assert!(reader.restore(checkpoint).is_err());
+    let checkpoint = ReaderCheckpoint::new(8, 8, 1, 1);
+    assert!(reader.restore(checkpoint).is_err());
+    let checkpoint = ReaderCheckpoint::new(8, 8, 2, 0);
+    assert!(reader.restore(checkpoint).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_checkpoint_restore_at_end() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut record = reader.create_record_buf();
+    while reader.read_record(&mut record)?.is_not_done() {}
+
+    let checkpoint = reader.checkpoint();
+    reader.restore(checkpoint)?;
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    reader.restore(checkpoint)?;
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    // Sites left past the end of the index are not valid
+    let checkpoint = ReaderCheckpoint::new(
+        checkpoint.position_offset(),
+        checkpoint.item_offset(),
+        checkpoint.contig_id(),
+        1,
+    );
+    assert!(reader.restore(checkpoint).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::io;
+
+use angsd_saf::{
+    record::Band,
+    version::{V3, V4},
+    ReadStatus,
+};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_clamp_likelihoods_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1e30, -2.],
+        chr1:2 [-1., 0., -2.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?.clamp_likelihoods(-10.);
+    let mut record = reader.get_ref().create_record_buf();
+
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::NotDone);
+    assert_eq!(record.item().as_ref(), &[0., -10., -2.]);
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::NotDone);
+    assert_eq!(record.item().as_ref(), &[-1., 0., -2.]);
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::Done);
+
+    assert_eq!(reader.clamped(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_clamp_likelihoods_v3_neg_infinity() -> io::Result<()> {
+    let records = &[angsd_saf::Record::new(
+        "chr1",
+        1,
+        vec![f32::NEG_INFINITY, 0., f32::NEG_INFINITY].into(),
+    )];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?.clamp_likelihoods(-5.);
+    let mut record = reader.get_ref().create_record_buf();
+
+    reader.read_record(&mut record)?;
+    assert_eq!(record.item().as_ref(), &[-5., 0., -5.]);
+    assert_eq!(reader.clamped(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_clamp_likelihoods_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1e30],
+        chr1:2 [nil, nil; -20.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(3, records, 1)?.clamp_likelihoods(-10.);
+    let mut record = reader.get_ref().create_record_buf();
+
+    reader.read_record(&mut record)?;
+    assert_eq!(record.item(), &Band::new(1, vec![0., -10.]));
+    reader.read_record(&mut record)?;
+    assert_eq!(record.item(), &Band::new(2, vec![-10.]));
+
+    assert_eq!(reader.clamped(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use std::io;
+
+use angsd_saf::version::V3;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_columnar_matches_row_wise() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr2:5 [-2., -1., 0.],
+        chr2:6 [-3., -1., 0.],
+        chr3:1 [0., -4., -8.],
+    ];
+
+    let mut row_reader = reader_from_records::<V3>(2, records, 1)?;
+    let mut rows = Vec::new();
+    let mut record = row_reader.create_record_buf();
+    while row_reader.read_record(&mut record)?.is_not_done() {
+        rows.push(record.clone());
+    }
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+
+    let fst = reader.read_columnar(3)?;
+    let snd = reader.read_columnar(3)?;
+    assert_eq!(fst.len(), 3);
+    assert_eq!(snd.len(), 2);
+    assert_eq!(fst.likelihoods().len(), 3 * 3);
+    assert!(reader.read_columnar(3)?.is_empty());
+
+    let sites = fst
+        .sites()
+        .zip(fst.positions())
+        .zip(fst.contig_ids())
+        .chain(snd.sites().zip(snd.positions()).zip(snd.contig_ids()));
+    for (((likelihoods, position), contig_id), row) in sites.zip(rows.iter()) {
+        assert_eq!(likelihoods, &row.item()[..]);
+        assert_eq!(*position, row.position());
+        assert_eq!(contig_id, row.contig_id());
+    }
+
+    assert_eq!(snd.site(1), &rows[4].item()[..]);
+
+    Ok(())
+}
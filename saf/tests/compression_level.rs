@@ -0,0 +1,74 @@
+use std::io;
+
+use angsd_saf::{
+    record::{Id, Record},
+    version::{Version, V3, V4},
+    writer::{Builder, CompressionLevel},
+};
+
+pub mod utils;
+use utils::{reader_from_records, reader_from_writer};
+
+fn read_all_with_level<V>(
+    alleles: usize,
+    records: &[Record<&str, V::Item>],
+    compression_level: u8,
+) -> io::Result<Vec<Record<Id, V::Item>>>
+where
+    V: Version,
+{
+    let compression_level = CompressionLevel::try_from(compression_level)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid compression level"))?;
+
+    let mut writer = Builder::<V>::default()
+        .set_compression_level(compression_level)
+        .build(
+            io::Cursor::new(Vec::new()),
+            io::Cursor::new(Vec::new()),
+            io::Cursor::new(Vec::new()),
+        );
+    writer.write_magic()?;
+    writer.write_alleles(alleles)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    reader_from_writer(writer, 1)?.records().collect()
+}
+
+#[test]
+fn test_compression_level_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+    ];
+
+    let expected = reader_from_records::<V3>(2, records, 1)?
+        .records()
+        .collect::<io::Result<Vec<_>>>()?;
+
+    for level in [0, 9] {
+        assert_eq!(read_all_with_level::<V3>(2, records, level)?, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_compression_level_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let expected = reader_from_records::<V4>(2, records, 1)?
+        .records()
+        .collect::<io::Result<Vec<_>>>()?;
+
+    for level in [0, 9] {
+        assert_eq!(read_all_with_level::<V4>(2, records, level)?, expected);
+    }
+
+    Ok(())
+}
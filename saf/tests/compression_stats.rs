@@ -0,0 +1,54 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_compression_stats_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr2:5 [-2., -1., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    let stats = reader.compression_stats()?;
+    assert_eq!(stats.uncompressed_position_bytes(), 8 + 3 * 4);
+    assert_eq!(stats.uncompressed_item_bytes(), 8 + 3 * 3 * 4);
+    assert_eq!(
+        stats.compressed_position_bytes(),
+        reader.position_reader().get_ref().get_ref().len() as u64
+    );
+    assert_eq!(
+        stats.compressed_item_bytes(),
+        reader.item_reader().get_ref().get_ref().len() as u64
+    );
+
+    // Reader position is restored
+    reader.read_record(&mut record)?;
+    assert_eq!(record.position(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_compression_stats_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1.],
+        chr2:1 [nil; -1., 0., -2.],
+        chr2:2 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(4, records, 1)?;
+
+    let stats = reader.compression_stats()?;
+    assert_eq!(stats.uncompressed_position_bytes(), 8 + 3 * 4);
+    assert_eq!(stats.uncompressed_item_bytes(), 8 + 3 * 8 + 6 * 4);
+
+    Ok(())
+}
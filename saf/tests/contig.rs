@@ -0,0 +1,71 @@
+use std::io;
+
+use angsd_saf::{
+    record::{Contig, Site},
+    version::{V3, V4},
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, reader_from_records};
+
+#[test]
+fn test_v3_read_contig() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1.], chr1:4 [-1., 0.],
+        chr2:2 [-2., 0.],
+        chr3:5 [0., -3.], chr3:6 [-3., 0.], chr3:9 [-1., -1.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(get_alleles_v3(records), records, 1)?;
+
+    let mut contigs = Vec::new();
+    while let Some(contig) = reader.read_contig()? {
+        contigs.push(contig);
+    }
+
+    let names = contigs.iter().map(Contig::name).collect::<Vec<_>>();
+    assert_eq!(names, ["chr1", "chr2", "chr3"]);
+
+    let sites = contigs
+        .iter()
+        .map(|contig| {
+            contig
+                .sites()
+                .iter()
+                .map(Site::position)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(sites, vec![vec![1, 4], vec![2], vec![5, 6, 9]]);
+
+    assert_eq!(contigs[2].sites()[2].item(), records[5].item());
+
+    Ok(())
+}
+
+#[test]
+fn test_v4_read_contig_partial() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0.], chr1:2 [nil; -1.],
+        chr2:3 [0., -1.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(get_alleles_v4(records), records, 1)?;
+
+    // Read first record, so that the remainder of the first contig is read
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    let contig = reader.read_contig()?.expect("no contig");
+    assert_eq!(contig.name(), "chr1");
+    assert_eq!(contig.sites().len(), 1);
+    assert_eq!(contig.sites()[0].item(), records[1].item());
+
+    let contig = reader.read_contig()?.expect("no contig");
+    assert_eq!(contig.name(), "chr2");
+    assert_eq!(contig.sites()[0].position(), 3);
+
+    assert!(reader.read_contig()?.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::io;
+
+use angsd_saf::{version::V3, ReadStatus};
+
+pub mod utils;
+use utils::{reader_from_writer, setup_writer};
+
+#[test]
+fn test_read_v3_with_contig_alleles_override() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chrX:1 [0., -1.],
+        chrX:5 [-1., 0.],
+        chr2:3 [-2., -1., 0.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    writer.set_contig_alleles("chrX", 1);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+
+    assert_eq!(reader.index().records()[1].alleles(), Some(1));
+    assert_eq!(reader.index().contig_alleles(0), 2);
+    assert_eq!(reader.index().contig_alleles(1), 1);
+    assert_eq!(reader.index().contig_alleles(2), 2);
+
+    let mut record = reader.create_record_buf();
+    for expected in records.iter() {
+        reader.read_record(&mut record)?;
+        assert_eq!(record.clone().to_named(reader.index()), *expected);
+    }
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::Done);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_contig_alleles_while_writing_contig() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chrX:1 [0., -1.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    writer.set_contig_alleles("chrX", 1);
+
+    let reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().records()[0].alleles(), None);
+    assert_eq!(reader.index().records()[1].alleles(), Some(1));
+
+    Ok(())
+}
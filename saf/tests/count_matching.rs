@@ -0,0 +1,50 @@
+use std::io;
+
+use angsd_saf::version::V3;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_count_matching_even_positions() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1, chr1:2, chr1:4, chr1:7,
+        chr2:2, chr2:3,
+        chr3:10,
+    ];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    // Read first record to check that counting is independent of location
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    assert_eq!(reader.count_matching(|position| position % 2 == 0)?, 4);
+    assert_eq!(reader.count_matching(|position| position > 100)?, 0);
+
+    // Reading is unaffected
+    reader.read_record(&mut record)?;
+    assert_eq!(record.position(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_count_matching_error_restores_position() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:3];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    *reader.index_mut().records_mut()[1].sites_mut() += 1;
+
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    let error = reader.count_matching(|_| true).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    // Reading is unaffected
+    reader.read_record(&mut record)?;
+    assert_eq!(record.position(), 2);
+
+    Ok(())
+}
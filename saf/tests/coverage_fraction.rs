@@ -0,0 +1,37 @@
+use std::io;
+
+use angsd_saf::version::V4;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_coverage_fraction() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2., -3.],
+        chr1:2 [nil; -1., 0.],
+        chr2:1 [nil, nil; 0.],
+        chr2:5 [nil, nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(3, records, 1)?;
+
+    let fraction = reader.coverage_fraction()?;
+    let expected = (4. / 4. + 2. / 4. + 1. / 4. + 1. / 4.) / 4.;
+    assert!((fraction - expected).abs() < 1e-12);
+
+    Ok(())
+}
+
+#[test]
+fn test_coverage_fraction_no_records() -> io::Result<()> {
+    let records = records_v4![chr1:1 [0., -1.]];
+
+    let mut reader = reader_from_records::<V4>(1, records, 1)?;
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    assert!(reader.coverage_fraction()?.is_nan());
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::io;
+
+use angsd_saf::{reader::depth_reader_from_prefix, version::V3};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_read_with_depth() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1.],
+        chr1:3 [-1., 0.],
+        chr2:2 [-2., 0.],
+    ];
+    let depths = [4u32, 0, 12];
+
+    let mut data = Vec::new();
+    {
+        let mut writer = bgzf::Writer::new(&mut data);
+        for depth in depths {
+            io::Write::write_all(&mut writer, &depth.to_le_bytes())?;
+        }
+        writer.finish()?;
+    }
+
+    let reader = reader_from_records::<V3>(1, records, 1)?;
+    let mut reader = reader.with_depth(Some(bgzf::Reader::new(&data[..])));
+
+    let mut record = reader.get_ref().create_record_buf();
+    let mut depth = None;
+    let mut read = Vec::new();
+    while reader.read_record(&mut record, &mut depth)?.is_not_done() {
+        read.push((
+            *record.contig_id(),
+            record.position(),
+            record.item()[0],
+            depth,
+        ));
+    }
+
+    assert_eq!(
+        read,
+        [
+            (0, 1, 0., Some(4)),
+            (0, 3, -1., Some(0)),
+            (1, 2, -2., Some(12)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_without_depth() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr2:2 [-1.]];
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut reader = reader.with_depth(None::<&[u8]>);
+
+    let mut record = reader.get_ref().create_record_buf();
+    let mut depth = Some(1);
+    let mut n = 0;
+    while reader.read_record(&mut record, &mut depth)?.is_not_done() {
+        assert_eq!(depth, None);
+        n += 1;
+    }
+    assert_eq!(n, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_with_truncated_depth() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr2:2 [-1.]];
+    let data = 5u32.to_le_bytes();
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut reader = reader.with_depth(Some(&data[..]));
+
+    let mut record = reader.get_ref().create_record_buf();
+    let mut depth = None;
+    assert!(reader.read_record(&mut record, &mut depth)?.is_not_done());
+    assert_eq!(depth, Some(5));
+    assert!(reader.read_record(&mut record, &mut depth).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_read_with_trailing_depth() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.]];
+    let data = [5u32, 6]
+        .iter()
+        .flat_map(|depth| depth.to_le_bytes())
+        .collect::<Vec<_>>();
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut reader = reader.with_depth(Some(&data[..]));
+
+    let mut record = reader.get_ref().create_record_buf();
+    let mut depth = None;
+    assert!(reader.read_record(&mut record, &mut depth)?.is_not_done());
+    assert_eq!(depth, Some(5));
+
+    let error = reader.read_record(&mut record, &mut depth).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
+
+#[test]
+fn test_depth_reader_from_missing_prefix() -> io::Result<()> {
+    let prefix = std::env::temp_dir().join("angsd-saf-depth-missing-prefix");
+
+    assert!(depth_reader_from_prefix(prefix)?.is_none());
+
+    Ok(())
+}
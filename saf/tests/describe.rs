@@ -0,0 +1,38 @@
+use std::io;
+
+use angsd_saf::{describe_readers, version::V3};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_describe_readers() -> io::Result<()> {
+    let fst = records_v3![chr1:1, chr1:2, chr2:1];
+    let snd = records_v3![chr1:1, chr2:4, chr3:1, chr3:5];
+
+    let readers = vec![
+        reader_from_records::<V3>(4, fst, 1)?,
+        reader_from_records::<V3>(6, snd, 1)?,
+    ];
+
+    let description = describe_readers(&readers);
+
+    let [fst, snd] = description.readers() else {
+        panic!("expected two reader descriptions")
+    };
+    assert_eq!((fst.alleles(), fst.contigs(), fst.sites()), (4, 2, 3));
+    assert_eq!((snd.alleles(), snd.contigs(), snd.sites()), (6, 3, 4));
+
+    assert_eq!(description.dimensions(), 2);
+    assert_eq!(description.shape(), vec![5, 7]);
+    assert_eq!(description.sfs_len(), 35);
+
+    assert_eq!(
+        description.to_string(),
+        "reader 1: 4 alleles, 2 contigs, 3 sites\n\
+        reader 2: 6 alleles, 3 contigs, 4 sites\n\
+        joint SFS: 2 dimensions, shape 5/7, 35 values"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::io;
+
+use angsd_saf::{reader::SafEvent, version::V3};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_events() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr1:3 [1.], chr2:2 [2.]];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let events = reader
+        .events()
+        .map(|event| {
+            event.map(|event| match event {
+                SafEvent::ContigStart { name } => format!("start {name}"),
+                SafEvent::Site(record) => format!("site {}", record.position()),
+                SafEvent::End => String::from("end"),
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        events,
+        [
+            "start chr1",
+            "site 1",
+            "site 3",
+            "start chr2",
+            "site 2",
+            "end"
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_events_site_records() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr2:2 [2.]];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut events = reader.events();
+
+    assert!(matches!(
+        events.next(),
+        Some(Ok(SafEvent::ContigStart { .. }))
+    ));
+    match events.next() {
+        Some(Ok(SafEvent::Site(record))) => {
+            assert_eq!(record.contig_id(), &0);
+            assert_eq!(record.item().as_ref(), &[0.]);
+        }
+        _ => panic!("expected site event"),
+    }
+    assert!(matches!(
+        events.next(),
+        Some(Ok(SafEvent::ContigStart { .. }))
+    ));
+    assert!(matches!(events.next(), Some(Ok(SafEvent::Site(_)))));
+    assert!(matches!(events.next(), Some(Ok(SafEvent::End))));
+    assert!(events.next().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::io;
+
+use angsd_saf::{
+    record::{Band, Record},
+    version::{V3, V4},
+    ReadStatus,
+};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_filter_finite_v3() -> io::Result<()> {
+    let records = &[
+        Record::new("chr1", 1, vec![0., -1.].into()),
+        Record::new("chr1", 2, vec![f32::NAN, 0.].into()),
+        Record::new("chr1", 3, vec![-1., 0.].into()),
+        Record::new("chr2", 1, vec![0., f32::INFINITY].into()),
+        Record::new("chr2", 2, vec![-2., 0.].into()),
+    ];
+
+    let mut reader = reader_from_records::<V3>(1, records, 1)?.filter_finite();
+    let mut record = reader.get_ref().create_record_buf();
+
+    for i in [0, 2, 4] {
+        assert_eq!(reader.read_record(&mut record)?, ReadStatus::NotDone);
+        assert_eq!(
+            record.clone().to_named(reader.get_ref().index()),
+            records[i]
+        );
+    }
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::Done);
+    assert_eq!(reader.dropped(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_finite_v4() -> io::Result<()> {
+    let records = &[
+        Record::new("chr1", 1, Band::new(0, vec![f32::NEG_INFINITY])),
+        Record::new("chr1", 2, Band::new(1, vec![-1., 0.])),
+    ];
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?.filter_finite();
+    let mut record = reader.get_ref().create_record_buf();
+
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::NotDone);
+    assert_eq!(record.position(), 2);
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::Done);
+    assert_eq!(reader.dropped(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::io;
+
+use angsd_saf::{
+    version::{V3, V4},
+    ReadStatus,
+};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_filter_informative_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -3., -6.],
+        chr1:2 [-1., -1., -1.],
+        chr2:1 [-1., -1.5, -1.],
+        chr2:4 [-5., 0., -5.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?.filter_informative(1.);
+    let mut record = reader.get_ref().create_record_buf();
+
+    for i in [0, 3] {
+        assert_eq!(reader.read_record(&mut record)?, ReadStatus::NotDone);
+        assert_eq!(
+            record.clone().to_named(reader.get_ref().index()),
+            records[i]
+        );
+    }
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::Done);
+    assert_eq!(reader.dropped(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_informative_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., 0.],
+        chr1:2 [nil; -2., 0.],
+        chr1:3 [0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?.filter_informative(1.);
+    let mut record = reader.get_ref().create_record_buf();
+
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::NotDone);
+    assert_eq!(record.position(), 2);
+    assert_eq!(reader.read_record(&mut record)?, ReadStatus::Done);
+    assert_eq!(reader.dropped(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_fold_likelihoods_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2., -3., -4.],
+        chr1:2 [-1., 0., -2., -0.5, -3.],
+        chr2:1 [-2., -1., 0., -1., -2.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(4, records, 1)?.fold_likelihoods();
+
+    let mut record = reader.create_record_buf();
+    assert_eq!(record.item().len(), 3);
+
+    for expected in records.iter() {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+
+        let expected = expected.fold();
+        assert_eq!(record.position(), expected.position());
+        assert_eq!(record.item(), expected.item());
+    }
+
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_fold_likelihoods_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2., -3.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(3, records, 1)?.fold_likelihoods();
+
+    let mut record = reader.create_record_buf();
+    assert_eq!(record.item().len(), 2);
+
+    for expected in records.iter() {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+
+        let expected = expected.fold(3, f32::NEG_INFINITY);
+        assert_eq!(record.position(), expected.position());
+        assert_eq!(record.item(), expected.item());
+    }
+
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::io;
+
+use angsd_saf::{
+    record::{Band, Likelihoods},
+    version::{V3, V4},
+    Record,
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, reader_from_records};
+
+#[test]
+fn test_read_record_freq_range_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2., -3., -4.],
+        chr1:3 [-4., -3., -2., -1., 0.],
+        chr2:2 [-1., 0., -1., -2., -3.],
+    ];
+    let alleles = get_alleles_v3(records);
+
+    for (lo, hi) in [(0, 4), (0, 0), (4, 4), (1, 3), (2, 4)] {
+        let mut reader = reader_from_records::<V3>(alleles, records, 1)?;
+        let mut full_reader = reader_from_records::<V3>(alleles, records, 1)?;
+
+        let mut buf = Record::from_alleles(0, 1, hi - lo);
+        let mut full_buf = full_reader.create_record_buf();
+
+        while reader
+            .read_record_freq_range(&mut buf, lo, hi)?
+            .is_not_done()
+        {
+            assert!(full_reader.read_record(&mut full_buf)?.is_not_done());
+
+            assert_eq!(buf.contig_id(), full_buf.contig_id());
+            assert_eq!(buf.position(), full_buf.position());
+            assert_eq!(buf.item().as_ref(), &full_buf.item()[lo..=hi]);
+        }
+
+        assert!(full_reader.read_record(&mut full_buf)?.is_done());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_read_record_freq_range_v3_invalid() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0., -1., -2.]];
+    let mut reader = reader_from_records::<V3>(get_alleles_v3(records), records, 1)?;
+
+    let mut buf = Record::new(0, 1, Likelihoods::from(vec![0.; 2]));
+
+    assert!(reader.read_record_freq_range(&mut buf, 2, 1).is_err());
+    assert!(reader.read_record_freq_range(&mut buf, 2, 3).is_err());
+    assert!(reader.read_record_freq_range(&mut buf, 0, 2).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_read_record_freq_range_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil, nil; -1., 0., -1.],
+        chr2:1 [nil, nil, nil, nil; 0.],
+    ];
+    let alleles = get_alleles_v4(records);
+
+    let mut reader = reader_from_records::<V4>(alleles, records, 1)?;
+    let mut buf = reader.create_record_buf();
+
+    reader.read_record_freq_range(&mut buf, 1, 3)?;
+    assert_eq!(buf.item(), &Band::new(1, vec![-1., -2.]));
+
+    reader.read_record_freq_range(&mut buf, 1, 3)?;
+    assert_eq!(buf.item(), &Band::new(2, vec![-1., 0.]));
+
+    reader.read_record_freq_range(&mut buf, 1, 3)?;
+    assert_eq!(*buf.contig_id(), 1);
+    assert_eq!(buf.item(), &Band::new(1, Vec::new()));
+
+    assert!(reader.read_record_freq_range(&mut buf, 1, 3)?.is_done());
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{
+    index::IndexBuilder,
+    version::{Version, V3, V4},
+    Index, Reader,
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, setup_writer, MockWriter};
+
+type BgzfReader = bgzf::Reader<io::Cursor<Vec<u8>>>;
+
+fn finish<V>(writer: MockWriter<V>) -> io::Result<(Index<V>, BgzfReader, BgzfReader)>
+where
+    V: Version,
+{
+    let (mut index_reader, mut position_reader, mut item_reader) = writer.finish()?;
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+    item_reader.seek(io::SeekFrom::Start(0))?;
+
+    Ok((
+        Index::read(&mut index_reader)?,
+        bgzf::Reader::new(position_reader),
+        bgzf::Reader::new(item_reader),
+    ))
+}
+
+#[test]
+fn test_index_builder_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.], chr1:9 [-1., 0., -2.],
+        chr2:10 [-2., -1., 0.],
+        chr3:11 [0., -3., -1.], chr3:12 [0., -4., -2.],
+    ];
+
+    let mut writer = setup_writer::<V3>(get_alleles_v3(records))?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (index, mut position_reader, mut item_reader) = finish(writer)?;
+
+    let mut builder = IndexBuilder::<V3>::new(2);
+    builder.add_contig(String::from("chr1"), 3);
+    builder.add_contig(String::from("chr2"), 1);
+    builder.add_contig(String::from("chr3"), 2);
+    let rebuilt = builder.build(&mut position_reader, &mut item_reader)?;
+    assert_eq!(rebuilt, index);
+
+    // Seek using the rebuilt index
+    let mut position_data = position_reader.into_inner();
+    let mut item_data = item_reader.into_inner();
+    position_data.seek(io::SeekFrom::Start(0))?;
+    item_data.seek(io::SeekFrom::Start(0))?;
+
+    let mut reader = Reader::from_bgzf(
+        rebuilt,
+        bgzf::Reader::new(position_data),
+        bgzf::Reader::new(item_data),
+    )
+    .expect("non-empty index");
+    reader.read_magic()?;
+    reader.seek_by_name("chr3")?;
+
+    let mut record = reader.create_record_buf();
+    assert!(reader.read_record(&mut record)?.is_not_done());
+    assert_eq!(record.clone().to_named(reader.index()), records[4]);
+
+    Ok(())
+}
+
+#[test]
+fn test_index_builder_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1.], chr1:2 [-2., -1., 0., -1.],
+        chr2:2 [nil, nil; 0.],
+    ];
+
+    let mut writer = setup_writer::<V4>(get_alleles_v4(records))?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (index, mut position_reader, mut item_reader) = finish(writer)?;
+
+    let mut builder = IndexBuilder::<V4>::new(get_alleles_v4(records));
+    builder.add_contig(String::from("chr1"), 2);
+    builder.add_contig(String::from("chr2"), 1);
+    let rebuilt = builder.build(&mut position_reader, &mut item_reader)?;
+
+    assert_eq!(rebuilt, index);
+    assert_eq!(rebuilt.records()[0].sum_band(), 6);
+
+    // Seek using the rebuilt index
+    let mut position_data = position_reader.into_inner();
+    let mut item_data = item_reader.into_inner();
+    position_data.seek(io::SeekFrom::Start(0))?;
+    item_data.seek(io::SeekFrom::Start(0))?;
+
+    let mut reader = Reader::from_bgzf(
+        rebuilt,
+        bgzf::Reader::new(position_data),
+        bgzf::Reader::new(item_data),
+    )
+    .expect("non-empty index");
+    reader.read_magic()?;
+    reader.seek_by_name("chr2")?;
+
+    let mut record = reader.create_record_buf();
+    assert!(reader.read_record(&mut record)?.is_not_done());
+    assert_eq!(record.clone().to_named(reader.index()), records[2]);
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_index_builder_mismatched_sites() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr1:2 [0.], chr2:1 [0.]];
+
+    for sites in [[2, 2], [1, 1]] {
+        let mut writer = setup_writer::<V3>(0)?;
+        for record in records.iter() {
+            writer.write_record(record)?;
+        }
+        let (_, mut position_reader, mut item_reader) = finish(writer)?;
+
+        let mut builder = IndexBuilder::<V3>::new(0);
+        builder.add_contig(String::from("chr1"), sites[0]);
+        builder.add_contig(String::from("chr2"), sites[1]);
+
+        assert!(builder
+            .build(&mut position_reader, &mut item_reader)
+            .is_err());
+    }
+
+    Ok(())
+}
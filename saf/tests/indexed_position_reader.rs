@@ -0,0 +1,68 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{reader::PositionReader, version::V3, Index};
+
+pub mod utils;
+use utils::setup_writer;
+
+#[test]
+fn test_indexed_position_reader() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0.],
+        chr1:4 [0.],
+        chr2:2 [0.],
+        chr3:1 [0.],
+        chr3:7 [0.],
+        chr3:9 [0.],
+    ];
+
+    let mut writer = setup_writer::<V3>(0)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (mut index_reader, mut position_reader, _) = writer.finish()?;
+
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+
+    let index = Index::<V3>::read(&mut index_reader)?;
+
+    let mut reader = PositionReader::new(bgzf::Reader::new(position_reader));
+    reader.read_magic::<V3>()?;
+    let mut reader = reader.with_index(index);
+
+    let mut positions = Vec::new();
+    while let Some(position) = reader.read_position()? {
+        positions.push(position);
+    }
+
+    assert_eq!(positions, [(0, 1), (0, 4), (1, 2), (2, 1), (2, 7), (2, 9)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_indexed_position_reader_too_many_positions() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr1:2 [0.]];
+
+    let mut writer = setup_writer::<V3>(0)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (mut index_reader, mut position_reader, _) = writer.finish()?;
+
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+
+    let mut index = Index::<V3>::read(&mut index_reader)?;
+    *index.records_mut()[0].sites_mut() = 1;
+
+    let mut reader = PositionReader::new(bgzf::Reader::new(position_reader));
+    reader.read_magic::<V3>()?;
+    let mut reader = reader.with_index(index);
+
+    assert_eq!(reader.read_position()?, Some((0, 1)));
+    assert!(reader.read_position().is_err());
+
+    Ok(())
+}
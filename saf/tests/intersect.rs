@@ -1,12 +1,15 @@
 use std::{fmt, io};
 
 use angsd_saf::{
+    reader::NoSharedContigs,
     version::{Version, V3, V4},
     Intersect, Record,
 };
 
 pub mod utils;
-use utils::{get_alleles_v3, get_alleles_v4, reader_from_records};
+use utils::{
+    get_alleles_v3, get_alleles_v4, reader_from_records, reader_from_writer, setup_writer,
+};
 
 /// Returns record with the same contig id and position as `target` in `records`, if it exists.
 fn find_intersection<'a, V>(
@@ -67,17 +70,20 @@ where
     V: Version,
     V::Item: Clone + fmt::Debug + PartialEq,
 {
-    let mut intersect = all_alleles
-        .iter()
-        .zip(all_records)
-        .map(|(&alleles, records)| reader_from_records::<V>(alleles, records, threads))
-        .collect::<io::Result<Vec<_>>>()
-        .map(Intersect::new)?;
+    let setup_intersect = || {
+        all_alleles
+            .iter()
+            .zip(all_records)
+            .map(|(&alleles, records)| reader_from_records::<V>(alleles, records, threads))
+            .collect::<io::Result<Vec<_>>>()
+            .map(Intersect::new)
+    };
+    let mut intersect = setup_intersect()?;
 
     let all_expected_records = brute_force_intersect::<V>(all_records);
 
     let mut bufs = intersect.create_record_bufs();
-    for expected_records in all_expected_records {
+    for expected_records in all_expected_records.iter() {
         intersect.read_records(&mut bufs)?;
         let read_records = bufs
             .iter()
@@ -85,11 +91,54 @@ where
             .map(|(buf, reader)| buf.clone().to_named(reader.index()))
             .collect::<Vec<_>>();
 
-        assert_eq!(read_records, expected_records);
+        assert_eq!(&read_records, expected_records);
     }
 
     assert!(intersect.read_records(&mut bufs)?.is_done());
 
+    // Iterating over the intersection gives the same records
+    let mut intersect = setup_intersect()?;
+    let iterated_records = intersect.records().collect::<io::Result<Vec<_>>>()?;
+    let iterated_records = iterated_records
+        .into_iter()
+        .map(|records| {
+            records
+                .into_iter()
+                .zip(intersect.get_readers())
+                .map(|(record, reader)| record.to_named(reader.index()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(iterated_records, all_expected_records);
+
+    // Writing the intersection gives files with the intersecting records of each reader
+    if all_expected_records.is_empty() {
+        // Readers cannot be created for files without records
+        return Ok(());
+    }
+
+    let mut intersect = setup_intersect()?;
+    let mut writers = all_alleles
+        .iter()
+        .map(|&alleles| setup_writer::<V>(alleles))
+        .collect::<io::Result<Vec<_>>>()?;
+    intersect.write_intersection(&mut writers)?;
+
+    for (i, writer) in writers.into_iter().enumerate() {
+        let mut reader = reader_from_writer(writer, threads)?;
+        let written_records = reader.records().collect::<io::Result<Vec<_>>>()?;
+        let written_records = written_records
+            .into_iter()
+            .map(|record| record.to_named(reader.index()))
+            .collect::<Vec<_>>();
+
+        let expected_records = all_expected_records
+            .iter()
+            .map(|records| records[i].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(written_records, expected_records);
+    }
+
     Ok(())
 }
 
@@ -214,3 +263,243 @@ fn test_intersect_three_v4() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_intersect_skip_to_contig() -> io::Result<()> {
+    let fst = records_v3![
+        chr1:1, chr1:2,
+        chr2:1, chr2:3, chr2:4,
+        chr3:1,
+        chr5:1,
+    ];
+    let snd = records_v3![
+        chr1:2,
+        chr2:2, chr2:3, chr2:4,
+        chr4:1,
+        chr5:1,
+    ];
+
+    let mut intersect =
+        reader_from_records::<V3>(0, fst, 1)?.intersect(reader_from_records::<V3>(0, snd, 1)?);
+
+    assert!(!intersect.skip_to_contig("chr3")?);
+    assert!(!intersect.skip_to_contig("chr6")?);
+    assert!(intersect.skip_to_contig("chr2")?);
+
+    let mut bufs = intersect.create_record_bufs();
+    for expected_position in [3, 4] {
+        assert!(intersect.read_records(&mut bufs)?.is_not_done());
+        let [fst_record, snd_record] = &bufs[..] else {
+            unreachable!()
+        };
+        assert_eq!(fst_record.contig_id(), &1);
+        assert_eq!(snd_record.contig_id(), &1);
+        assert_eq!(fst_record.position(), expected_position);
+        assert_eq!(snd_record.position(), expected_position);
+    }
+
+    assert!(intersect.read_records(&mut bufs)?.is_not_done());
+    assert_eq!(bufs[0].contig_id(), &3);
+    assert_eq!(bufs[1].contig_id(), &3);
+
+    assert!(intersect.read_records(&mut bufs)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_collect_matrices_v3() -> io::Result<()> {
+    let fst = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr2:3 [-2., -1., 0.],
+    ];
+    let snd = records_v3![
+        chr1:2 [0., -1.],
+        chr2:3 [-1., 0.],
+        chr2:4 [-2., 0.],
+    ];
+
+    let mut intersect =
+        reader_from_records::<V3>(2, fst, 1)?.intersect(reader_from_records::<V3>(1, snd, 1)?);
+
+    let matrices = intersect.collect_matrices()?;
+    assert_eq!(
+        matrices,
+        vec![
+            vec![vec![-1., 0., -2.], vec![-2., -1., 0.]],
+            vec![vec![0., -1.], vec![-1., 0.]],
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_collect_matrices_v4() -> io::Result<()> {
+    let fst = records_v4![
+        chr1:1 [nil; 0., -1.],
+        chr1:2 [0.],
+    ];
+    let snd = records_v4![
+        chr1:1 [nil, nil; 0.],
+    ];
+
+    let mut intersect =
+        reader_from_records::<V4>(3, fst, 1)?.intersect(reader_from_records::<V4>(2, snd, 1)?);
+
+    let matrices = intersect.collect_matrices(f32::NEG_INFINITY)?;
+    let inf = f32::NEG_INFINITY;
+    assert_eq!(
+        matrices,
+        vec![vec![vec![inf, 0., -1., inf]], vec![vec![inf, inf, 0.]]]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_no_shared_contigs() -> io::Result<()> {
+    let fst = records_v3![chr1:1, chr1:2, chr2:1];
+    let snd = records_v3![chr3:1, chr4:2];
+
+    let mut intersect =
+        reader_from_records::<V3>(0, fst, 1)?.intersect(reader_from_records::<V3>(0, snd, 1)?);
+    assert!(!intersect.has_shared_contigs());
+
+    let mut bufs = intersect.create_record_bufs();
+    assert!(intersect.read_records(&mut bufs)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_error_on_no_shared_contigs() -> io::Result<()> {
+    let fst = records_v3![chr1:1, chr1:2, chr2:1];
+    let snd = records_v3![chr3:1, chr4:2];
+
+    let mut intersect =
+        reader_from_records::<V3>(0, fst, 1)?.intersect(reader_from_records::<V3>(0, snd, 1)?);
+    intersect.set_error_on_no_shared_contigs(true);
+
+    let mut bufs = intersect.create_record_bufs();
+    let error = intersect.read_records(&mut bufs).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(error.get_ref().unwrap().is::<NoSharedContigs>());
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_error_on_no_shared_contigs_with_shared() -> io::Result<()> {
+    let fst = records_v3![chr1:1, chr1:2, chr2:1];
+    let snd = records_v3![chr1:2, chr4:2];
+
+    let mut intersect =
+        reader_from_records::<V3>(0, fst, 1)?.intersect(reader_from_records::<V3>(0, snd, 1)?);
+    assert!(intersect.has_shared_contigs());
+    intersect.set_error_on_no_shared_contigs(true);
+
+    let mut bufs = intersect.create_record_bufs();
+    assert!(intersect.read_records(&mut bufs)?.is_not_done());
+    assert_eq!(bufs[0].position(), 2);
+    assert!(intersect.read_records(&mut bufs)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_write_intersection_writer_count_mismatch() -> io::Result<()> {
+    let left = records_v3![chr1:1 [0., -1., -2.]];
+    let right = records_v3![chr1:1 [-1., 0., -2.]];
+
+    let mut intersect = Intersect::new(vec![
+        reader_from_records::<V3>(2, left, 1)?,
+        reader_from_records::<V3>(2, right, 1)?,
+    ]);
+    let mut writers = vec![setup_writer::<V3>(2)?];
+
+    let error = intersect.write_intersection(&mut writers).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+fn test_intersect_unordered<V>(
+    all_alleles: &[usize],
+    all_records: &[&[Record<&'static str, V::Item>]],
+) -> io::Result<()>
+where
+    V: Version,
+    V::Item: Clone + fmt::Debug + PartialEq,
+{
+    let readers = all_alleles
+        .iter()
+        .zip(all_records)
+        .map(|(&alleles, records)| reader_from_records::<V>(alleles, records, 1))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut intersect = Intersect::new_unordered(readers);
+
+    let read_records = intersect.records().collect::<io::Result<Vec<_>>>()?;
+    let read_records = read_records
+        .into_iter()
+        .map(|records| {
+            records
+                .into_iter()
+                .zip(intersect.get_readers())
+                .map(|(record, reader)| record.to_named(reader.index()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(read_records, brute_force_intersect::<V>(all_records));
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_unordered_v3() -> io::Result<()> {
+    let all_records: &[&[_]] = &[
+        records_v3![chr1:1, chr1:2, chr1:4, chr2:1, chr2:3, chr3:1],
+        records_v3![chr2:1, chr2:2, chr2:3, chr3:1, chr1:2, chr1:3, chr1:4],
+    ];
+    let all_alleles = all_records
+        .iter()
+        .map(|records| get_alleles_v3(records))
+        .collect::<Vec<_>>();
+
+    test_intersect_unordered::<V3>(&all_alleles, all_records)
+}
+
+#[test]
+fn test_intersect_unordered_v4() -> io::Result<()> {
+    let all_records: &[&[_]] = &[
+        records_v4![chr1:1, chr1:2, chr2:1, chr2:5, chr4:1],
+        records_v4![chr4:1, chr2:1, chr2:3, chr2:5, chr1:2],
+        records_v4![chr2:5, chr3:1, chr1:1, chr1:2, chr4:1],
+    ];
+    let all_alleles = all_records
+        .iter()
+        .map(|records| get_alleles_v4(records))
+        .collect::<Vec<_>>();
+
+    test_intersect_unordered::<V4>(&all_alleles, all_records)
+}
+
+#[test]
+fn test_intersect_ordered_misses_unordered_contigs() -> io::Result<()> {
+    let fst = records_v3![chr1:1, chr2:1];
+    let snd = records_v3![chr2:1, chr1:1];
+
+    let mut intersect =
+        reader_from_records::<V3>(0, fst, 1)?.intersect(reader_from_records::<V3>(0, snd, 1)?);
+    assert_eq!(intersect.records().count(), 1);
+
+    let mut intersect = Intersect::new_unordered(vec![
+        reader_from_records::<V3>(0, fst, 1)?,
+        reader_from_records::<V3>(0, snd, 1)?,
+    ]);
+    assert_eq!(intersect.records().count(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{
+    version::{Version, V3},
+    Index, Reader,
+};
+
+pub mod utils;
+use utils::setup_writer;
+
+/// The position file, index, and a separate item file for each contig of a written file.
+struct Sharded {
+    position_reader: io::Cursor<Vec<u8>>,
+    index: Index<V3>,
+    shards: Vec<Vec<u8>>,
+}
+
+fn write_sharded() -> io::Result<Sharded> {
+    let records = records_v3![
+        chr1:1 [0., -1.],
+        chr1:2 [-1., 0.],
+        chr2:1 [-2., 0.],
+        chr3:4 [0., -3.],
+        chr3:8 [0., -4.],
+    ];
+
+    let mut writer = setup_writer::<V3>(1)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (mut index_reader, mut position_reader, _) = writer.finish()?;
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+    let index = Index::read(&mut index_reader)?;
+
+    let shards = ["chr1", "chr2", "chr3"]
+        .iter()
+        .map(|name| {
+            let mut writer = setup_writer::<V3>(1)?;
+            for record in records.iter().filter(|record| record.contig_id() == name) {
+                writer.write_record(record)?;
+            }
+            writer
+                .finish()
+                .map(|(_, _, item_writer)| item_writer.into_inner())
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    Ok(Sharded {
+        position_reader,
+        index,
+        shards,
+    })
+}
+
+fn reader_from_sharded(sharded: Sharded) -> io::Result<Reader<io::Cursor<Vec<u8>>, V3>> {
+    let Sharded {
+        position_reader,
+        index,
+        shards,
+    } = sharded;
+
+    let mut position_reader = bgzf::Reader::new(position_reader);
+    V3::read_magic(&mut position_reader)?;
+
+    Reader::from_bgzf_with_item_resolver(index, position_reader, move |contig_id| {
+        let mut item_reader = bgzf::Reader::new(io::Cursor::new(shards[contig_id].clone()));
+        V3::read_magic(&mut item_reader)?;
+        Ok(item_reader)
+    })
+}
+
+#[test]
+fn test_item_resolver_read() -> io::Result<()> {
+    let mut reader = reader_from_sharded(write_sharded()?)?;
+
+    let mut record = reader.create_record_buf();
+    let mut read = Vec::new();
+    while reader.read_record(&mut record)?.is_not_done() {
+        read.push((*record.contig_id(), record.position(), record.item()[0]));
+    }
+
+    assert_eq!(
+        read,
+        vec![(0, 1, 0.), (0, 2, -1.), (1, 1, -2.), (2, 4, 0.), (2, 8, 0.)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_item_resolver_seek() -> io::Result<()> {
+    let mut reader = reader_from_sharded(write_sharded()?)?;
+
+    let mut record = reader.create_record_buf();
+
+    reader.seek(2)?;
+    reader.read_record(&mut record)?;
+    assert_eq!((*record.contig_id(), record.position()), (2, 4));
+    assert_eq!(record.item().as_ref(), &[0., -3.]);
+
+    reader.seek(1)?;
+    reader.read_record(&mut record)?;
+    assert_eq!((*record.contig_id(), record.position()), (1, 1));
+    assert_eq!(record.item().as_ref(), &[-2., 0.]);
+
+    reader.read_record(&mut record)?;
+    assert_eq!((*record.contig_id(), record.position()), (2, 4));
+    assert_eq!(record.item().as_ref(), &[0., -3.]);
+
+    Ok(())
+}
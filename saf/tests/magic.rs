@@ -0,0 +1,44 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{
+    index,
+    reader::Builder,
+    version::{Version, V3, V4},
+    Index,
+};
+
+pub mod utils;
+use utils::{reader_from_records, setup_writer};
+
+#[test]
+fn test_magic_matches_file() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2];
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    assert_eq!(reader.magic(), &V3::MAGIC_NUMBER);
+
+    Ok(())
+}
+
+#[test]
+fn test_magic_stored_on_mismatch() -> io::Result<()> {
+    let mut writer = setup_writer::<V4>(0)?;
+    for record in records_v4![chr1:1].iter() {
+        writer.write_record(record)?;
+    }
+    let (_, mut position_reader, mut item_reader) = writer.finish()?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+    item_reader.seek(io::SeekFrom::Start(0))?;
+
+    let index = Index::<V3>::new(0, vec![index::Record::new("chr1".to_string(), 1, 8, 8)]);
+    let mut reader = Builder::v3()
+        .build(index, position_reader, item_reader)
+        .expect("empty index");
+    assert_eq!(reader.magic(), &[0; 8]);
+
+    let error = reader.read_magic().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(reader.magic(), &V4::MAGIC_NUMBER);
+
+    Ok(())
+}
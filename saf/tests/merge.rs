@@ -0,0 +1,95 @@
+use std::{io, path::Path};
+
+use angsd_saf::{merge, reader::Builder, record::Likelihoods, version::V3, Record, Writer};
+
+pub mod utils;
+use utils::TempPrefix;
+
+fn write_file(
+    prefix: &Path,
+    alleles: usize,
+    records: &[Record<&str, Likelihoods>],
+) -> io::Result<()> {
+    let mut writer = Writer::<_, V3>::from_prefix(alleles, prefix)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+fn merge_files(
+    name: &str,
+    inputs: [(usize, &[Record<&str, Likelihoods>]); 2],
+) -> io::Result<Vec<(String, u32, Vec<f32>)>> {
+    let prefixes = [0, 1].map(|i| TempPrefix::new(&format!("merge-{name}-{i}")));
+    let output = TempPrefix::new(&format!("merge-{name}-merged"));
+
+    for (prefix, (alleles, records)) in prefixes.iter().zip(inputs) {
+        write_file(prefix.as_ref(), alleles, records)?;
+    }
+
+    let mut writer = Writer::<_, V3>::from_prefix(inputs[0].0, &output)?;
+    merge(&mut writer, &prefixes)?;
+    writer.finish()?;
+
+    let mut reader = Builder::<V3>::default().build_from_prefix(&output)?;
+    let sites = reader
+        .index()
+        .records()
+        .iter()
+        .map(|record| (record.name().to_owned(), record.sites()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sites,
+        [("chr1", 2), ("chr2", 1), ("chr3", 3)].map(|(name, sites)| (name.to_owned(), sites))
+    );
+
+    let mut record = reader.create_record_buf();
+    let mut merged = Vec::new();
+    while reader.read_record(&mut record)?.is_not_done() {
+        let name = reader.index().records()[*record.contig_id()].name();
+        merged.push((name.to_owned(), record.position(), record.item().to_vec()));
+    }
+
+    Ok(merged)
+}
+
+#[test]
+fn test_merge() -> io::Result<()> {
+    let first = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+    ];
+    let second = records_v3![
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.], chr3:8 [0., 0., -2.],
+    ];
+
+    let merged = merge_files("valid", [(2, first), (2, second)])?;
+
+    let expected = first
+        .iter()
+        .chain(second.iter())
+        .map(|record| {
+            (
+                record.contig_id().to_string(),
+                record.position(),
+                record.item().to_vec(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(merged.len(), 6);
+    assert_eq!(merged, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_alleles_mismatch() {
+    let first = records_v3![chr1:1 [0., -1., -2.]];
+    let second = records_v3![chr3:4 [-2., 0., -1., 0., 0.]];
+
+    let error = merge_files("mismatch", [(2, first), (4, second)]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "mmap")]
+
+use std::io;
+
+use angsd_saf::{reader::Builder, version::V3, Writer};
+
+pub mod utils;
+use utils::TempPrefix;
+
+#[test]
+fn test_mmap_seek() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:3 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.],
+        chr4:6 [0., -2., -1.],
+    ];
+
+    let prefix = TempPrefix::new("mmap-seek");
+    let mut writer = Writer::<_, V3>::from_prefix(2, &prefix)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    let mut reader = unsafe { Builder::<V3>::default().build_from_prefix_mmap(&prefix)? };
+    let mut record = reader.create_record_buf();
+
+    for (name, position, likelihoods) in [
+        ("chr3", 4, [-2., 0., -1.]),
+        ("chr1", 1, [0., -1., -2.]),
+        ("chr4", 6, [0., -2., -1.]),
+        ("chr2", 3, [-2., -1., 0.]),
+        ("chr3", 4, [-2., 0., -1.]),
+    ] {
+        reader.seek_by_name(name)?;
+        reader.read_record(&mut record)?;
+
+        assert_eq!(reader.current_contig_name(), Some(name));
+        assert_eq!(record.position(), position);
+        assert_eq!(&record.item()[..], likelihoods);
+    }
+
+    Ok(())
+}
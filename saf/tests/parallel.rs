@@ -0,0 +1,115 @@
+use std::{fmt, io};
+
+use angsd_saf::{
+    record::{Band, Likelihoods, Record},
+    version::{Version, V3, V4},
+};
+
+pub mod utils;
+use utils::reader_from_records;
+
+/// Tests that reading in parallel gives the same records as reading on a single thread.
+fn test_parallel_matches_single<V>(
+    alleles: usize,
+    records: &[Record<&str, V::Item>],
+) -> io::Result<()>
+where
+    V: Version,
+    V::Item: Clone + fmt::Debug + PartialEq + Send + 'static,
+{
+    let mut single = reader_from_records::<V>(alleles, records, 1)?;
+    let mut parallel = reader_from_records::<V>(alleles, records, 1)?.into_parallel()?;
+
+    let mut single_record = single.create_record_buf();
+    let mut parallel_record = parallel.create_record_buf();
+
+    let mut n = 0;
+    while single.read_record(&mut single_record)?.is_not_done() {
+        assert!(parallel.read_record(&mut parallel_record)?.is_not_done());
+        assert_eq!(parallel_record, single_record);
+        n += 1;
+    }
+    assert!(parallel.read_record(&mut parallel_record)?.is_done());
+    assert_eq!(n, records.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_parallel_v3() -> io::Result<()> {
+    test_parallel_matches_single::<V3>(
+        2,
+        records_v3![
+            chr1:1 [0., -1., -2.],
+            chr1:2 [-1., 0., -2.],
+            chr2:5 [-2., -1., 0.],
+        ],
+    )
+}
+
+#[test]
+fn test_parallel_v4() -> io::Result<()> {
+    test_parallel_matches_single::<V4>(
+        4,
+        records_v4![
+            chr1:1 [0., -1.],
+            chr2:1 [nil; -1., 0., -2.],
+            chr2:2 [nil, nil; 0.],
+        ],
+    )
+}
+
+#[test]
+fn test_parallel_many_sites() -> io::Result<()> {
+    let records = (0..10_000)
+        .map(|i| {
+            let contig = if i < 6_000 { "chr1" } else { "chr2" };
+            Record::new(contig, i, Likelihoods::from(vec![i as f32, -1.]))
+        })
+        .collect::<Vec<_>>();
+
+    test_parallel_matches_single::<V3>(1, &records)?;
+
+    let records = records
+        .into_iter()
+        .map(|record| {
+            let (contig, position, item) =
+                (*record.contig_id(), record.position(), record.into_item());
+            Record::new(contig, position, Band::new(1, item.to_vec()))
+        })
+        .collect::<Vec<_>>();
+
+    test_parallel_matches_single::<V4>(2, &records)
+}
+
+#[test]
+fn test_parallel_mid_stream() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1, chr2:3];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    let mut parallel = reader.into_parallel()?;
+    for expected in &records[1..] {
+        assert!(parallel.read_record(&mut record)?.is_not_done());
+        assert_eq!(&record.clone().to_named(parallel.index()), expected);
+    }
+    assert!(parallel.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_parallel_after_done() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut record = reader.create_record_buf();
+    while reader.read_record(&mut record)?.is_not_done() {}
+
+    let mut parallel = reader.into_parallel()?;
+    assert!(parallel.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
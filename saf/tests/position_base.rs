@@ -0,0 +1,107 @@
+use std::io;
+
+use angsd_saf::{
+    index::{PositionBase, PositionWidth},
+    reader::PositionReader,
+    record::Record64,
+    version::V3,
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, reader_from_writer, setup_writer};
+
+/// Writes the records with the provided position base, returning the stored positions and the
+/// positions read back.
+fn write_then_read(position_base: PositionBase) -> io::Result<(Vec<u32>, Vec<u32>)> {
+    let records = records_v3![
+        chr1:0 [0., -1., -2.], chr1:5 [-1., 0., -2.],
+        chr2:3 [-2., -1., 0.],
+    ];
+
+    let mut writer = setup_writer::<V3>(get_alleles_v3(records))?;
+    writer.set_position_base(position_base);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().position_base(), position_base);
+
+    let mut read = Vec::new();
+    let mut record = reader.create_record_buf();
+    while reader.read_record(&mut record)?.is_not_done() {
+        read.push(record.position());
+    }
+
+    let (_, position_reader, _) = reader.into_parts();
+    let data = position_reader.into_inner().into_inner();
+    let mut position_reader = PositionReader::new(bgzf::Reader::new(&data[..]));
+    position_reader.read_magic::<V3>()?;
+    let mut stored = Vec::new();
+    while let Some(position) = position_reader.read_position()? {
+        stored.push(position);
+    }
+
+    Ok((stored, read))
+}
+
+#[test]
+fn test_position_base_zero() -> io::Result<()> {
+    let (stored, read) = write_then_read(PositionBase::Zero)?;
+
+    assert_eq!(stored, [0, 5, 3]);
+    assert_eq!(read, [0, 5, 3]);
+
+    Ok(())
+}
+
+#[test]
+fn test_position_base_one() -> io::Result<()> {
+    let (stored, read) = write_then_read(PositionBase::One)?;
+
+    assert_eq!(stored, [1, 6, 4]);
+    assert_eq!(read, [0, 5, 3]);
+
+    Ok(())
+}
+
+#[test]
+fn test_position_base_one_parallel_unsupported() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    writer.set_position_base(PositionBase::One);
+    for record in records_v3![chr1:1 [0.]].iter() {
+        writer.write_record(record)?;
+    }
+
+    let reader = reader_from_writer(writer, 1)?;
+    assert!(reader.into_parallel().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_position_base_one_overflow_leaves_index_unchanged() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    writer.set_position_base(PositionBase::One);
+    writer.set_position_width(PositionWidth::U64);
+    writer.write_record64(&Record64::new("chr1", 1, vec![0.].into()))?;
+
+    let error = writer
+        .write_record64(&Record64::new("chr2", u64::MAX, vec![0.].into()))
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    writer.write_record64(&Record64::new("chr1", 2, vec![-1.].into()))?;
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().records().len(), 1);
+    assert_eq!(reader.index().records()[0].sites(), 2);
+
+    let mut buf = reader.create_record_buf();
+    for position in [1, 2] {
+        assert!(reader.read_record(&mut buf)?.is_not_done());
+        assert_eq!(buf.position(), position);
+    }
+    assert!(reader.read_record(&mut buf)?.is_done());
+
+    Ok(())
+}
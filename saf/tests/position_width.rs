@@ -0,0 +1,134 @@
+use std::io;
+
+use angsd_saf::{
+    index::PositionWidth,
+    record::{Band, Record64},
+    version::{V3, V4},
+};
+
+pub mod utils;
+use utils::{reader_from_writer, setup_writer};
+
+const LARGE: u64 = u32::MAX as u64 + 10;
+
+#[test]
+fn test_v3_position_width_u64_roundtrip() -> io::Result<()> {
+    let records = [
+        Record64::new("chr1", 1, vec![0., -1., -2.].into()),
+        Record64::new("chr1", LARGE, vec![-1., 0., -2.].into()),
+        Record64::new("chr2", LARGE + 1, vec![-2., -1., 0.].into()),
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    writer.set_position_width(PositionWidth::U64);
+    for record in records.iter() {
+        writer.write_record64(record)?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().position_width(), PositionWidth::U64);
+
+    let mut buf = Record64::from(reader.create_record_buf());
+    for expected in records.iter() {
+        assert!(reader.read_record64(&mut buf)?.is_not_done());
+        assert_eq!(
+            reader.index().records()[*buf.contig_id()].name(),
+            *expected.contig_id()
+        );
+        assert_eq!(buf.position(), expected.position());
+        assert_eq!(buf.item(), expected.item());
+    }
+    assert!(reader.read_record64(&mut buf)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_v4_position_width_u64_roundtrip() -> io::Result<()> {
+    let records = [
+        Record64::new("chr1", LARGE, Band::new(0, vec![0., -1.])),
+        Record64::new("chr2", 2, Band::new(1, vec![-1.])),
+        Record64::new("chr2", LARGE * 2, Band::new(2, vec![-1., 0., -2.])),
+    ];
+
+    let mut writer = setup_writer::<V4>(4)?;
+    writer.set_position_width(PositionWidth::U64);
+    for record in records.iter() {
+        writer.write_record64(record)?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().records()[1].sum_band(), 4);
+
+    let mut buf = Record64::from(reader.create_record_buf());
+    for expected in records.iter() {
+        assert!(reader.read_record64(&mut buf)?.is_not_done());
+        assert_eq!(
+            reader.index().records()[*buf.contig_id()].name(),
+            *expected.contig_id()
+        );
+        assert_eq!(buf.position(), expected.position());
+        assert_eq!(buf.item(), expected.item());
+    }
+    assert!(reader.read_record64(&mut buf)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_position_width_u64_read_record_narrows() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    writer.set_position_width(PositionWidth::U64);
+    writer.write_record64(&Record64::new("chr1", 3, vec![0.].into()))?;
+    writer.write_record64(&Record64::new("chr1", LARGE, vec![0.].into()))?;
+
+    let mut reader = reader_from_writer(writer, 1)?;
+
+    let mut buf = reader.create_record_buf();
+    assert!(reader.read_record(&mut buf)?.is_not_done());
+    assert_eq!(buf.position(), 3);
+
+    let error = reader.read_record(&mut buf).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
+
+#[test]
+fn test_position_width_u32_rejects_large_position() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+
+    let error = writer
+        .write_record64(&Record64::new("chr1", LARGE, vec![0.].into()))
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+#[test]
+fn test_position_width_u32_rejected_position_leaves_index_unchanged() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    writer.write_record64(&Record64::new("chr1", 1, vec![0.].into()))?;
+    assert!(writer
+        .write_record64(&Record64::new("chr2", LARGE, vec![0.].into()))
+        .is_err());
+    assert!(writer
+        .write_record64(&Record64::new("chr1", LARGE, vec![0.].into()))
+        .is_err());
+    writer.write_record64(&Record64::new("chr1", 2, vec![-1.].into()))?;
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().records().len(), 1);
+    assert_eq!(reader.index().records()[0].sites(), 2);
+
+    let mut buf = reader.create_record_buf();
+    for (position, item) in [(1, [0.]), (2, [-1.])] {
+        assert!(reader.read_record(&mut buf)?.is_not_done());
+        assert_eq!(buf.position(), position);
+        assert_eq!(buf.item(), &item.to_vec().into());
+    }
+    assert!(reader.read_record(&mut buf)?.is_done());
+
+    Ok(())
+}
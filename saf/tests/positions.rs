@@ -0,0 +1,46 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_positions_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:3 [-1., 0., -2.],
+        chr2:0 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+    let positions = reader.positions().collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(positions, [(0, 1), (0, 3), (1, 0), (2, 4), (2, 5)]);
+    assert!(reader.positions().next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_positions_then_seek_and_read_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?;
+    let positions = reader.positions().collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(positions, [(0, 1), (0, 2), (1, 5)]);
+
+    let expected = reader_from_records::<V4>(2, records, 1)?
+        .records()
+        .collect::<io::Result<Vec<_>>>()?;
+
+    reader.seek(0)?;
+    let read = reader.records().collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, expected);
+
+    Ok(())
+}
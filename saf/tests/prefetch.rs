@@ -0,0 +1,75 @@
+use std::{io, num::NonZeroUsize};
+
+use angsd_saf::{
+    reader::Builder,
+    record::{Id, Record},
+    version::{Version, V3, V4},
+    Index,
+};
+
+pub mod utils;
+use utils::setup_writer;
+
+fn read_all<V>(
+    alleles: usize,
+    records: &[Record<&str, V::Item>],
+    threads: usize,
+    prefetch: bool,
+) -> io::Result<Vec<Record<Id, V::Item>>>
+where
+    V: Version,
+{
+    let mut writer = setup_writer::<V>(alleles)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (index_reader, position_reader, item_reader) = writer.finish()?;
+
+    let index = Index::read(&mut index_reader.get_ref().as_slice())?;
+    let mut reader = Builder::<V>::default()
+        .set_threads(NonZeroUsize::new(threads).unwrap())
+        .set_prefetch(prefetch)
+        .build(
+            index,
+            position_reader.get_ref().as_slice(),
+            item_reader.get_ref().as_slice(),
+        )
+        .unwrap();
+    reader.read_magic()?;
+
+    reader.records().collect()
+}
+
+#[test]
+fn test_prefetch_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.],
+    ];
+
+    for threads in [1, 2] {
+        let expected = read_all::<V3>(2, records, threads, false)?;
+        assert_eq!(expected.len(), 5);
+        assert_eq!(read_all::<V3>(2, records, threads, true)?, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_prefetch_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    for threads in [1, 2] {
+        let expected = read_all::<V4>(2, records, threads, false)?;
+        assert_eq!(expected.len(), 3);
+        assert_eq!(read_all::<V4>(2, records, threads, true)?, expected);
+    }
+
+    Ok(())
+}
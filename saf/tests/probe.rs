@@ -0,0 +1,64 @@
+use std::io;
+
+use angsd_saf::{
+    probe,
+    version::{V3, V4},
+    Writer,
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, TempPrefix};
+
+#[test]
+fn test_probe_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+        chr3:4 [-2., 0., -1.], chr3:5 [0., 0., -1.], chr3:8 [0., 0., -1.],
+    ];
+
+    let prefix = TempPrefix::new("probe-v3");
+    let mut writer = Writer::<_, V3>::from_prefix(get_alleles_v3(records), &prefix)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    let probe = probe(&prefix)?;
+
+    assert_eq!(probe.version(), 3);
+    assert_eq!(probe.alleles(), 2);
+    assert_eq!(probe.contigs(), 3);
+    assert_eq!(probe.total_sites(), 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_probe_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1.],
+        chr2:2 [nil, nil; 0.], chr2:3 [-2., -1., 0., -1.],
+    ];
+
+    let prefix = TempPrefix::new("probe-v4");
+    let mut writer = Writer::<_, V4>::from_prefix(get_alleles_v4(records), &prefix)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+
+    let probe = probe(&prefix)?;
+
+    assert_eq!(probe.version(), 4);
+    assert_eq!(probe.alleles(), 4);
+    assert_eq!(probe.contigs(), 2);
+    assert_eq!(probe.total_sites(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_probe_missing() {
+    assert!(probe(TempPrefix::new("probe-missing")).is_err());
+}
@@ -0,0 +1,41 @@
+use std::io;
+
+use angsd_saf::{
+    record::{Likelihoods, Record},
+    version::V4,
+};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_read_record_full() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2., -3.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(3, records, 1)?;
+
+    let expected: [&[f32]; 3] = [
+        &[0., -1., -2., -3.],
+        &[f32::NEG_INFINITY, -1., 0., f32::NEG_INFINITY],
+        &[f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY, 0.],
+    ];
+
+    let mut record = Record::new(0, 0, Likelihoods::from(Vec::new()));
+    for (expected_position, expected_likelihoods) in [1, 2, 5].into_iter().zip(expected) {
+        assert!(reader
+            .read_record_full(&mut record, f32::NEG_INFINITY)?
+            .is_not_done());
+        assert_eq!(record.position(), expected_position);
+        assert_eq!(&record.item()[..], expected_likelihoods);
+    }
+
+    assert!(reader
+        .read_record_full(&mut record, f32::NEG_INFINITY)?
+        .is_done());
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, reader_from_records};
+
+#[test]
+fn test_read_records_batches_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1.],
+        chr1:2 [-1., 0.],
+        chr1:5 [-2., 0.],
+        chr2:1 [0., -3.],
+        chr2:4 [-4., 0.],
+        chr3:9 [0., -5.],
+        chr3:10 [-6., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(get_alleles_v3(records), records, 1)?;
+    let mut bufs = vec![reader.create_record_buf(); 3];
+
+    let mut read = Vec::new();
+    let mut counts = Vec::new();
+    loop {
+        let n = reader.read_records(&mut bufs)?;
+        counts.push(n);
+        read.extend_from_slice(&bufs[..n]);
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    assert_eq!(counts, [3, 3, 1, 0]);
+    let read = read
+        .into_iter()
+        .map(|record| record.to_named(reader.index()))
+        .collect::<Vec<_>>();
+    assert_eq!(read, records);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_records_batches_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1.],
+        chr2:2 [-2., -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(get_alleles_v4(records), records, 1)?;
+    let mut bufs = vec![reader.create_record_buf(); 2];
+
+    assert_eq!(reader.read_records(&mut bufs)?, 2);
+    assert_eq!(bufs[0].clone().to_named(reader.index()), records[0]);
+    assert_eq!(bufs[1].clone().to_named(reader.index()), records[1]);
+
+    assert_eq!(reader.read_records(&mut bufs)?, 1);
+    assert_eq!(bufs[0].clone().to_named(reader.index()), records[2]);
+    // Buffers past those filled are untouched
+    assert_eq!(bufs[1].clone().to_named(reader.index()), records[1]);
+
+    assert_eq!(reader.read_records(&mut bufs)?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_records_empty_bufs() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.]];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    assert_eq!(reader.read_records(&mut [])?, 0);
+    let mut record = reader.create_record_buf();
+    assert!(reader.read_record(&mut record)?.is_not_done());
+
+    Ok(())
+}
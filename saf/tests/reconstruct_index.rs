@@ -0,0 +1,48 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{reconstruct_index, version::V3, Index};
+
+pub mod utils;
+use utils::setup_writer;
+
+#[test]
+fn test_reconstruct_index() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr1:9 [-1., 0., -2.],
+        chr2:5 [-2., -1., 0.],
+        chr3:5 [0., -3., -1.],
+        chr3:7 [0., -4., -2.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (mut index_reader, mut position_reader, mut item_reader) = writer.finish()?;
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+    item_reader.seek(io::SeekFrom::Start(0))?;
+
+    let index = Index::<V3>::read(&mut index_reader)?;
+
+    let reconstructed = reconstruct_index(
+        &mut bgzf::Reader::new(position_reader),
+        &mut bgzf::Reader::new(item_reader),
+        2,
+        |i| format!("chr{}", i + 1),
+    )?;
+
+    let sites = |index: &Index<V3>| {
+        index
+            .records()
+            .iter()
+            .map(|record| record.sites())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(sites(&reconstructed), vec![3, 1, 2]);
+    assert_eq!(reconstructed, index);
+
+    Ok(())
+}
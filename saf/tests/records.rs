@@ -0,0 +1,107 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4, reader_from_records};
+
+#[test]
+fn test_records_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:3 [-1., 0., -2.],
+        chr2:2 [-2., -1., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(get_alleles_v3(records), records, 1)?;
+
+    let read = reader.records().collect::<io::Result<Vec<_>>>()?;
+    let named = read
+        .into_iter()
+        .map(|record| record.to_named(reader.index()))
+        .collect::<Vec<_>>();
+    assert_eq!(named, records);
+
+    assert!(reader.records().next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_records_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1.],
+        chr2:2 [-2., -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(get_alleles_v4(records), records, 1)?;
+
+    let read = reader.records().collect::<io::Result<Vec<_>>>()?;
+    let named = read
+        .into_iter()
+        .map(|record| record.to_named(reader.index()))
+        .collect::<Vec<_>>();
+    assert_eq!(named, records);
+
+    Ok(())
+}
+
+#[test]
+fn test_records_continue_from_location() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr1:3 [-1.], chr2:2 [-2.]];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+
+    let positions = reader
+        .records()
+        .map(|record| record.map(|record| record.position()))
+        .collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(positions, [3, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_into_records_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr2:2 [-2., -1., 0.],
+        chr2:4 [-1., 0., -2.],
+    ];
+
+    let reader = reader_from_records::<V3>(get_alleles_v3(records), records, 1)?;
+    let index = reader.index().clone();
+
+    let read = reader.into_records().collect::<io::Result<Vec<_>>>()?;
+    let named = read
+        .into_iter()
+        .map(|record| record.to_named(&index))
+        .collect::<Vec<_>>();
+    assert_eq!(named, records);
+
+    Ok(())
+}
+
+#[test]
+fn test_into_records_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil; 0., -1.],
+        chr1:2 [-2., -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let reader = reader_from_records::<V4>(get_alleles_v4(records), records, 1)?;
+    let index = reader.index().clone();
+
+    let mut read = Vec::new();
+    for record in reader {
+        read.push(record?.to_named(&index));
+    }
+    assert_eq!(read, records);
+
+    Ok(())
+}
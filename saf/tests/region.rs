@@ -0,0 +1,53 @@
+use std::io;
+
+use angsd_saf::{version::V3, Region};
+
+pub mod utils;
+use utils::reader_from_records;
+
+fn region_positions(region: &str) -> io::Result<Vec<(usize, u32)>> {
+    let records = records_v3![chr1:1, chr1:4, chr2:2, chr2:5, chr2:9, chr3:3];
+    let mut reader = reader_from_records::<V3>(0, records, 2)?;
+
+    let region: Region = region.parse()?;
+    let positions = reader
+        .reader_for_region(&region)?
+        .map(|record| record.map(|record| (*record.contig_id(), record.position())))
+        .collect();
+
+    positions
+}
+
+#[test]
+fn test_region_whole_contig() -> io::Result<()> {
+    assert_eq!(region_positions("chr2")?, [(1, 2), (1, 5), (1, 9)]);
+    assert_eq!(region_positions("chr3")?, [(2, 3)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_region_open_ended() -> io::Result<()> {
+    assert_eq!(region_positions("chr2:3")?, [(1, 5), (1, 9)]);
+    assert_eq!(region_positions("chr2:10")?, []);
+
+    Ok(())
+}
+
+#[test]
+fn test_region_range() -> io::Result<()> {
+    assert_eq!(region_positions("chr2:2-5")?, [(1, 2), (1, 5)]);
+    assert_eq!(region_positions("chr2:3-8")?, [(1, 5)]);
+    assert_eq!(region_positions("chr2:6-8")?, []);
+    assert_eq!(region_positions("chr1:0-100")?, [(0, 1), (0, 4)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_region_errors() {
+    let kind = |region| region_positions(region).unwrap_err().kind();
+
+    assert_eq!(kind("chr4"), io::ErrorKind::InvalidInput);
+    assert_eq!(kind("chr2:5-2"), io::ErrorKind::InvalidInput);
+}
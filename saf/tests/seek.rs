@@ -23,3 +23,94 @@ fn test_seek_v3() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_current_contig_name() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1, chr3:1, chr3:2];
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    assert_eq!(reader.current_contig_name(), None);
+
+    let mut record = reader.create_record_buf();
+    let mut names = Vec::new();
+    while reader.read_record(&mut record)?.is_not_done() {
+        names.push(reader.current_contig_name().map(String::from));
+    }
+    let expected = ["chr1", "chr1", "chr2", "chr3", "chr3"].map(|s| Some(s.to_string()));
+    assert_eq!(names, expected);
+
+    assert_eq!(reader.current_contig_name(), None);
+
+    reader.seek_by_name("chr2")?;
+    assert_eq!(reader.current_contig_name(), Some("chr2"));
+
+    Ok(())
+}
+
+#[test]
+fn test_try_seek_invalid() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1];
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let err = reader.try_seek(2).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let err = reader.try_seek_by_name("chr3").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+    assert_eq!((*record.contig_id(), record.position()), (0, 1));
+
+    reader.try_seek_by_name("chr2")?;
+    reader.read_record(&mut record)?;
+    assert_eq!((*record.contig_id(), record.position()), (1, 1));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "name not found in index")]
+fn test_seek_by_name_panics() {
+    let records = records_v3![chr1:1];
+    let mut reader = reader_from_records::<V3>(0, records, 1).unwrap();
+
+    let _ = reader.seek_by_name("chr2");
+}
+
+#[test]
+fn test_seek_to_position() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:4, chr2:2, chr2:5, chr2:9, chr3:3];
+    let mut reader = reader_from_records::<V3>(0, records, 2)?;
+
+    let mut record = reader.create_record_buf();
+    let mut next = |reader: &mut angsd_saf::ReaderV3<_>| -> io::Result<(usize, u32)> {
+        reader.read_record(&mut record)?;
+        Ok((*record.contig_id(), record.position()))
+    };
+
+    // Exact hit
+    assert!(reader.seek_to_position(1, 5)?);
+    assert_eq!(reader.current_contig_name(), Some("chr2"));
+    assert_eq!(next(&mut reader)?, (1, 5));
+    assert_eq!(next(&mut reader)?, (1, 9));
+
+    // Gap
+    assert!(reader.seek_to_position(0, 2)?);
+    assert_eq!(next(&mut reader)?, (0, 4));
+
+    // Before first
+    assert!(reader.seek_to_position(2, 0)?);
+    assert_eq!(next(&mut reader)?, (2, 3));
+
+    // Past the end
+    assert!(!reader.seek_to_position(1, 10)?);
+    assert_eq!(next(&mut reader)?, (2, 3));
+    assert!(!reader.seek_to_position(2, 4)?);
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    let err = reader.seek_to_position(3, 0).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::io;
+
+use angsd_saf::version::V3;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_select_positions() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0.], chr1:3 [1.], chr1:7 [2.],
+        chr2:2 [3.], chr2:4 [4.],
+        chr3:9 [5.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let targets = [
+        (String::from("chr1"), 3),
+        (String::from("chr1"), 5),
+        (String::from("chr2"), 4),
+        (String::from("chr4"), 1),
+    ];
+    let selected = reader
+        .select_positions(&targets)
+        .map(|result| {
+            result.map(|record| record.map(|r| (*r.contig_id(), r.position(), r.item()[0])))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        selected,
+        vec![Some((0, 3, 1.)), None, Some((1, 4, 4.)), None]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_select_positions_past_end() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr1:3 [1.]];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let targets = [(String::from("chr1"), 3), (String::from("chr1"), 4)];
+    let selected = reader
+        .select_positions(&targets)
+        .map(|result| result.map(|record| record.map(|r| r.position())))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(selected, vec![Some(3), None]);
+
+    Ok(())
+}
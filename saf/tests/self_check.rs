@@ -0,0 +1,41 @@
+use std::io;
+
+use angsd_saf::{record::Likelihoods, version::V3, writer::WriterStateError, Record, Writer};
+
+fn writer() -> Writer<Vec<u8>, V3> {
+    Writer::new(Vec::new(), Vec::new(), Vec::new())
+}
+
+#[test]
+fn test_self_check_no_records() -> io::Result<()> {
+    let mut writer = writer();
+    writer.write_magic()?;
+    writer.write_alleles(2)?;
+
+    assert_eq!(writer.self_check(), Err(WriterStateError::NoRecords));
+
+    Ok(())
+}
+
+#[test]
+fn test_self_check_missing_alleles() -> io::Result<()> {
+    let mut writer = writer();
+    writer.write_magic()?;
+    writer.write_record(&Record::new("chr1", 1, Likelihoods::from(vec![0., 0., 0.])))?;
+
+    assert_eq!(writer.self_check(), Err(WriterStateError::MissingAlleles));
+
+    Ok(())
+}
+
+#[test]
+fn test_self_check_ok() -> io::Result<()> {
+    let mut writer = writer();
+    writer.write_magic()?;
+    writer.write_alleles(2)?;
+    writer.write_record(&Record::new("chr1", 1, Likelihoods::from(vec![0., 0., 0.])))?;
+
+    assert_eq!(writer.self_check(), Ok(()));
+
+    Ok(())
+}
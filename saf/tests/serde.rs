@@ -0,0 +1,86 @@
+#![cfg(feature = "serde")]
+
+use angsd_saf::{
+    index,
+    record::{Band, Likelihoods},
+    version::{V3, V4},
+    Index, Record,
+};
+
+#[test]
+fn test_serde_roundtrip_v3_record() {
+    let record = Record::new(
+        "chr1".to_string(),
+        3,
+        Likelihoods::from(vec![0., -1.5, -3.]),
+    );
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(
+        json,
+        r#"{"contig_id":"chr1","position":3,"item":[0.0,-1.5,-3.0]}"#
+    );
+
+    let deserialized: Record<String, Likelihoods> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, record);
+}
+
+#[test]
+fn test_serde_roundtrip_v4_record() {
+    let record = Record::new(1usize, 7, Band::new(2, vec![-0.5, 0.]));
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(
+        json,
+        r#"{"contig_id":1,"position":7,"item":{"start":2,"likelihoods":[-0.5,0.0]}}"#
+    );
+
+    let deserialized: Record<usize, Band> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, record);
+}
+
+#[test]
+fn test_serde_roundtrip_v3_index() {
+    let index = Index::<V3>::new(
+        4,
+        vec![
+            index::Record::new("chr1".to_string(), 2, 0, 0),
+            index::Record::new("chr2".to_string(), 3, 10, 20),
+        ],
+    );
+
+    let json = serde_json::to_string(&index).unwrap();
+    assert!(!json.contains("sum_band"));
+
+    let deserialized: Index<V3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, index);
+}
+
+#[test]
+fn test_serde_roundtrip_v4_index() {
+    let mut index = Index::<V4>::new(
+        4,
+        vec![
+            index::Record::new_with_sum_band("chr1".to_string(), 2, 5, 0, 0),
+            index::Record::new_with_sum_band("chr2".to_string(), 3, 7, 10, 20),
+        ],
+    );
+    *index.site_checksum_mut() = Some(5);
+
+    let json = serde_json::to_string(&index).unwrap();
+    assert!(json.contains(r#""sum_band":7"#));
+
+    let deserialized: Index<V4> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, index);
+    assert_eq!(deserialized.contig_id("chr2"), Some(1));
+}
+
+#[test]
+fn test_serde_sum_band_mismatch() {
+    let v3_json = r#"{"name":"chr1","sites":2,"position_offset":0,"item_offset":0,"alleles":null,"reference_length":null}"#;
+    assert!(serde_json::from_str::<index::Record<V4>>(v3_json).is_err());
+
+    let v4_json = r#"{"name":"chr1","sites":2,"sum_band":5,"position_offset":0,"item_offset":0,"alleles":null,"reference_length":null}"#;
+    assert!(serde_json::from_str::<index::Record<V3>>(v4_json).is_err());
+    assert!(serde_json::from_str::<index::Record<V4>>(v4_json).is_ok());
+}
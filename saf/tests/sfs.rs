@@ -0,0 +1,148 @@
+use std::io;
+
+use angsd_saf::{
+    record::Band,
+    version::{V3, V4},
+    Record,
+};
+
+pub mod utils;
+use utils::{reader_from_records, reader_from_writer, setup_writer};
+
+fn ln(values: &[f32]) -> Vec<f32> {
+    values.iter().map(|x| x.ln()).collect()
+}
+
+fn assert_approx_eq(x: &[f64], y: &[f64]) {
+    assert_eq!(x.len(), y.len());
+    for (a, b) in x.iter().zip(y.iter()) {
+        assert!((a - b).abs() < 1e-6, "{x:?} != {y:?}");
+    }
+}
+
+#[test]
+fn test_fold_folded_sfs_even_alleles() -> io::Result<()> {
+    let records = &[
+        Record::new("chr1", 1, ln(&[0.5, 0.2, 0.1, 0.1, 0.1]).into()),
+        Record::new("chr1", 2, ln(&[1., 1., 4., 1., 3.]).into()),
+    ];
+    let mut reader = reader_from_records::<V3>(4, records, 1)?;
+
+    let mut sfs = vec![0.0; 3];
+    reader.fold_folded_sfs(&mut sfs)?;
+
+    // Site 1: [0.5 + 0.1, 0.2 + 0.1, 0.1], site 2: [0.1 + 0.3, 0.1 + 0.1, 0.4]
+    assert_approx_eq(&sfs, &[1.0, 0.5, 0.5]);
+
+    Ok(())
+}
+
+#[test]
+fn test_fold_folded_sfs_odd_alleles() -> io::Result<()> {
+    let records = &[Record::new("chr1", 1, ln(&[0.4, 0.3, 0.2, 0.1]).into())];
+    let mut reader = reader_from_records::<V3>(3, records, 1)?;
+
+    let mut sfs = vec![0.0; 2];
+    reader.fold_folded_sfs(&mut sfs)?;
+
+    assert_approx_eq(&sfs, &[0.5, 0.5]);
+
+    Ok(())
+}
+
+#[test]
+fn test_fold_folded_sfs_wrong_length() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0., 0., 0.]];
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+
+    assert!(reader.fold_folded_sfs(&mut [0.0; 3]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_sfs_accumulating_writer_v3() -> io::Result<()> {
+    let records = &[
+        Record::new("chr1", 1, ln(&[0.5, 0.2, 0.3]).into()),
+        Record::new("chr1", 2, ln(&[2., 1., 1.]).into()),
+        Record::new("chr2", 1, ln(&[0.1, 0.1, 0.8]).into()),
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?.into_sfs_accumulating(2);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let sfs = writer.sfs().to_vec();
+    assert_approx_eq(&sfs, &[1.1, 0.55, 1.35]);
+
+    // Compare to separately reading and accumulating
+    let mut reader = reader_from_writer(writer.into_inner(), 1)?;
+    let mut expected = vec![0.0; 3];
+    let mut record = reader.create_record_buf();
+    while reader.read_record(&mut record)?.is_not_done() {
+        let linear = record
+            .item()
+            .iter()
+            .map(|v| f64::from(*v).exp())
+            .collect::<Vec<_>>();
+        let sum: f64 = linear.iter().sum();
+        for (x, v) in expected.iter_mut().zip(linear) {
+            *x += v / sum;
+        }
+    }
+    assert_approx_eq(&sfs, &expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_sfs_accumulating_writer_v4() -> io::Result<()> {
+    let mut writer = setup_writer::<V4>(3)?.into_sfs_accumulating(3);
+    writer.write_record(&Record::new("chr1", 1, Band::new(1, ln(&[1., 3.]))))?;
+    writer.write_record(&Record::new("chr1", 2, Band::new(0, ln(&[1.]))))?;
+
+    assert_approx_eq(writer.sfs(), &[1., 0.25, 0.75, 0.]);
+
+    let error = writer
+        .write_record(&Record::new("chr1", 3, Band::new(3, ln(&[1., 1.]))))
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+#[test]
+fn test_sfs_accumulating_writer_skips_uninformative_site() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(2)?.into_sfs_accumulating(2);
+    writer.write_record(&Record::new("chr1", 1, ln(&[0.5, 0.2, 0.3]).into()))?;
+    writer.write_record(&Record::new("chr1", 2, ln(&[0., 0., 0.]).into()))?;
+
+    assert_approx_eq(writer.sfs(), &[0.5, 0.2, 0.3]);
+
+    // The site is still written
+    let mut reader = reader_from_writer(writer.into_inner(), 1)?;
+    assert_eq!(reader.records().count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_sfs_accumulating_writer_skips_rejected_records() -> io::Result<()> {
+    let mut inner = setup_writer::<V3>(2)?;
+    inner.set_validate_positions(true);
+    inner.set_dedup_positions(true);
+
+    let mut writer = inner.into_sfs_accumulating(2);
+    writer.write_record(&Record::new("chr1", 2, ln(&[0.5, 0.2, 0.3]).into()))?;
+
+    // Dropped as duplicate
+    writer.write_record(&Record::new("chr1", 2, ln(&[1., 0., 0.]).into()))?;
+    // Rejected as unsorted
+    assert!(writer
+        .write_record(&Record::new("chr1", 1, ln(&[1., 0., 0.]).into()))
+        .is_err());
+
+    assert_approx_eq(writer.sfs(), &[0.5, 0.2, 0.3]);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::io;
+
+use angsd_saf::{
+    single_file,
+    version::{V3, V4},
+};
+
+pub mod utils;
+use utils::{get_alleles_v3, get_alleles_v4};
+
+#[test]
+fn test_v3_single_file_roundtrip() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:5 [-2., -1., 0.],
+        chr3:2 [-2., 0., -1.], chr3:8 [0., 0., -1.],
+    ];
+
+    let mut writer = single_file::Writer::<_, V3>::new(Vec::new(), get_alleles_v3(records))?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let bytes = writer.finish()?;
+
+    let mut reader = single_file::read::<_, V3>(bytes.as_slice())?;
+    assert_eq!(reader.index().records().len(), 3);
+
+    let mut record = reader.create_record_buf();
+    for expected in records.iter() {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+        assert_eq!(&record.clone().to_named(reader.index()), expected);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_v4_single_file_roundtrip() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1.], chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let mut writer = single_file::Writer::<_, V4>::new(Vec::new(), get_alleles_v4(records))?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let bytes = writer.finish()?;
+
+    let mut reader = single_file::read::<_, V4>(bytes.as_slice())?;
+
+    let mut record = reader.create_record_buf();
+    for expected in records.iter() {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+        assert_eq!(&record.clone().to_named(reader.index()), expected);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_single_file_missing_trailer() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0., -1., -2.]];
+
+    let mut writer = single_file::Writer::<_, V3>::new(Vec::new(), get_alleles_v3(records))?;
+    writer.write_record(&records[0])?;
+    let mut bytes = writer.finish()?;
+    bytes.truncate(bytes.len() / 2);
+
+    assert!(single_file::read::<_, V3>(bytes.as_slice()).is_err());
+
+    Ok(())
+}
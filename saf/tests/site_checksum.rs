@@ -0,0 +1,67 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{reader::Builder, version::V3, Index};
+
+pub mod utils;
+use utils::{reader_from_writer, setup_writer};
+
+#[test]
+fn test_site_checksum_roundtrip() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1];
+
+    let mut writer = setup_writer::<V3>(0)?;
+    writer.set_site_checksum(true);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.index().site_checksum(), Some(3));
+
+    let mut record = reader.create_record_buf();
+    while reader.read_record(&mut record)?.is_not_done() {}
+
+    Ok(())
+}
+
+#[test]
+fn test_site_checksum_detects_truncation() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1, chr3:4, chr3:5];
+
+    // Write full file with site checksum
+    let mut writer = setup_writer::<V3>(0)?;
+    writer.set_site_checksum(true);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (mut index_reader, _, _) = writer.finish()?;
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    let mut index = Index::<V3>::read(&mut index_reader)?;
+    assert_eq!(index.site_checksum(), Some(5));
+
+    // Simulate files truncated at the end of a contig, along with the index records
+    let mut writer = setup_writer::<V3>(0)?;
+    for record in records.iter().take(3) {
+        writer.write_record(record)?;
+    }
+    let (_, position_reader, item_reader) = writer.finish()?;
+    index.records_mut().pop();
+
+    let mut reader = Builder::v3()
+        .build(
+            index,
+            io::Cursor::new(position_reader.into_inner()),
+            io::Cursor::new(item_reader.into_inner()),
+        )
+        .expect("empty index");
+    reader.read_magic()?;
+
+    let mut record = reader.create_record_buf();
+    for _ in 0..3 {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+    }
+    let error = reader.read_record(&mut record).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::io::{self, Seek};
+
+use angsd_saf::{reader::Builder, version::V3, Index};
+
+pub mod utils;
+use utils::setup_writer;
+
+#[test]
+fn test_sparse_writer_merges_contig_runs() -> io::Result<()> {
+    let records = records_v3![
+        chr1:3 [0., -1.],
+        chr1:1 [-1., 0.],
+        chr2:1 [-2., 0.],
+        chr1:5 [0., -5.],
+        chr1:2 [0., -2.],
+    ];
+
+    let mut writer = setup_writer::<V3>(1)?.into_sparse();
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+    let (mut index_reader, mut position_reader, mut item_reader) = writer.finish()?;
+
+    index_reader.seek(io::SeekFrom::Start(0))?;
+    position_reader.seek(io::SeekFrom::Start(0))?;
+    item_reader.seek(io::SeekFrom::Start(0))?;
+
+    let index = Index::<V3>::read(&mut index_reader)?;
+    let contigs = index
+        .records()
+        .iter()
+        .map(|record| (record.name(), record.sites()))
+        .collect::<Vec<_>>();
+    assert_eq!(contigs, vec![("chr1", 4), ("chr2", 1)]);
+
+    let mut reader = Builder::v3()
+        .build(index, position_reader, item_reader)
+        .expect("empty index");
+    reader.read_magic()?;
+
+    let mut record = reader.create_record_buf();
+    let mut read = Vec::new();
+    while reader.read_record(&mut record)?.is_not_done() {
+        read.push((*record.contig_id(), record.position(), record.item()[1]));
+    }
+    assert_eq!(
+        read,
+        vec![
+            (0, 1, 0.),
+            (0, 2, -2.),
+            (0, 3, -1.),
+            (0, 5, -5.),
+            (1, 1, 0.),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sparse_writer_duplicate_position() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr2:1, chr1:1];
+
+    let mut writer = setup_writer::<V3>(0)?.into_sparse();
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let error = writer.finish().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
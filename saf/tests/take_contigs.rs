@@ -0,0 +1,70 @@
+use std::io;
+
+use angsd_saf::version::V3;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_take_contigs() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0.],
+        chr1:2 [1.],
+        chr2:1 [2.],
+        chr2:5 [3.],
+        chr2:6 [4.],
+        chr3:3 [5.],
+    ];
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut reader = reader.take_contigs(2);
+
+    let mut record = reader.get_ref().create_record_buf();
+    let mut read = Vec::new();
+    while reader.read_record(&mut record)?.is_not_done() {
+        read.push((*record.contig_id(), record.position()));
+    }
+
+    assert_eq!(read, [(0, 1), (0, 2), (1, 1), (1, 5), (1, 6)]);
+    assert_eq!(reader.contigs_read(), 2);
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    // Nothing from the third contig has been read
+    let mut reader = reader.into_inner();
+    assert!(reader.read_record(&mut record)?.is_not_done());
+    assert_eq!((*record.contig_id(), record.position()), (2, 3));
+
+    Ok(())
+}
+
+#[test]
+fn test_take_zero_contigs() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr2:1 [1.]];
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut reader = reader.take_contigs(0);
+
+    let mut record = reader.get_ref().create_record_buf();
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_take_more_contigs_than_available() -> io::Result<()> {
+    let records = records_v3![chr1:1 [0.], chr2:1 [1.]];
+
+    let reader = reader_from_records::<V3>(0, records, 1)?;
+    let mut reader = reader.take_contigs(5);
+
+    let mut record = reader.get_ref().create_record_buf();
+    let mut n = 0;
+    while reader.read_record(&mut record)?.is_not_done() {
+        n += 1;
+    }
+
+    assert_eq!(n, 2);
+    assert_eq!(reader.contigs_read(), 2);
+
+    Ok(())
+}
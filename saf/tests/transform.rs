@@ -0,0 +1,43 @@
+use std::io;
+
+use angsd_saf::{transform, version::V3};
+
+pub mod utils;
+use utils::{get_alleles_v3, reader_from_records, reader_from_writer, setup_writer};
+
+#[test]
+fn test_transform_scales_likelihoods() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:4 [-1., 0., -2.],
+        chr2:2 [-2., -1., 0.],
+    ];
+    let expected = records_v3![
+        chr1:1 [0., -2., -4.],
+        chr1:4 [-2., 0., -4.],
+        chr2:2 [-4., -2., 0.],
+    ];
+
+    let alleles = get_alleles_v3(records);
+    let mut reader = reader_from_records::<V3>(alleles, records, 1)?;
+    let mut writer = setup_writer::<V3>(alleles)?;
+
+    transform(&mut reader, &mut writer, |record| {
+        record.item_mut().iter_mut().for_each(|v| *v *= 2.);
+    })?;
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    let index = reader.index().clone();
+    assert_eq!(index.records().len(), 2);
+    assert_eq!(index.records()[0].sites(), 2);
+    assert_eq!(index.records()[1].sites(), 1);
+
+    let mut record = reader.create_record_buf();
+    for expected_record in expected.iter() {
+        assert!(reader.read_record(&mut record)?.is_not_done());
+        assert_eq!(&record.clone().to_named(&index), expected_record);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
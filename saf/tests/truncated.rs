@@ -0,0 +1,133 @@
+use std::io::{self, Write};
+
+use angsd_saf::{
+    reader::Builder,
+    record::Record,
+    version::{Version, V3, V4},
+    Error, Index,
+};
+
+pub mod utils;
+use utils::{setup_writer, MockReader, MockWriter};
+
+/// Creates writers with the first `n` records of the provided records.
+fn writer_from_records<V>(
+    alleles: usize,
+    records: &[Record<&str, V::Item>],
+    n: usize,
+) -> io::Result<MockWriter<V>>
+where
+    V: Version,
+{
+    let mut writer = setup_writer::<V>(alleles)?;
+    for record in records[..n].iter() {
+        writer.write_record(record)?;
+    }
+
+    Ok(writer)
+}
+
+/// Creates a reader from the index and positions of one writer and the items of another.
+fn reader_from_parts<V>(
+    index_and_positions: MockWriter<V>,
+    items: MockWriter<V>,
+) -> io::Result<MockReader<V>>
+where
+    V: Version,
+{
+    let (index_reader, position_reader, _) = index_and_positions.finish()?;
+    let (_, _, item_reader) = items.finish()?;
+
+    let index = Index::read(&mut index_reader.get_ref().as_slice())?;
+    let mut reader = Builder::<V>::default()
+        .build(
+            index,
+            io::Cursor::new(position_reader.into_inner()),
+            io::Cursor::new(item_reader.into_inner()),
+        )
+        .unwrap();
+    reader.read_magic()?;
+
+    Ok(reader)
+}
+
+fn read_error<V>(reader: &mut MockReader<V>) -> io::Error
+where
+    V: Version,
+{
+    reader
+        .records()
+        .find_map(Result::err)
+        .expect("reading truncated file should error")
+}
+
+#[test]
+fn test_truncated_item_file_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr2:1 [-2., -1., 0.], chr2:2 [-2., 0., -1.], chr2:3 [0., 0., -1.],
+    ];
+
+    let mut reader = reader_from_parts(
+        writer_from_records::<V3>(2, records, 4)?,
+        writer_from_records::<V3>(2, records, 2)?,
+    )?;
+    let error = read_error(&mut reader);
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(
+        error.to_string(),
+        "reached EoF in SAF item file at site 1 on contig 'chr2' before reaching end of index"
+    );
+    assert!(matches!(
+        Error::from(error),
+        Error::Truncated { files: "SAF item file", ref contig, site: 1 } if contig == "chr2"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_truncated_item_file_mid_record_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-2., -1., 0.],
+    ];
+
+    let mut truncated = writer_from_records::<V3>(2, records, 1)?;
+    truncated
+        .item_writer_mut()
+        .write_all(&(-2f32).to_le_bytes())?;
+
+    let mut reader = reader_from_parts(writer_from_records::<V3>(2, records, 2)?, truncated)?;
+    let error = read_error(&mut reader);
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert!(error
+        .to_string()
+        .contains("SAF item file at site 1 on contig 'chr1'"));
+
+    Ok(())
+}
+
+#[test]
+fn test_truncated_both_files_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr1:3 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_parts(
+        writer_from_records::<V4>(2, records, 2)?,
+        writer_from_records::<V4>(2, records, 2)?,
+    )?;
+    *reader.index_mut().records_mut()[0].sites_mut() += 1;
+    reader.seek(0)?;
+    let error = read_error(&mut reader);
+
+    assert!(error
+        .to_string()
+        .contains("both SAF files at site 2 on contig 'chr1'"));
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use std::{
+    env, fs,
     io::{self, Seek},
     num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process,
 };
 
 use angsd_saf::{
@@ -13,6 +16,31 @@ use angsd_saf::{
 pub type MockReader<V> = Reader<io::Cursor<Vec<u8>>, V>;
 pub type MockWriter<V> = Writer<io::Cursor<Vec<u8>>, V>;
 
+/// A temporary prefix for SAF files, removing any SAF files with the prefix when dropped.
+///
+/// The prefix is unique to the test process and the provided name.
+pub struct TempPrefix(PathBuf);
+
+impl TempPrefix {
+    pub fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!("angsd-saf-{}-{name}", process::id())))
+    }
+}
+
+impl AsRef<Path> for TempPrefix {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempPrefix {
+    fn drop(&mut self) {
+        for ext in ["saf.idx", "saf.pos.gz", "saf.gz"] {
+            let _ = fs::remove_file(format!("{}.{ext}", self.0.display()));
+        }
+    }
+}
+
 pub fn setup_writer<V>(alleles: usize) -> io::Result<MockWriter<V>>
 where
     V: Version,
@@ -0,0 +1,92 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::{reader_from_writer, setup_writer};
+
+#[test]
+fn test_validate_positions_accepts_sorted() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:3 [-1., 0., -2.],
+        chr2:0 [-2., -1., 0.], chr2:1 [-2., 0., -1.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    writer.set_validate_positions(true);
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.records().count(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_positions_rejects_duplicate() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:1 [-1., 0., -2.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    writer.set_validate_positions(true);
+    writer.write_record(&records[0])?;
+
+    let error = writer.write_record(&records[1]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    assert_eq!(reader.records().count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_positions_rejects_decreasing() -> io::Result<()> {
+    let records = records_v4![
+        chr1:5 [0., -1., -2.],
+        chr1:4 [nil; -1., 0.],
+    ];
+
+    let mut writer = setup_writer::<V4>(2)?;
+    writer.set_validate_positions(true);
+    writer.write_record(&records[0])?;
+
+    let error = writer.write_record(&records[1]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_positions_off_by_default() -> io::Result<()> {
+    let records = records_v3![
+        chr1:2 [0., -1., -2.], chr1:1 [-1., 0., -2.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_positions_with_dedup() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:1 [-1., 0., -2.], chr1:0 [-2., -1., 0.],
+    ];
+
+    let mut writer = setup_writer::<V3>(2)?;
+    writer.set_validate_positions(true);
+    writer.set_dedup_positions(true);
+    writer.write_record(&records[0])?;
+    writer.write_record(&records[1])?;
+    assert_eq!(writer.duplicates_dropped(), 1);
+    assert!(writer.write_record(&records[2]).is_err());
+
+    Ok(())
+}
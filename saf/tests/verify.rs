@@ -0,0 +1,125 @@
+use std::io;
+
+use angsd_saf::version::{V3, V4};
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_verify_valid_v3() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+    let first = reader.records().next().transpose()?;
+
+    reader.verify()?;
+
+    // Location is restored after verifying
+    assert_eq!(reader.records().count(), 2);
+    assert!(first.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_valid_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?;
+    reader.verify()?;
+
+    assert_eq!(reader.records().count(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_after_reading_all() -> io::Result<()> {
+    let records = records_v3![chr1:1, chr1:2, chr2:1];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+    assert_eq!(reader.records().count(), 3);
+
+    reader.verify()?;
+
+    // Reader remains at the end after verifying
+    let mut record = reader.create_record_buf();
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_too_many_sites_in_index() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+    *reader.index_mut().records_mut()[1].sites_mut() += 1;
+
+    let error = reader.verify().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("'chr2'"));
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_too_few_sites_in_index() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.], chr1:2 [-1., 0., -2.],
+        chr2:1 [-2., -1., 0.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(2, records, 1)?;
+    *reader.index_mut().records_mut()[0].sites_mut() -= 1;
+
+    assert!(reader.verify().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_unsorted_positions() -> io::Result<()> {
+    let decreasing = records_v3![
+        chr1:2 [0., -1., -2.], chr1:1 [-1., 0., -2.],
+    ];
+    let duplicate = records_v3![
+        chr1:1 [0., -1., -2.], chr1:1 [-1., 0., -2.],
+    ];
+
+    for records in [decreasing, duplicate] {
+        let mut reader = reader_from_records::<V3>(2, records, 1)?;
+
+        let error = reader.verify().unwrap_err();
+        assert!(error.to_string().contains("does not follow"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_sum_band_mismatch_v4() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [nil; -1., 0.],
+        chr2:5 [nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(2, records, 1)?;
+    *reader.index_mut().records_mut()[0].sum_band_mut() += 1;
+
+    let error = reader.verify().unwrap_err();
+    assert!(error.to_string().contains("'chr1'"));
+
+    Ok(())
+}
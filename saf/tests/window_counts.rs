@@ -0,0 +1,45 @@
+use std::io;
+
+use angsd_saf::version::V3;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_window_counts() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1, chr1:5, chr1:10, chr1:25, chr1:29,
+        chr2:12,
+    ];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let windows = reader.window_counts(10)?;
+    let expected = vec![
+        (String::from("chr1"), 0, 2),
+        (String::from("chr1"), 10, 1),
+        (String::from("chr1"), 20, 2),
+        (String::from("chr2"), 0, 0),
+        (String::from("chr2"), 10, 1),
+    ];
+    assert_eq!(windows, expected);
+
+    // Reading is unaffected
+    let mut record = reader.create_record_buf();
+    reader.read_record(&mut record)?;
+    assert_eq!(record.position(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_window_counts_zero_window() -> io::Result<()> {
+    let records = records_v3![chr1:1];
+
+    let mut reader = reader_from_records::<V3>(0, records, 1)?;
+
+    let error = reader.window_counts(0).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use std::{io, num::NonZeroUsize};
+
+use angsd_saf::version::V3;
+
+pub mod utils;
+use utils::reader_from_records;
+
+#[test]
+fn test_set_worker_count_mid_stream() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1.],
+        chr1:2 [-1., 0.],
+        chr2:1 [-2., 0.],
+        chr2:7 [0., -3.],
+    ];
+
+    let mut reader = reader_from_records::<V3>(1, records, 1)?;
+    assert_eq!(reader.worker_count(), NonZeroUsize::new(1));
+
+    let mut record = reader.create_record_buf();
+    for expected in &records[..2] {
+        reader.read_record(&mut record)?;
+        assert_eq!(&record.clone().to_named(reader.index()), expected);
+    }
+
+    reader.set_worker_count(NonZeroUsize::new(2).unwrap())?;
+    assert_eq!(reader.worker_count(), NonZeroUsize::new(2));
+
+    for expected in &records[2..] {
+        reader.read_record(&mut record)?;
+        assert_eq!(&record.clone().to_named(reader.index()), expected);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::io;
+
+use angsd_saf::{
+    record::{Band, Likelihoods, Record},
+    version::V4,
+};
+
+pub mod utils;
+use utils::{reader_from_writer, setup_writer, MockReader};
+
+const CUTOFF: f32 = 5.;
+
+const SITES: [(&str, u32, [f32; 5]); 3] = [
+    ("chr1", 1, [-20., -3., 0., -15., -2.]),
+    ("chr1", 4, [0., -1., -2., -30., -40.]),
+    ("chr2", 2, [-12., -11., -10., -11., 0.]),
+];
+
+fn setup_reader() -> io::Result<MockReader<V4>> {
+    let mut writer = setup_writer::<V4>(4)?;
+    for (contig, position, likelihoods) in SITES.iter() {
+        writer.write_likelihoods_as_band(contig, *position, likelihoods, CUTOFF)?;
+    }
+    reader_from_writer(writer, 1)
+}
+
+#[test]
+fn test_write_likelihoods_as_band() -> io::Result<()> {
+    let mut reader = setup_reader()?;
+
+    let expected_bands = [
+        Band::new(1, vec![-3., 0., -15., -2.]),
+        Band::new(0, vec![0., -1., -2.]),
+        Band::new(4, vec![0.]),
+    ];
+
+    let mut record = reader.create_record_buf();
+    for ((_, position, _), expected) in SITES.iter().zip(expected_bands) {
+        reader.read_record(&mut record)?;
+        assert_eq!(record.position(), *position);
+        assert_eq!(record.item(), &expected);
+    }
+    assert!(reader.read_record(&mut record)?.is_done());
+
+    Ok(())
+}
+
+#[test]
+fn test_write_likelihoods_as_band_expands_within_cutoff() -> io::Result<()> {
+    let mut reader = setup_reader()?;
+
+    // With maximum likelihoods of zero, all dropped values are below the fill
+    let fill = -CUTOFF;
+
+    let mut record = Record::new(0, 0, Likelihoods::from(Vec::new()));
+    for (_, _, likelihoods) in SITES.iter() {
+        reader.read_record_full(&mut record, fill)?;
+
+        for (&read, &input) in record.item().iter().zip(likelihoods) {
+            assert!(read == input || (read == fill && input < fill));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use angsd_saf::{record::Likelihoods, version::V3, write_sorted, Record};
+
+pub mod utils;
+use utils::{reader_from_writer, setup_writer};
+
+fn shuffled_records() -> Vec<Record<String, Likelihoods>> {
+    [
+        ("chr2", 4, -4.),
+        ("chr1", 7, -2.),
+        ("chr3", 1, -5.),
+        ("chr1", 2, -1.),
+        ("chr2", 3, -3.),
+        ("chr1", 1, 0.),
+    ]
+    .into_iter()
+    .map(|(contig, position, value)| {
+        Record::new(contig.to_string(), position, Likelihoods::from(vec![value]))
+    })
+    .collect()
+}
+
+#[test]
+fn test_write_sorted() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    write_sorted(shuffled_records(), &mut writer, |a, b| {
+        (a.contig_id(), a.position()).cmp(&(b.contig_id(), b.position()))
+    })?;
+
+    let mut reader = reader_from_writer(writer, 1)?;
+
+    let mut record = reader.create_record_buf();
+    let mut read = Vec::new();
+    while reader.read_record(&mut record)?.is_not_done() {
+        let contig = reader.index().records()[*record.contig_id()].name();
+        read.push((contig.to_string(), record.position(), record.item()[0]));
+    }
+
+    let expected = [
+        ("chr1", 1, 0.),
+        ("chr1", 2, -1.),
+        ("chr1", 7, -2.),
+        ("chr2", 3, -3.),
+        ("chr2", 4, -4.),
+        ("chr3", 1, -5.),
+    ]
+    .map(|(contig, position, value)| (contig.to_string(), position, value));
+    assert_eq!(read, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_write_sorted_by_position_only_not_grouped() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    let result = write_sorted(shuffled_records(), &mut writer, |a, b| {
+        a.position().cmp(&b.position())
+    });
+
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+#[test]
+fn test_write_sorted_contigs_only_not_sorted() -> io::Result<()> {
+    let mut writer = setup_writer::<V3>(0)?;
+    let result = write_sorted(shuffled_records(), &mut writer, |a, b| {
+        a.contig_id().cmp(b.contig_id())
+    });
+
+    assert!(result.is_err());
+
+    Ok(())
+}
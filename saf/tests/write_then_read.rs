@@ -1,12 +1,16 @@
 use std::{fmt, io};
 
 use angsd_saf::{
+    reader::Builder,
     version::{Version, V3, V4},
-    Index, Record,
+    Index, Record, Writer,
 };
 
 pub mod utils;
-use utils::{get_alleles_v3, get_alleles_v4, reader_from_records, MockReader};
+use utils::{
+    get_alleles_v3, get_alleles_v4, reader_from_records, reader_from_writer, setup_writer,
+    MockReader,
+};
 
 /// Test that contigs names and sites per contig index matches those in provided records.
 fn test_index_matches_records<V>(index: &Index<V>, records: &[Record<&str, V::Item>])
@@ -174,6 +178,29 @@ fn test_v4_two_contigs() -> io::Result<()> {
     ])
 }
 
+#[test]
+fn test_v4_two_contigs_sum_band() -> io::Result<()> {
+    let records = records_v4![
+        chr1:1 [nil, nil; 0., -1., -2.],
+        chr1:4 [nil; -2., 0.],
+        chr1:6 [-3.],
+        chr2:2 [-8., -8., -2., -1., 0.],
+        chr2:20 [nil, nil, nil; 0.],
+    ];
+
+    let mut reader = reader_from_records::<V4>(get_alleles_v4(records), records, 1)?;
+
+    let sum_bands = reader
+        .index()
+        .records()
+        .iter()
+        .map(|record| (record.name(), record.sites(), record.sum_band()))
+        .collect::<Vec<_>>();
+    assert_eq!(sum_bands, [("chr1", 3, 3 + 2 + 1), ("chr2", 2, 5 + 1)]);
+
+    test_reader_matches_records(&mut reader, records)
+}
+
 #[test]
 fn test_v3_single_record_many_contigs() -> io::Result<()> {
     test_write_read_v3(records_v3![
@@ -195,3 +222,105 @@ fn test_v4_single_record_many_contigs() -> io::Result<()> {
         chr10:1000 [nil, nil; -4.],
     ])
 }
+
+#[test]
+fn test_v3_split_writer_types() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr2:5 [-2., -1., 0.],
+    ];
+
+    let mut writer = Writer::<_, V3, _, _>::new_split(
+        Vec::new(),
+        io::Cursor::new(Vec::new()),
+        io::BufWriter::new(Vec::new()),
+    );
+    writer.write_magic()?;
+    writer.write_alleles(get_alleles_v3(records))?;
+    for record in records.iter() {
+        writer.write_record(record)?;
+    }
+
+    let (index_writer, position_writer, item_writer) = writer.finish()?;
+    let item_writer = item_writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?;
+
+    let index = Index::read(&mut index_writer.as_slice())?;
+    test_index_matches_records(&index, records);
+
+    let mut reader = Builder::v3()
+        .build(
+            index,
+            io::Cursor::new(position_writer.into_inner()),
+            io::Cursor::new(item_writer),
+        )
+        .expect("empty index");
+    reader.read_magic()?;
+    test_reader_matches_records(&mut reader, records)
+}
+
+#[test]
+fn test_v3_write_sites_from_slices() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr2:5 [-2., -1., 0.],
+        chr3:2 [-2., 0., -1.],
+    ];
+
+    let mut writer = setup_writer::<V3>(get_alleles_v3(records))?;
+    for record in records.iter() {
+        writer.write_site(record.contig_id(), record.position(), record.item())?;
+    }
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    test_index_matches_records(reader.index(), records);
+    test_reader_matches_records(&mut reader, records)
+}
+
+#[test]
+fn test_v3_dedup_positions_drops_consecutive_duplicates() -> io::Result<()> {
+    let written = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr1:2 [-2., -1., 0.],
+        chr2:2 [-2., 0., -1.],
+    ];
+    let expected = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr2:2 [-2., 0., -1.],
+    ];
+
+    let mut writer = setup_writer::<V3>(get_alleles_v3(written))?;
+    writer.set_dedup_positions(true);
+    for record in written.iter() {
+        writer.write_record(record)?;
+    }
+    assert_eq!(writer.duplicates_dropped(), 1);
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    test_index_matches_records(reader.index(), expected);
+    test_reader_matches_records(&mut reader, expected)
+}
+
+#[test]
+fn test_v3_dedup_positions_keeps_non_consecutive_duplicates() -> io::Result<()> {
+    let records = records_v3![
+        chr1:1 [0., -1., -2.],
+        chr1:2 [-1., 0., -2.],
+        chr1:1 [-2., -1., 0.],
+    ];
+
+    let mut writer = setup_writer::<V3>(get_alleles_v3(records))?;
+    writer.set_dedup_positions(true);
+    for record in records.iter() {
+        writer.write_site(record.contig_id(), record.position(), record.item())?;
+    }
+    assert_eq!(writer.duplicates_dropped(), 0);
+
+    let mut reader = reader_from_writer(writer, 1)?;
+    test_reader_matches_records(&mut reader, records)
+}